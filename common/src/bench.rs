@@ -0,0 +1,56 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    alloc::{track, AllocStats},
+    Solution,
+};
+
+/// Time taken and memory used by each stage of a solution
+#[derive(Debug, Clone)]
+pub struct Timings {
+    pub day: u8,
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+    /// What each of parsing, part 1 and part 2 allocated, if allocations are being tracked
+    pub allocs: [Option<AllocStats>; 3],
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+
+    /// Most bytes allocated at once during any stage
+    pub fn peak_bytes(&self) -> Option<usize> {
+        self.allocs.iter().flatten().map(|a| a.peak_bytes).max()
+    }
+
+    /// Allocations made across every stage
+    pub fn allocations(&self) -> Option<usize> {
+        self.allocs.iter().map(|a| a.map(|a| a.count)).sum()
+    }
+}
+
+/// Run every stage of a solution once, timing each of them
+pub fn time<S: Solution>(input: &str) -> Timings {
+    let (parse, input, parse_allocs) = time_stage(|| S::parse(input));
+    let (part1, _, part1_allocs) = time_stage(|| S::part1(&input));
+    let (part2, _, part2_allocs) = time_stage(|| S::part2(&input));
+    Timings {
+        day: S::DAY,
+        parse,
+        part1,
+        part2,
+        allocs: [parse_allocs, part1_allocs, part2_allocs],
+    }
+}
+
+fn time_stage<T>(f: impl FnOnce() -> T) -> (Duration, T, Option<AllocStats>) {
+    let ((elapsed, result), allocs) = track(|| {
+        let start = Instant::now();
+        let result = f();
+        (start.elapsed(), result)
+    });
+    (elapsed, result, allocs)
+}
@@ -0,0 +1,146 @@
+/// A fixed-size set of small integers, stored as `N` 64 bit words.
+/// Holds values in `0..N * 64`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BitSet<const N: usize> {
+    words: [u64; N],
+}
+
+impl<const N: usize> BitSet<N> {
+    /// The number of distinct values the set can hold
+    pub const CAPACITY: usize = N * 64;
+
+    pub const fn new() -> Self {
+        Self { words: [0; N] }
+    }
+
+    /// Add a value, returning whether it was newly inserted
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, mask) = Self::locate(value);
+        let inserted = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        inserted
+    }
+
+    /// Remove a value, returning whether it was present
+    pub fn remove(&mut self, value: usize) -> bool {
+        let (word, mask) = Self::locate(value);
+        let removed = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        removed
+    }
+
+    /// A copy of this set with a value added
+    pub fn with(mut self, value: usize) -> Self {
+        self.insert(value);
+        self
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        if value >= Self::CAPACITY {
+            return false;
+        }
+        let (word, mask) = Self::locate(value);
+        self.words[word] & mask != 0
+    }
+
+    /// Iterate over the values in the set, in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            (0..64)
+                .filter(move |bit| (word >> bit) & 1 == 1)
+                .map(move |bit| i * 64 + bit)
+        })
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            words: std::array::from_fn(|i| self.words[i] | other.words[i]),
+        }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self {
+            words: std::array::from_fn(|i| self.words[i] & other.words[i]),
+        }
+    }
+
+    /// The number of values in the set
+    pub fn count(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// Get the word index and bit mask for a value
+    fn locate(value: usize) -> (usize, u64) {
+        assert!(
+            value < Self::CAPACITY,
+            "{} is out of range for the bitset",
+            value
+        );
+        (value / 64, 1 << (value % 64))
+    }
+}
+
+impl<const N: usize> Default for BitSet<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> FromIterator<usize> for BitSet<N> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+impl<const N: usize> std::fmt::Debug for BitSet<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut set = BitSet::<2>::new();
+        assert!(set.is_empty());
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.insert(100));
+        assert!(set.contains(3) && set.contains(100));
+        assert!(!set.contains(4) && !set.contains(1000));
+        assert_eq!(set.count(), 2);
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![100]);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: BitSet<2> = [1, 5, 70].into_iter().collect();
+        let b: BitSet<2> = [5, 70, 127].into_iter().collect();
+        assert_eq!(a.union(&b).iter().collect::<Vec<_>>(), vec![1, 5, 70, 127]);
+        assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), vec![5, 70]);
+        assert_eq!(a.with(2).count(), 4);
+        assert_eq!(format!("{:?}", a), "{1, 5, 70}");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_out_of_range() {
+        BitSet::<1>::new().insert(64);
+    }
+}
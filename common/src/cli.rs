@@ -0,0 +1,339 @@
+//! Command line arguments shared by every day's binary, so flags behave the same everywhere
+
+use std::str::FromStr;
+
+use crate::{
+    normalize_newlines, piped_stdin, read_input, read_puzzle_input, read_stdin, Part, Solution,
+    YEAR,
+};
+
+/// A `--flag` a binary accepts besides the ones `Args` handles, with what its value is called if
+/// it takes one. Values can be given either as `--knots N` or `--knots=N`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Flag {
+    /// The flag without its leading dashes, e.g `knots`
+    pub name: &'static str,
+    /// What the flag's value is called in the usage, e.g `N`
+    pub value: Option<&'static str>,
+}
+
+impl Flag {
+    /// A flag that's either given or not, like `--json`
+    pub const fn switch(name: &'static str) -> Self {
+        Self { name, value: None }
+    }
+
+    /// A flag that's given a value, like `--knots N`
+    pub const fn with_value(name: &'static str, value: &'static str) -> Self {
+        Self {
+            name,
+            value: Some(value),
+        }
+    }
+}
+
+impl std::fmt::Display for Flag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.value {
+            Some(value) => write!(f, "--{} {}", self.name, value),
+            None => write!(f, "--{}", self.name),
+        }
+    }
+}
+
+/// Flags picked up by `common` itself when printing answers, logging, inspecting and drawing,
+/// which every binary accepts. Days add their own with `Solution::FLAGS`.
+pub const SHARED_FLAGS: &[Flag] = &[
+    Flag::switch("json"),
+    Flag::switch("verbose"),
+    Flag::switch("inspect"),
+    Flag::switch("animate"),
+    Flag::with_value("every", "N"),
+    Flag::with_value("record", "PATH"),
+    Flag::with_value("svg", "PATH"),
+];
+
+/// The shared and day specific flags a binary was given, which are handed to
+/// `Solution::parse_with`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Flags {
+    /// Whether the day is being run on its sample rather than its input
+    pub sample: bool,
+    given: Vec<(&'static str, Option<String>)>,
+}
+
+impl Flags {
+    /// Whether a flag was given
+    pub fn is_set(&self, name: &str) -> bool {
+        self.given.iter().any(|(flag, _)| *flag == name)
+    }
+
+    /// The value given to a flag, the last one if it was given more than once
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.given
+            .iter()
+            .rev()
+            .find(|(flag, _)| *flag == name)
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    /// Parse the value given to a flag, with an error naming the flag if it's invalid
+    pub fn parse<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.value(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("Invalid value {} for --{}", value, name))
+            })
+            .transpose()
+    }
+}
+
+/// What a day's binary was asked to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    /// Where to read the input from, or `-` for stdin
+    pub input: Option<String>,
+    /// Where the day's sample is, see `with_sample`
+    pub sample_path: Option<&'static str>,
+    pub year: u16,
+    pub part: Part,
+    /// Whether to read an input piped into stdin when no path is given. Only `parse` turns this
+    /// on, so tests parsing their own arguments never wait on cargo's stdin.
+    pub piped: bool,
+    /// Every other flag, including `--sample`
+    pub flags: Flags,
+}
+
+/// Run a day's binary on the input or sample its arguments ask for, printing the answers
+pub fn run<S: Solution>(sample_path: &'static str) -> Vec<(u8, String)> {
+    parse::<S>().with_sample(sample_path).run::<S>()
+}
+
+/// Parse the program's arguments for a day, printing the usage and exiting if they're invalid.
+/// Besides the `SHARED_FLAGS`, the day's own `Solution::FLAGS` are accepted.
+pub fn parse<S: Solution>() -> Args {
+    let args = parse_from(std::env::args().skip(1), S::FLAGS)
+        .unwrap_or_else(|err| exit_with_usage::<S>(&err));
+    Args {
+        piped: true,
+        ..args
+    }
+}
+
+/// Parse a list of arguments, not including the program name, accepting the `SHARED_FLAGS` and
+/// a day's own `flags`
+pub fn parse_from(args: impl IntoIterator<Item = String>, flags: &[Flag]) -> Result<Args, String> {
+    let mut parsed = Args {
+        input: None,
+        sample_path: None,
+        year: YEAR,
+        part: Part::Both,
+        piped: false,
+        flags: Flags::default(),
+    };
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--input" => parsed.input = Some(value()?),
+            "--year" => parsed.year = value()?.parse().map_err(|_| "Year must be a number")?,
+            "--part" => parsed.part = value()?.parse()?,
+            "--sample" => parsed.flags.sample = true,
+            _ if arg.starts_with("--") => {
+                let (name, inline) = match arg[2..].split_once('=') {
+                    Some((name, value)) => (name, Some(value.to_owned())),
+                    None => (&arg[2..], None),
+                };
+                let flag = SHARED_FLAGS
+                    .iter()
+                    .chain(flags)
+                    .find(|flag| flag.name == name)
+                    .ok_or(format!("Unknown argument {}", arg))?;
+                let given = match (flag.value, inline) {
+                    (None, None) => None,
+                    (None, Some(_)) => return Err(format!("--{} doesn't take a value", name)),
+                    (Some(_), Some(inline)) => Some(inline),
+                    (Some(_), None) => Some(value()?),
+                };
+                parsed.flags.given.push((flag.name, given));
+            }
+            // A lone path is the input, as it always has been
+            _ if parsed.input.is_none() => parsed.input = Some(arg),
+            _ => return Err(format!("Unexpected argument {}", arg)),
+        }
+    }
+    Ok(parsed)
+}
+
+/// The `SHARED_FLAGS` and a day's own `flags` as they're shown in a usage line
+pub fn flags_usage(flags: &[Flag]) -> String {
+    SHARED_FLAGS
+        .iter()
+        .chain(flags)
+        .map(|flag| format!("[{}]", flag))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Print an error and the usage of a day's binary, then exit
+fn exit_with_usage<S: Solution>(err: &str) -> ! {
+    eprintln!("{}", err);
+    eprintln!(
+        "Usage: day{} [PATH|-] [--input PATH|-] [--sample] [--year <YYYY>] [--part 1|2|both] {}",
+        S::DAY,
+        flags_usage(S::FLAGS)
+    );
+    std::process::exit(1);
+}
+
+impl Args {
+    /// Set where the sample read by `--sample` is, usually `sample_path!()`
+    pub fn with_sample(mut self, path: &'static str) -> Self {
+        self.sample_path = Some(path);
+        self
+    }
+
+    /// Read the input for a day: its sample with `--sample`, the given path, stdin if the path
+    /// is `-` or an input is piped in (see `piped`), or `inputs/{year}/{day}.txt` otherwise
+    pub fn read_input(&self, day: u8) -> String {
+        if self.flags.sample {
+            let path = self
+                .sample_path
+                .expect("This binary doesn't know where its sample is");
+            let sample = std::fs::read_to_string(path)
+                .unwrap_or_else(|_| panic!("Couldn't find a sample for day {} at {}", day, path));
+            return normalize_newlines(sample);
+        }
+        match self.input.as_deref() {
+            Some("-") => read_stdin(),
+            Some(path) => read_input(path, Some((self.year, day))),
+            None => self
+                .piped
+                .then(piped_stdin)
+                .flatten()
+                .unwrap_or_else(|| read_puzzle_input(self.year, day)),
+        }
+    }
+
+    /// Read the input for a solution and print the answers to the requested parts (see
+    /// `common::run`), printing the usage and exiting if the day rejects one of its flags
+    pub fn run<S: Solution>(&self) -> Vec<(u8, String)> {
+        crate::run::<S>(&self.read_input(S::DAY), self.part, &self.flags)
+            .unwrap_or_else(|err| exit_with_usage::<S>(&err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_FLAGS: &[Flag] = &[Flag::switch("descent"), Flag::with_value("knots", "N")];
+
+    fn parse_strs(args: &[&str]) -> Result<Args, String> {
+        parse_from(args.iter().map(|arg| arg.to_string()), DAY_FLAGS)
+    }
+
+    #[test]
+    fn test_parse() {
+        let args = parse_strs(&["--part", "2", "--json", "--input", "in.txt"]).unwrap();
+        assert_eq!(
+            args,
+            Args {
+                input: Some("in.txt".into()),
+                sample_path: None,
+                year: YEAR,
+                part: Part::Two,
+                piped: false,
+                flags: Flags {
+                    sample: false,
+                    given: vec![("json", None)],
+                },
+            }
+        );
+        let args = parse_strs(&["-", "--year", "2021", "--svg=out.svg"]).unwrap();
+        assert_eq!(args.input.as_deref(), Some("-"));
+        assert_eq!(args.year, 2021);
+        assert_eq!(args.part, Part::Both);
+        assert!(parse_strs(&["--sample"]).unwrap().flags.sample);
+    }
+
+    #[test]
+    fn test_parse_flags() {
+        let flags = parse_strs(&["--animate", "--descent", "--every=10", "--record", "a.gif"])
+            .unwrap()
+            .flags;
+        assert!(flags.is_set("animate") && flags.is_set("descent"));
+        assert!(!flags.is_set("json"));
+        assert_eq!(flags.value("every"), Some("10"));
+        assert_eq!(flags.value("record"), Some("a.gif"));
+
+        // Both forms of a value leave the input path alone
+        let args = parse_strs(&["in.txt", "--knots", "3"]).unwrap();
+        assert_eq!(args.input.as_deref(), Some("in.txt"));
+        assert_eq!(args.flags.parse::<usize>("knots"), Ok(Some(3)));
+        let flags = parse_strs(&["--knots=4", "--knots=5"]).unwrap().flags;
+        assert_eq!(flags.parse::<usize>("knots"), Ok(Some(5)));
+        assert_eq!(flags.parse::<usize>("every"), Ok(None));
+        let flags = parse_strs(&["--knots=many"]).unwrap().flags;
+        assert_eq!(
+            flags.parse::<usize>("knots"),
+            Err("Invalid value many for --knots".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_read_sample() {
+        let args = parse_strs(&["--sample"])
+            .unwrap()
+            .with_sample(concat!(env!("CARGO_MANIFEST_DIR"), "/crlf_input.txt"));
+        assert_eq!(args.read_input(1), "hello\nworld!\n\n");
+    }
+
+    #[test]
+    fn test_only_parse_reads_piped_input() {
+        assert!(!parse_strs(&[]).unwrap().piped);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_strs(&["--bogus"]).unwrap_err(),
+            "Unknown argument --bogus"
+        );
+        assert_eq!(
+            parse_strs(&["--part"]).unwrap_err(),
+            "Missing value for --part"
+        );
+        assert_eq!(
+            parse_strs(&["--part", "3"]).unwrap_err(),
+            "Part must be one of 1, 2 or both"
+        );
+        assert_eq!(
+            parse_strs(&["a.txt", "b.txt"]).unwrap_err(),
+            "Unexpected argument b.txt"
+        );
+        assert_eq!(
+            parse_strs(&["--knots"]).unwrap_err(),
+            "Missing value for --knots"
+        );
+        assert_eq!(
+            parse_strs(&["--descent=yes"]).unwrap_err(),
+            "--descent doesn't take a value"
+        );
+        assert_eq!(
+            parse_from(vec!["--descent".to_owned()], &[]).unwrap_err(),
+            "Unknown argument --descent"
+        );
+    }
+
+    #[test]
+    fn test_flags_usage() {
+        assert_eq!(
+            flags_usage(DAY_FLAGS),
+            "[--json] [--verbose] [--inspect] [--animate] [--every N] [--record PATH] \
+             [--svg PATH] [--descent] [--knots N]"
+        );
+    }
+}
@@ -0,0 +1,29 @@
+//! Maps and sets for the day crates. `Map` and `Set` always iterate in the same order from run
+//! to run so debug output and tie-breaking are reproducible, while `FastMap` and `FastSet` are
+//! for hot lookups where the order doesn't matter.
+
+/// A hash map with a fixed hasher, so the same inserts always iterate in the same order
+pub type Map<K, V> = rustc_hash::FxHashMap<K, V>;
+
+/// A hash set with a fixed hasher, so the same inserts always iterate in the same order
+pub type Set<T> = rustc_hash::FxHashSet<T>;
+
+/// A hash map with a quicker hasher than std's, seeded differently each run
+pub type FastMap<K, V> = std::collections::HashMap<K, V, ahash::RandomState>;
+
+/// A hash set with a quicker hasher than std's, seeded differently each run
+pub type FastSet<T> = std::collections::HashSet<T, ahash::RandomState>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iteration_order_is_fixed() {
+        let order = || (0..100).map(|i| i * 7919 % 1000).collect::<Set<u32>>();
+        let first: Vec<_> = order().into_iter().collect();
+        for _ in 0..5 {
+            assert_eq!(order().into_iter().collect::<Vec<_>>(), first);
+        }
+    }
+}
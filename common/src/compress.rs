@@ -0,0 +1,40 @@
+/// Squash sparse coordinates down to dense indices which keep their order.
+/// Returns the distinct values in ascending order, which maps an index back to its value,
+/// and the index of each value that was given, in the order they were given.
+/// ```ignore
+/// let (mapping, ranks) = compress_coords([1000, -5, 1000, 20]);
+/// assert_eq!(mapping, vec![-5, 20, 1000]);
+/// assert_eq!(ranks, vec![2, 0, 2, 1]);
+/// ```
+pub fn compress_coords<T: Ord + Clone>(
+    values: impl IntoIterator<Item = T>,
+) -> (Vec<T>, Vec<usize>) {
+    let values: Vec<T> = values.into_iter().collect();
+
+    // Sort the distinct values
+    let mut mapping = values.clone();
+    mapping.sort_unstable();
+    mapping.dedup();
+
+    // Look up where each value ended up
+    let ranks = values
+        .iter()
+        .map(|value| mapping.binary_search(value).unwrap())
+        .collect();
+    (mapping, ranks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compress_coords() {
+        let (mapping, ranks) = compress_coords([1000, -5, 1000, 20]);
+        assert_eq!(mapping, vec![-5, 20, 1000]);
+        assert_eq!(ranks, vec![2, 0, 2, 1]);
+
+        let (mapping, ranks) = compress_coords(Vec::<u64>::new());
+        assert!(mapping.is_empty() && ranks.is_empty());
+    }
+}
@@ -0,0 +1,115 @@
+//! Finding where a sequence starts repeating, to skip ahead billions of steps
+
+use std::{
+    hash::Hash,
+    ops::{Add, Mul, Sub},
+};
+
+use crate::FastMap;
+
+/// Find the first state that repeats, reading states until one does.
+/// Returns the `offset` of its first appearance and the `period` until it appears again.
+pub fn find_cycle<T: Hash + Eq>(states: impl IntoIterator<Item = T>) -> Option<(usize, usize)> {
+    let mut seen = FastMap::default();
+    for (i, state) in states.into_iter().enumerate() {
+        if let Some(first) = seen.insert(state, i) {
+            return Some((first, i - first));
+        }
+    }
+    None
+}
+
+/// Find the cycle reached by repeatedly applying `step` to `start`, using Brent's algorithm.
+/// Only a couple of states are kept at once, so states don't need to be hashable.
+/// Returns the `offset` of the first state in the cycle and the cycle's `period`.
+pub fn brent<T: Clone + PartialEq>(start: T, step: impl Fn(&T) -> T) -> (usize, usize) {
+    // Find the period by racing a hare ahead of a tortoise which teleports to it
+    // each time it has gone a power of two steps
+    let (mut power, mut period) = (1, 1);
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    // Find the offset by moving both a period apart until they meet
+    let mut tortoise = start.clone();
+    let mut hare = (0..period).fold(start, |state, _| step(&state));
+    let mut offset = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        offset += 1;
+    }
+    (offset, period)
+}
+
+/// Find the `n`th of a sequence of values that grows by the same amount every period once the
+/// cycle starts, e.g the height of a tower after `n` rocks.
+/// `values` must have been recorded for at least a full period after `offset`.
+pub fn extrapolate<T>(n: usize, offset: usize, period: usize, values: &[T]) -> T
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + TryFrom<usize>,
+{
+    if n < values.len() {
+        return values[n];
+    }
+    assert!(
+        values.len() > offset + period,
+        "Need values for a full period after the offset to extrapolate"
+    );
+    let growth = values[offset + period] - values[offset];
+    let (cycles, remainder) = ((n - offset) / period, (n - offset) % period);
+    let cycles = T::try_from(cycles)
+        .ok()
+        .expect("Too many cycles to fit in the value type");
+    values[offset + remainder] + growth * cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A function that reaches a cycle of length 6 after 3 steps
+    fn step(&x: &u32) -> u32 {
+        if x < 3 {
+            x + 1
+        } else {
+            3 + (x - 3 + 1) % 6
+        }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let states = std::iter::successors(Some(0), |x| Some(step(x)));
+        assert_eq!(find_cycle(states), Some((3, 6)));
+        assert_eq!(find_cycle([1, 2, 3]), None);
+        assert_eq!(find_cycle("abcdbc".chars()), Some((1, 3)));
+    }
+
+    #[test]
+    fn test_brent() {
+        assert_eq!(brent(0, step), (3, 6));
+        assert_eq!(brent(5, step), (0, 6));
+        assert_eq!(brent(7, |&x: &u8| x), (0, 1));
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // Grows 1, 2, then 3 then 4 every two steps forever
+        let values: Vec<i64> = vec![0, 1, 3, 6, 10, 13, 17, 20];
+        assert_eq!(extrapolate(5, 2, 2, &values), 13);
+        assert_eq!(extrapolate(8, 2, 2, &values), 24);
+        assert_eq!(extrapolate(9, 2, 2, &values), 27);
+        assert_eq!(
+            extrapolate(1_000_000_000_000, 2, 2, &values),
+            3_500_000_000_000 - 4
+        );
+    }
+}
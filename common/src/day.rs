@@ -0,0 +1,90 @@
+//! Naming for day crates, which are a mix of `day4` and `day05` styles
+
+use std::{fmt::Display, str::FromStr};
+
+/// A puzzle day from 1 to 25, the canonical way to refer to a day crate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Day(u8);
+
+impl Day {
+    pub fn new(day: u8) -> Option<Self> {
+        (1..=25).contains(&day).then_some(Self(day))
+    }
+
+    pub fn number(self) -> u8 {
+        self.0
+    }
+
+    /// Find the day a crate or its directory is named after, e.g `day4`, `day04` or `day16`
+    pub fn from_crate_name(name: &str) -> Option<Self> {
+        let digits = name.strip_prefix("day")?;
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        Self::new(digits.parse().ok()?)
+    }
+
+    /// The package name of the day's crate, e.g `day4`
+    pub fn crate_name(self) -> String {
+        format!("day{}", self.0)
+    }
+
+    /// The directory of the day's crate, e.g `day04`
+    pub fn dir_name(self) -> String {
+        format!("day{:02}", self.0)
+    }
+}
+
+impl FromStr for Day {
+    type Err = String;
+
+    /// Parse a day number such as `4` or a crate name such as `day04`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse()
+            .ok()
+            .and_then(Self::new)
+            .or_else(|| Self::from_crate_name(s))
+            .ok_or_else(|| format!("{} isn't a day from 1 to 25", s))
+    }
+}
+
+impl Display for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<Day> for u8 {
+    fn from(day: Day) -> Self {
+        day.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_crate_name() {
+        assert_eq!(Day::from_crate_name("day4"), Day::new(4));
+        assert_eq!(Day::from_crate_name("day05"), Day::new(5));
+        assert_eq!(Day::from_crate_name("day16"), Day::new(16));
+        assert_eq!(Day::from_crate_name("day0"), None);
+        assert_eq!(Day::from_crate_name("day26"), None);
+        assert_eq!(Day::from_crate_name("day+1"), None);
+        assert_eq!(Day::from_crate_name("common"), None);
+    }
+
+    #[test]
+    fn test_names() {
+        let day = Day::new(4).unwrap();
+        assert_eq!(day.crate_name(), "day4");
+        assert_eq!(day.dir_name(), "day04");
+        assert_eq!("04".parse(), Ok(day));
+        assert_eq!("day04".parse(), Ok(day));
+        assert_eq!(
+            "day".parse::<Day>(),
+            Err("day isn't a day from 1 to 25".to_owned())
+        );
+    }
+}
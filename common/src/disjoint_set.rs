@@ -0,0 +1,118 @@
+/// Groups of the elements `0..len`, which can be merged and looked up in near constant time
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    set_count: usize,
+}
+
+impl DisjointSet {
+    /// Every element in a set of its own
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            set_count: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// The representative element of the set containing `element`
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        // Point everything on the way straight at the root
+        let mut current = element;
+        while self.parents[current] != root {
+            current = std::mem::replace(&mut self.parents[current], root);
+        }
+        root
+    }
+
+    /// Merge the sets containing two elements, returning whether they were separate
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        // Attach the smaller set to the larger
+        let (larger, smaller) = if self.sizes[a] >= self.sizes[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parents[smaller] = larger;
+        self.sizes[larger] += self.sizes[smaller];
+        self.set_count -= 1;
+        true
+    }
+
+    pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// How many elements are in the same set as `element`
+    pub fn set_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    pub fn set_count(&self) -> usize {
+        self.set_count
+    }
+
+    /// The elements of each set, in order of their smallest element
+    pub fn sets(&mut self) -> impl Iterator<Item = Vec<usize>> {
+        let mut set_indices = vec![None; self.len()];
+        let mut sets: Vec<Vec<usize>> = Vec::with_capacity(self.set_count);
+        for element in 0..self.len() {
+            let root = self.find(element);
+            let index = *set_indices[root].get_or_insert_with(|| {
+                sets.push(Vec::new());
+                sets.len() - 1
+            });
+            sets[index].push(element);
+        }
+        sets.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_find() {
+        let mut set = DisjointSet::new(6);
+        assert_eq!(set.set_count(), 6);
+        assert!(set.union(0, 1));
+        assert!(set.union(3, 4));
+        assert!(set.union(1, 4));
+        assert!(!set.union(0, 3));
+        assert_eq!(set.set_count(), 3);
+        assert!(set.same_set(0, 4));
+        assert!(!set.same_set(2, 5));
+        assert_eq!(set.set_size(3), 4);
+        assert_eq!(set.set_size(5), 1);
+    }
+
+    #[test]
+    fn test_sets() {
+        let mut set = DisjointSet::new(5);
+        set.union(4, 1);
+        set.union(2, 0);
+        let sets: Vec<_> = set.sets().collect();
+        assert_eq!(sets, vec![vec![0, 2], vec![1, 4], vec![3]]);
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt::Display;
+
+/// Errors from reading and solving puzzles
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AocError {
+    /// Input didn't match the expected format, at a 1-based line and column
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// Failed to read or fetch an input
+    Io(String),
+    /// Input was well formed but doesn't make sense for the puzzle
+    BadInput(String),
+    /// No answer could be found for the input
+    Unsolvable(String),
+}
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+impl AocError {
+    /// A parse error at the start of the input
+    pub fn parse(message: impl Into<String>) -> Self {
+        Self::Parse {
+            line: 1,
+            column: 1,
+            message: message.into(),
+        }
+    }
+
+    /// A parse error at the point where `remaining` starts, where `remaining` is a slice of `input`
+    pub fn parse_at(input: &str, remaining: &str, message: impl Into<String>) -> Self {
+        let offset = (remaining.as_ptr() as usize)
+            .saturating_sub(input.as_ptr() as usize)
+            .min(input.len());
+        let consumed = input.get(..offset).unwrap_or(input);
+        Self::Parse {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed.rsplit('\n').next().unwrap_or("").chars().count() + 1,
+            message: message.into(),
+        }
+    }
+
+    /// Move a parse error from a piece of the input further down by some number of lines,
+    /// to give its position in the whole input
+    pub fn offset_lines(self, lines: usize) -> Self {
+        match self {
+            Self::Parse {
+                line,
+                column,
+                message,
+            } => Self::Parse {
+                line: line + lines,
+                column,
+                message,
+            },
+            other => other,
+        }
+    }
+
+    /// Note which blank line separated block of the input a parse error came from
+    pub fn in_block(self, block: usize) -> Self {
+        match self {
+            Self::Parse {
+                line,
+                column,
+                message,
+            } => Self::Parse {
+                line,
+                column,
+                message: format!("{} (in block {})", message, block),
+            },
+            other => other,
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "Parse error at {}:{}: {}", line, column, message),
+            AocError::Io(message) => write!(f, "IO error: {}", message),
+            AocError::BadInput(message) => write!(f, "Bad input: {}", message),
+            AocError::Unsolvable(message) => write!(f, "Unsolvable: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+impl From<std::io::Error> for AocError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err.to_string())
+    }
+}
+
+impl From<std::num::ParseIntError> for AocError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        Self::parse(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_at() {
+        let input = "abc\ndef\nghi";
+        let err = AocError::parse_at(input, &input[9..], "bad");
+        assert_eq!(
+            err,
+            AocError::Parse {
+                line: 3,
+                column: 2,
+                message: "bad".into()
+            }
+        );
+        assert_eq!(err.to_string(), "Parse error at 3:2: bad");
+    }
+
+    #[test]
+    fn test_offset_lines() {
+        let err = AocError::parse("bad").offset_lines(4);
+        assert_eq!(err.to_string(), "Parse error at 5:1: bad");
+        let err = AocError::BadInput("empty".into()).offset_lines(4);
+        assert_eq!(err.to_string(), "Bad input: empty");
+    }
+}
@@ -1,4 +1,6 @@
-trait Grid<T> {
+use crate::Direction;
+
+pub trait Grid<T> {
     /// Get a reference to the value in a cell
     fn get(&self, x: usize, y: usize) -> Option<&T>;
 
@@ -22,42 +24,208 @@ trait Grid<T> {
         self.width() * self.height()
     }
 
-    /// Iterate over cell value references, row by row
-    fn iter_rows(&self) -> GridIterator<T, Self>
+    /// Iterate over every cell with its coordinates, row by row from the top left
+    fn iter_with_coords<'a>(&'a self) -> impl Iterator<Item = (usize, usize, &'a T)>
     where
         Self: std::marker::Sized,
+        T: 'a,
     {
-        GridIterator {
-            grid: self,
-            x: 0,
-            y: 0,
-            by_rows: true,
-            marker: std::marker::PhantomData,
-        }
+        (0..self.height())
+            .flat_map(move |y| (0..self.width()).map(move |x| (x, y, self.get(x, y).unwrap())))
+    }
+
+    /// Iterate over each row from the top, each going from left to right
+    fn rows<'a>(
+        &'a self,
+    ) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &'a T> + ExactSizeIterator>
+    where
+        Self: std::marker::Sized,
+        T: 'a,
+    {
+        (0..self.height()).map(move |y| (0..self.width()).map(move |x| self.get(x, y).unwrap()))
+    }
+
+    /// Iterate over each column from the left, each going from top to bottom
+    fn cols<'a>(
+        &'a self,
+    ) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &'a T> + ExactSizeIterator>
+    where
+        Self: std::marker::Sized,
+        T: 'a,
+    {
+        (0..self.width()).map(move |x| (0..self.height()).map(move |y| self.get(x, y).unwrap()))
+    }
+
+    /// Iterate over each diagonal going down and to the right, starting from the bottom left
+    /// corner, up the left edge and then along the top edge
+    fn diagonals<'a>(&'a self) -> impl Iterator<Item = impl Iterator<Item = &'a T>>
+    where
+        Self: std::marker::Sized,
+        T: 'a,
+    {
+        let (width, height) = (self.width(), self.height());
+        (0..diagonal_count(width, height)).map(move |i| {
+            let (x, y) = if i < height {
+                (0, height - 1 - i)
+            } else {
+                (i + 1 - height, 0)
+            };
+            let len = (width - x).min(height - y);
+            (0..len).map(move |k| self.get(x + k, y + k).unwrap())
+        })
     }
 
-    /// Iterate over cell value references, column by column
-    fn iter_cols(&self) -> GridIterator<T, Self>
+    /// Iterate over each diagonal going down and to the left, starting from the top left
+    /// corner, along the top edge and then down the right edge
+    fn anti_diagonals<'a>(&'a self) -> impl Iterator<Item = impl Iterator<Item = &'a T>>
     where
         Self: std::marker::Sized,
+        T: 'a,
     {
-        GridIterator {
+        let (width, height) = (self.width(), self.height());
+        (0..diagonal_count(width, height)).map(move |i| {
+            let x = i.min(width - 1);
+            let y = i - x;
+            let len = (x + 1).min(height - y);
+            (0..len).map(move |k| self.get(x - k, y + k).unwrap())
+        })
+    }
+
+    /// Iterate over the cells in a straight line from a cell to the edge of the grid, not
+    /// including the cell itself
+    fn ray(&self, from: (usize, usize), direction: Direction) -> Ray<'_, T, Self>
+    where
+        Self: std::marker::Sized,
+    {
+        Ray {
             grid: self,
-            x: 0,
-            y: 0,
-            by_rows: false,
-            marker: std::marker::PhantomData,
+            position: from,
+            direction,
+            cell: std::marker::PhantomData,
         }
     }
+
+    /// Iterate over the in-bounds coordinates orthogonally adjacent to a cell
+    fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_
+    where
+        Self: std::marker::Sized,
+    {
+        offset_neighbors(self, x, y, &ORTHOGONAL_OFFSETS)
+    }
+
+    /// Iterate over the in-bounds coordinates orthogonally or diagonally adjacent to a cell
+    fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_
+    where
+        Self: std::marker::Sized,
+    {
+        offset_neighbors(self, x, y, &ALL_OFFSETS)
+    }
+
+    /// Iterate over the cells orthogonally adjacent to a cell, with their coordinates
+    fn neighbor_cells4<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+    ) -> impl Iterator<Item = (usize, usize, &'a T)> + 'a
+    where
+        Self: std::marker::Sized,
+        T: 'a,
+    {
+        self.neighbors4(x, y)
+            .flat_map(move |(x, y)| self.get(x, y).map(|value| (x, y, value)))
+    }
+
+    /// Iterate over the cells orthogonally or diagonally adjacent to a cell, with their coordinates
+    fn neighbor_cells8<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+    ) -> impl Iterator<Item = (usize, usize, &'a T)> + 'a
+    where
+        Self: std::marker::Sized,
+        T: 'a,
+    {
+        self.neighbors8(x, y)
+            .flat_map(move |(x, y)| self.get(x, y).map(|value| (x, y, value)))
+    }
+}
+
+/// Offsets to orthogonal neighbors, clockwise from up
+const ORTHOGONAL_OFFSETS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets to orthogonal and diagonal neighbors, clockwise from up
+const ALL_OFFSETS: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// How many diagonals there are in each direction across a grid
+fn diagonal_count(width: usize, height: usize) -> usize {
+    if width == 0 || height == 0 {
+        0
+    } else {
+        width + height - 1
+    }
+}
+
+fn offset_neighbors<'a, T, G: Grid<T>>(
+    grid: &'a G,
+    x: usize,
+    y: usize,
+    offsets: &'static [(isize, isize)],
+) -> impl Iterator<Item = (usize, usize)> + 'a {
+    offsets.iter().flat_map(move |&(dx, dy)| {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        grid.in_bounds(x, y).then_some((x, y))
+    })
 }
 
-struct VecGrid<T> {
+/// The cells in a straight line across a grid, with their coordinates
+pub struct Ray<'a, T, G> {
+    grid: &'a G,
+    position: (usize, usize),
+    direction: Direction,
+    cell: std::marker::PhantomData<&'a T>,
+}
+
+impl<'a, T: 'a, G: Grid<T>> Ray<'a, T, G> {
+    /// Stop after the first cell for which `stop` returns true, including that cell
+    pub fn until(
+        self,
+        mut stop: impl FnMut(&T) -> bool,
+    ) -> impl Iterator<Item = (usize, usize, &'a T)> {
+        let mut stopped = false;
+        self.take_while(move |&(_, _, cell)| !std::mem::replace(&mut stopped, stop(cell)))
+    }
+}
+
+impl<'a, T: 'a, G: Grid<T>> Iterator for Ray<'a, T, G> {
+    type Item = (usize, usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.direction.offset();
+        let x = self.position.0.checked_add_signed(offset.x)?;
+        let y = self.position.1.checked_add_signed(offset.y)?;
+        let cell = self.grid.get(x, y)?;
+        self.position = (x, y);
+        Some((x, y, cell))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VecGrid<T> {
     cells: Vec<T>,
     width: usize,
     height: usize,
 }
 
-#[allow(dead_code)]
 impl<T> VecGrid<T> {
     pub fn new(width: usize, height: usize) -> Self
     where
@@ -70,9 +238,82 @@ impl<T> VecGrid<T> {
         }
     }
 
+    /// Create a grid from cell values given row by row
+    pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "Grid cells don't match its size"
+        );
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Create a grid by computing the value of each cell from its coordinates
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Self::from_cells(width, height, cells)
+    }
+
     fn index(&self, x: usize, y: usize) -> usize {
         self.width * y + x
     }
+
+    /// A copy of the grid turned a quarter turn clockwise
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self.cells[self.index(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    /// A copy of the grid turned a quarter turn anticlockwise
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self.cells[self.index(self.width - 1 - y, x)].clone()
+        })
+    }
+
+    /// A copy of the grid with its rows and columns swapped
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self.cells[self.index(y, x)].clone()
+        })
+    }
+
+    /// A copy of the grid mirrored left to right
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.width, self.height, |x, y| {
+            self.cells[self.index(self.width - 1 - x, y)].clone()
+        })
+    }
+
+    /// A copy of the grid mirrored top to bottom
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.width, self.height, |x, y| {
+            self.cells[self.index(x, self.height - 1 - y)].clone()
+        })
+    }
 }
 
 impl<T> Grid<T> for VecGrid<T> {
@@ -86,7 +327,7 @@ impl<T> Grid<T> for VecGrid<T> {
     }
 
     fn in_bounds(&self, x: usize, y: usize) -> bool {
-        x > 0 && x < self.width && y > 0 && y < self.height
+        x < self.width && y < self.height
     }
 
     fn width(&self) -> usize {
@@ -106,53 +347,16 @@ impl<T> Grid<T> for VecGrid<T> {
     }
 }
 
-struct GridIterator<'a, T, G>
-where
-    G: Grid<T>,
-{
-    marker: std::marker::PhantomData<T>,
-    grid: &'a G,
-    x: usize,
-    y: usize,
-    by_rows: bool,
-}
-
-impl<'a, T: 'a, G: Grid<T>> Iterator for GridIterator<'a, T, G> {
-    type Item = &'a T;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        // Move in grid
-        if !self.by_rows {
-            self.x += 1;
-            if self.x > self.grid.width() {
-                self.x = 0;
-                self.y += 1;
-            }
-        } else {
-            self.y += 1;
-            if self.y > self.grid.height() {
-                self.y = 0;
-                self.x += 1;
-            }
-        }
-
-        // Return current item if applicable
-        self.grid.get(self.x, self.y)
-    }
-}
-
-struct VecGridTripleIterator<T> {
+pub struct VecGridTripleIterator<T> {
     grid_width: usize,
-    cells: Vec<T>,
-    offset: usize,
+    cells: std::iter::Enumerate<std::vec::IntoIter<T>>,
 }
 
 impl<T> VecGridTripleIterator<T> {
     fn new(grid: VecGrid<T>) -> Self {
         Self {
             grid_width: grid.width(),
-            cells: grid.cells,
-            offset: 0,
+            cells: grid.cells.into_iter().enumerate(),
         }
     }
 }
@@ -161,10 +365,9 @@ impl<T> Iterator for VecGridTripleIterator<T> {
     type Item = (usize, usize, T);
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.offset += 1;
-        self.cells.pop().map(|value| {
-            let x = self.offset % self.grid_width;
-            let y = self.offset / self.grid_width;
+        self.cells.next().map(|(offset, value)| {
+            let x = offset % self.grid_width;
+            let y = offset / self.grid_width;
             (x, y, value)
         })
     }
@@ -178,3 +381,214 @@ impl<T> IntoIterator for VecGrid<T> {
         VecGridTripleIterator::new(self)
     }
 }
+
+/// Cell values that can be read from a single character of a textual grid
+pub trait FromGridChar: Sized {
+    fn from_grid_char(c: char) -> Option<Self>;
+}
+
+impl FromGridChar for char {
+    fn from_grid_char(c: char) -> Option<Self> {
+        Some(c)
+    }
+}
+
+macro_rules! impl_from_grid_char_for_digits {
+    ($($t: ty),*) => {
+        $(
+            impl FromGridChar for $t {
+                fn from_grid_char(c: char) -> Option<Self> {
+                    c.to_digit(10).map(|digit| digit as $t)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_grid_char_for_digits!(u8, u32, u64, usize, i32, i64, isize);
+
+impl<T: FromGridChar> std::str::FromStr for VecGrid<T> {
+    type Err = &'static str;
+
+    /// Parse a grid with one row per line, e.g a grid of digits or of characters
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s
+            .lines()
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        let width = rows.first().map(|row| row.chars().count()).unwrap_or(0);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for row in rows {
+            if row.chars().count() != width {
+                return Err("Grid rows have differing lengths");
+            }
+            for c in row.chars() {
+                cells.push(T::from_grid_char(c).ok_or("Unexpected character in grid")?);
+            }
+        }
+        Ok(Self::from_cells(width, height, cells))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_bounds() {
+        let grid: VecGrid<u8> = VecGrid::new(3, 2);
+        assert!(grid.in_bounds(0, 0));
+        assert!(grid.in_bounds(2, 1));
+        assert!(!grid.in_bounds(3, 0));
+        assert!(!grid.in_bounds(0, 2));
+    }
+
+    #[test]
+    fn test_parse_digit_grid() {
+        let grid: VecGrid<u8> = "303\n255\n".parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(0, 0), Some(&3));
+        assert_eq!(grid.get(2, 1), Some(&5));
+        assert!("30\n255".parse::<VecGrid<u8>>().is_err());
+        assert!("3a3".parse::<VecGrid<u8>>().is_err());
+    }
+
+    #[test]
+    fn test_parse_char_grid() {
+        let grid: VecGrid<char> = "Sab\ncdE".parse().unwrap();
+        assert_eq!(grid.get(0, 0), Some(&'S'));
+        assert_eq!(grid.get(2, 1), Some(&'E'));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid: VecGrid<u8> = "123\n456\n789".parse().unwrap();
+        assert_eq!(
+            grid.neighbors4(0, 0).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors4(1, 1).count(), 4);
+        assert_eq!(
+            grid.neighbors8(2, 2).collect::<Vec<_>>(),
+            vec![(2, 1), (1, 2), (1, 1)]
+        );
+        assert_eq!(grid.neighbors8(1, 1).count(), 8);
+        assert_eq!(
+            grid.neighbor_cells4(1, 0)
+                .map(|(_, _, &value)| value)
+                .collect::<Vec<_>>(),
+            vec![3, 5, 1]
+        );
+        assert_eq!(
+            grid.neighbor_cells8(0, 0)
+                .map(|(_, _, &value)| value)
+                .sum::<u8>(),
+            2 + 5 + 4
+        );
+    }
+
+    /// Collect each line of cells from a grid iterator
+    fn lines<'a>(lines: impl Iterator<Item = impl Iterator<Item = &'a u8>>) -> Vec<Vec<u8>> {
+        lines.map(|line| line.copied().collect()).collect()
+    }
+
+    #[test]
+    fn test_iteration_order() {
+        let grid: VecGrid<u8> = "12\n34".parse().unwrap();
+        assert_eq!(
+            grid.iter_with_coords().collect::<Vec<_>>(),
+            vec![(0, 0, &1), (1, 0, &2), (0, 1, &3), (1, 1, &4)]
+        );
+        assert_eq!(lines(grid.rows()), vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(lines(grid.cols()), vec![vec![1, 3], vec![2, 4]]);
+        assert_eq!(
+            grid.rows().nth(1).unwrap().rev().collect::<Vec<_>>(),
+            vec![&4, &3]
+        );
+        assert_eq!(
+            grid.into_iter().collect::<Vec<_>>(),
+            vec![(0, 0, 1), (1, 0, 2), (0, 1, 3), (1, 1, 4)]
+        );
+    }
+
+    #[test]
+    fn test_transformations() {
+        let grid: VecGrid<char> = "ab\ncd\nef".parse().unwrap();
+        let parse = |s: &str| s.parse::<VecGrid<char>>().unwrap();
+        assert_eq!(grid.rotate_cw(), parse("eca\nfdb"));
+        assert_eq!(grid.rotate_ccw(), parse("bdf\nace"));
+        assert_eq!(grid.transpose(), parse("ace\nbdf"));
+        assert_eq!(grid.flip_horizontal(), parse("ba\ndc\nfe"));
+        assert_eq!(grid.flip_vertical(), parse("ef\ncd\nab"));
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(
+            grid.rotate_cw().rotate_cw(),
+            grid.flip_horizontal().flip_vertical()
+        );
+
+        let row: VecGrid<char> = "abc".parse().unwrap();
+        assert_eq!(row.rotate_cw(), parse("a\nb\nc"));
+        assert_eq!(row.transpose().transpose(), row);
+    }
+
+    #[test]
+    fn test_lines_of_thin_grids() {
+        let row: VecGrid<u8> = "123".parse().unwrap();
+        assert_eq!(lines(row.rows()), vec![vec![1, 2, 3]]);
+        assert_eq!(lines(row.cols()), vec![vec![1], vec![2], vec![3]]);
+        assert_eq!(lines(row.diagonals()), vec![vec![1], vec![2], vec![3]]);
+        assert_eq!(lines(row.anti_diagonals()), vec![vec![1], vec![2], vec![3]]);
+
+        let column: VecGrid<u8> = "1\n2\n3".parse().unwrap();
+        assert_eq!(lines(column.rows()), vec![vec![1], vec![2], vec![3]]);
+        assert_eq!(lines(column.cols()), vec![vec![1, 2, 3]]);
+        assert_eq!(lines(column.diagonals()), vec![vec![3], vec![2], vec![1]]);
+        assert_eq!(
+            lines(column.anti_diagonals()),
+            vec![vec![1], vec![2], vec![3]]
+        );
+        assert_eq!(
+            column.iter_with_coords().collect::<Vec<_>>(),
+            vec![(0, 0, &1), (0, 1, &2), (0, 2, &3)]
+        );
+
+        let empty: VecGrid<u8> = VecGrid::new(0, 0);
+        assert_eq!(empty.rows().count(), 0);
+        assert_eq!(empty.diagonals().count(), 0);
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid: VecGrid<u8> = "123\n456\n789".parse().unwrap();
+        let cells = |ray: Ray<'_, u8, VecGrid<u8>>| ray.map(|(_, _, &c)| c).collect::<Vec<_>>();
+        assert_eq!(cells(grid.ray((0, 1), Direction::Right)), vec![5, 6]);
+        assert_eq!(cells(grid.ray((2, 2), Direction::Up)), vec![6, 3]);
+        assert_eq!(cells(grid.ray((0, 0), Direction::Left)), vec![]);
+        assert_eq!(
+            grid.ray((1, 0), Direction::Down).collect::<Vec<_>>(),
+            vec![(1, 1, &5), (1, 2, &8)]
+        );
+        assert_eq!(
+            grid.ray((2, 0), Direction::Left).until(|&c| c >= 1).count(),
+            1
+        );
+        assert_eq!(
+            grid.ray((0, 0), Direction::Right).until(|&c| c > 9).count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let grid: VecGrid<u8> = "123\n456".parse().unwrap();
+        assert_eq!(
+            lines(grid.diagonals()),
+            vec![vec![4], vec![1, 5], vec![2, 6], vec![3]]
+        );
+        assert_eq!(
+            lines(grid.anti_diagonals()),
+            vec![vec![1], vec![2, 4], vec![3, 5], vec![6]]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::Day;
+
+/// The year of the advent calendar these solutions are for, unless another is chosen
+pub const YEAR: u16 = 2022;
+
+/// Environment variable holding the adventofcode.com session cookie
+const SESSION_VAR: &str = "AOC_SESSION";
+
+/// User agent sent with every request to adventofcode.com
+pub(crate) const USER_AGENT: &str = "github.com/giraugh/advent-of-code-2022-rust";
+
+/// Get the session cookie header value from `AOC_SESSION`
+pub(crate) fn session_cookie() -> Result<String, String> {
+    std::env::var(SESSION_VAR)
+        .map(|session| format!("session={}", session.trim()))
+        .map_err(|_| format!("set {} to talk to adventofcode.com", SESSION_VAR))
+}
+
+/// Get the day number from a day crate's package name, e.g `day16` or `day05`
+pub fn day_from_package_name(name: &str) -> Option<u8> {
+    Day::from_crate_name(name).map(Day::number)
+}
+
+/// Turn Windows line endings into plain `\n`s, which is what every parser expects
+pub fn normalize_newlines(input: String) -> String {
+    if input.contains('\r') {
+        input.replace("\r\n", "\n")
+    } else {
+        input
+    }
+}
+
+/// Where the input for a puzzle is kept, `inputs/{year}/{day}.txt` in the workspace root
+pub fn input_path(year: u16, day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join("inputs")
+        .join(year.to_string())
+        .join(format!("{}.txt", day))
+}
+
+/// Read the input for a puzzle from its usual place (see `input_path`), downloading it if needed
+pub fn read_puzzle_input(year: u16, day: u8) -> String {
+    read_input(input_path(year, day), Some((year, day)))
+}
+
+/// Read a puzzle input from disk, with its line endings normalised.
+/// If the file is missing and the puzzle's year and day are known, the input is downloaded and
+/// cached at that path first.
+pub fn read_input(path: impl AsRef<Path>, puzzle: Option<(u16, u8)>) -> String {
+    let path = path.as_ref();
+    if let Ok(input) = fs::read_to_string(path) {
+        return normalize_newlines(input);
+    }
+
+    let (year, day) =
+        puzzle.unwrap_or_else(|| panic!("Couldn't find AOC input file: {}", path.display()));
+    let input = fetch_input(year, day)
+        .unwrap_or_else(|err| panic!("Couldn't find AOC input file: {} ({})", path.display(), err));
+
+    // Cache the input so we only download it once
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).ok();
+    }
+    if let Err(err) = fs::write(path, &input) {
+        eprintln!("Couldn't cache AOC input at {}: {}", path.display(), err);
+    }
+
+    input
+}
+
+/// Read a whole puzzle input from stdin, with its line endings normalised
+pub fn read_stdin() -> String {
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .unwrap_or_else(|err| panic!("Couldn't read AOC input from stdin: {}", err));
+    normalize_newlines(input)
+}
+
+/// Read a puzzle input piped into stdin, if there is one.
+/// Nothing is read when stdin is a terminal, and an empty stdin counts as no input.
+/// Tests inherit whatever stdin cargo was given, which may never be closed, so only call this
+/// when a binary has opted into piped input (see `aoc_input!(piped)` and `cli::parse`).
+pub fn piped_stdin() -> Option<String> {
+    if atty::is(atty::Stream::Stdin) {
+        return None;
+    }
+    Some(read_stdin()).filter(|input| !input.is_empty())
+}
+
+/// Download a puzzle input from adventofcode.com, using the session cookie in `AOC_SESSION`
+pub fn fetch_input(year: u16, day: u8) -> Result<String, String> {
+    let url = format!("https://adventofcode.com/{}/day/{}/input", year, day);
+    ureq::get(&url)
+        .set("Cookie", &session_cookie()?)
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|err| format!("failed to download input: {}", err))?
+        .into_string()
+        .map_err(|err| format!("failed to read downloaded input: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_from_package_name() {
+        assert_eq!(day_from_package_name("day5"), Some(5));
+        assert_eq!(day_from_package_name("day05"), Some(5));
+        assert_eq!(day_from_package_name("day16"), Some(16));
+        assert_eq!(day_from_package_name("common"), None);
+    }
+
+    #[test]
+    fn test_input_path() {
+        let path = input_path(2021, 7);
+        assert!(path.ends_with("inputs/2021/7.txt"));
+        assert!(path.starts_with(Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()));
+    }
+
+    #[test]
+    fn test_normalize_newlines() {
+        assert_eq!(normalize_newlines("a\r\nb\r\n".into()), "a\nb\n");
+        assert_eq!(normalize_newlines("a\nb".into()), "a\nb");
+        assert_eq!(normalize_newlines("a\rb\r\n".into()), "a\rb\n");
+    }
+}
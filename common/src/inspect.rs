@@ -0,0 +1,119 @@
+//! Statistics about a raw puzzle input, for spotting inputs that were mangled on the way in
+
+use std::{collections::BTreeMap, fmt::Display};
+
+/// What a puzzle input looks like, see `inspect`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputStats {
+    pub lines: usize,
+    pub max_line_len: usize,
+    /// How many times each character appears, not counting line endings
+    pub chars: BTreeMap<char, usize>,
+    /// Whether any line ends with `\r\n`
+    pub crlf: bool,
+    /// 1-based numbers of the lines that end in spaces or tabs
+    pub trailing_whitespace: Vec<usize>,
+    pub trailing_newline: bool,
+}
+
+/// Count the lines and characters of an input and look for things that commonly go wrong when
+/// copying one, like Windows line endings and trailing whitespace
+pub fn inspect(input: &str) -> InputStats {
+    let mut stats = InputStats {
+        lines: 0,
+        max_line_len: 0,
+        chars: BTreeMap::new(),
+        crlf: input.contains("\r\n"),
+        trailing_whitespace: Vec::new(),
+        trailing_newline: input.ends_with('\n'),
+    };
+    for (i, line) in input.lines().enumerate() {
+        stats.lines += 1;
+        stats.max_line_len = stats.max_line_len.max(line.chars().count());
+        if line.ends_with([' ', '\t']) {
+            stats.trailing_whitespace.push(i + 1);
+        }
+        for c in line.chars() {
+            *stats.chars.entry(c).or_default() += 1;
+        }
+    }
+    stats
+}
+
+impl InputStats {
+    /// Anything about the input which might trip up a parser
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.lines == 0 {
+            warnings.push("Input is empty".to_string());
+        }
+        if self.crlf {
+            warnings.push("Lines end with \\r\\n".to_string());
+        }
+        if let Some(first) = self.trailing_whitespace.first() {
+            warnings.push(format!(
+                "{} line(s) end in whitespace, starting with line {}",
+                self.trailing_whitespace.len(),
+                first
+            ));
+        }
+        if self.lines > 0 && !self.trailing_newline {
+            warnings.push("No newline at the end of the input".to_string());
+        }
+        warnings
+    }
+}
+
+impl Display for InputStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} lines, longest is {} characters",
+            self.lines, self.max_line_len
+        )?;
+        let chars = self
+            .chars
+            .iter()
+            .map(|(c, count)| format!("{:?}: {}", c, count))
+            .collect::<Vec<_>>();
+        writeln!(f, "Characters: {}", chars.join(", "))?;
+        for warning in self.warnings() {
+            writeln!(f, "Warning: {}", warning)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inspect() {
+        let stats = inspect("ab\nbbb\n");
+        assert_eq!(stats.lines, 2);
+        assert_eq!(stats.max_line_len, 3);
+        assert_eq!(stats.chars, BTreeMap::from([('a', 1), ('b', 4)]));
+        assert!(stats.warnings().is_empty());
+        assert_eq!(
+            stats.to_string(),
+            "2 lines, longest is 3 characters\nCharacters: 'a': 1, 'b': 4\n"
+        );
+    }
+
+    #[test]
+    fn test_warnings() {
+        let stats = inspect("a \r\nb\r\nc\t");
+        assert!(stats.crlf);
+        assert_eq!(stats.trailing_whitespace, vec![1, 3]);
+        assert_eq!(
+            stats.warnings(),
+            vec![
+                "Lines end with \\r\\n",
+                "2 line(s) end in whitespace, starting with line 1",
+                "No newline at the end of the input",
+            ]
+        );
+        assert_eq!(inspect("").warnings(), vec!["Input is empty"]);
+    }
+}
@@ -0,0 +1,166 @@
+use std::ops::RangeInclusive;
+
+/// A set of integers stored as sorted, disjoint inclusive ranges.
+/// Overlapping or touching ranges are merged as they are inserted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Intervals {
+    ranges: Vec<RangeInclusive<isize>>,
+}
+
+impl Intervals {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add every value in a range to the set
+    pub fn insert(&mut self, range: RangeInclusive<isize>) {
+        if range.is_empty() {
+            return;
+        }
+        let (mut start, mut end) = range.into_inner();
+
+        // Ranges entirely before and after the new one are kept as they are,
+        // everything between them overlaps or touches it and gets merged in
+        let before = self.ranges.partition_point(|r| *r.end() < start - 1);
+        let after = self.ranges.partition_point(|r| *r.start() <= end + 1);
+        for merged in self.ranges.drain(before..after) {
+            start = start.min(*merged.start());
+            end = end.max(*merged.end());
+        }
+        self.ranges.insert(before, start..=end);
+    }
+
+    /// The set of values in either of two interval sets
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for range in other.iter() {
+            union.insert(range.clone());
+        }
+        union
+    }
+
+    pub fn contains(&self, value: isize) -> bool {
+        let index = self.ranges.partition_point(|r| *r.end() < value);
+        self.ranges.get(index).is_some_and(|r| r.contains(&value))
+    }
+
+    /// The number of values in the set
+    pub fn total_len(&self) -> usize {
+        self.ranges
+            .iter()
+            .map(|r| r.end().abs_diff(*r.start()) + 1)
+            .sum()
+    }
+
+    /// The ranges of values within `bounds` that are not in the set
+    pub fn gaps(&self, bounds: RangeInclusive<isize>) -> Vec<RangeInclusive<isize>> {
+        let mut gaps = Vec::new();
+        let mut next = *bounds.start();
+        for range in self.iter() {
+            if *range.start() > *bounds.end() {
+                break;
+            }
+            if *range.start() > next {
+                gaps.push(next..=*range.start() - 1);
+            }
+            next = next.max(*range.end() + 1);
+        }
+        if next <= *bounds.end() {
+            gaps.push(next..=*bounds.end());
+        }
+        gaps
+    }
+
+    /// Iterate over the disjoint ranges in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = &RangeInclusive<isize>> {
+        self.ranges.iter()
+    }
+}
+
+impl FromIterator<RangeInclusive<isize>> for Intervals {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<isize>>>(iter: I) -> Self {
+        let mut intervals = Self::new();
+        for range in iter {
+            intervals.insert(range);
+        }
+        intervals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(intervals: &Intervals) -> Vec<RangeInclusive<isize>> {
+        intervals.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_disjoint() {
+        let intervals: Intervals = [10..=12, 0..=2, 5..=6].into_iter().collect();
+        assert_eq!(ranges(&intervals), vec![0..=2, 5..=6, 10..=12]);
+        assert_eq!(intervals.total_len(), 8);
+    }
+
+    #[test]
+    fn test_touching() {
+        let intervals: Intervals = [0..=2, 3..=4, 6..=7].into_iter().collect();
+        assert_eq!(ranges(&intervals), vec![0..=4, 6..=7]);
+        let intervals: Intervals = [6..=7, 3..=5].into_iter().collect();
+        assert_eq!(ranges(&intervals), vec![3..=7]);
+    }
+
+    #[test]
+    fn test_overlapping() {
+        let intervals: Intervals = [0..=5, 3..=8, -4..=1].into_iter().collect();
+        assert_eq!(ranges(&intervals), vec![-4..=8]);
+        assert_eq!(intervals.total_len(), 13);
+
+        // A range bridging several existing ones merges them all
+        let intervals: Intervals = [0..=1, 4..=5, 8..=9, 12..=13, 1..=8].into_iter().collect();
+        assert_eq!(ranges(&intervals), vec![0..=9, 12..=13]);
+    }
+
+    #[test]
+    fn test_nested() {
+        let intervals: Intervals = [0..=10, 2..=3, 10..=10].into_iter().collect();
+        assert_eq!(ranges(&intervals), vec![0..=10]);
+        let intervals: Intervals = [2..=3, 5..=6, 0..=10].into_iter().collect();
+        assert_eq!(ranges(&intervals), vec![0..=10]);
+    }
+
+    #[test]
+    fn test_empty_ranges() {
+        #[allow(clippy::reversed_empty_ranges)]
+        let intervals: Intervals = [5..=4].into_iter().collect();
+        assert_eq!(intervals, Intervals::new());
+        assert_eq!(intervals.total_len(), 0);
+        assert_eq!(intervals.gaps(0..=3), vec![0..=3]);
+    }
+
+    #[test]
+    fn test_contains() {
+        let intervals: Intervals = [0..=2, 5..=6].into_iter().collect();
+        assert!(intervals.contains(0) && intervals.contains(2) && intervals.contains(6));
+        assert!(!intervals.contains(-1) && !intervals.contains(3) && !intervals.contains(7));
+    }
+
+    #[test]
+    fn test_union() {
+        let a: Intervals = [0..=2, 10..=12].into_iter().collect();
+        let b: Intervals = [3..=5, 11..=20].into_iter().collect();
+        assert_eq!(ranges(&a.union(&b)), vec![0..=5, 10..=20]);
+    }
+
+    #[test]
+    fn test_gaps() {
+        let intervals: Intervals = [0..=2, 5..=6, 10..=12].into_iter().collect();
+        assert_eq!(
+            intervals.gaps(-2..=15),
+            vec![-2..=-1, 3..=4, 7..=9, 13..=15]
+        );
+        assert_eq!(intervals.gaps(1..=11), vec![3..=4, 7..=9]);
+        assert_eq!(intervals.gaps(5..=6), vec![]);
+        assert_eq!(intervals.gaps(3..=3), vec![3..=3]);
+    }
+}
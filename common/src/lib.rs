@@ -1,27 +1,267 @@
 /* Util Structs */
 
 mod grid;
+pub use grid::{FromGridChar, Grid, Ray, VecGrid, VecGridTripleIterator};
+
+mod point;
+pub use point::Point;
+
+mod point3;
+pub use point3::Point3;
+
+mod direction;
+pub use direction::Direction;
+
+mod bitset;
+pub use bitset::BitSet;
+
+mod disjoint_set;
+pub use disjoint_set::DisjointSet;
+
+mod sparse_grid;
+pub use sparse_grid::SparseGrid;
+
+mod intervals;
+pub use intervals::Intervals;
+
+mod span;
+pub use span::Span;
+
+mod collections;
+pub use collections::{FastMap, FastSet, Map, Set};
+
+/* Algorithms */
+
+mod compress;
+pub use compress::compress_coords;
+
+pub mod cycles;
+pub mod math;
+pub mod memo;
+pub mod par;
+pub mod search;
+pub mod windows;
+
+mod top_k;
+pub use top_k::top_k;
+
+/* Parsing */
+
+mod error;
+pub use error::{AocError, Result};
+
+mod inspect;
+pub use inspect::{inspect, InputStats};
+
+pub mod ocr;
+pub mod parse;
+
+/* Solutions */
+
+mod day;
+pub use day::Day;
+
+mod solution;
+pub use solution::{run, solve, OutputFormat, Part, Solution};
+
+/// Define `part1` and `part2` functions for a day, which parse a raw input then solve that part,
+/// so other crates can call a day with a `&str` without going through `Solution`
+/// ```ignore
+/// common::solution_fns!(Day1);
+/// assert_eq!(day1::part1(&input), 24000);
+/// ```
+#[macro_export]
+macro_rules! solution_fns {
+    ($day: ident) => {
+        /// Parse a raw puzzle input and solve the first part
+        pub fn part1(input: &str) -> <$day as $crate::Solution>::Part1 {
+            <$day as $crate::Solution>::part1(&<$day as $crate::Solution>::parse(input))
+        }
+
+        /// Parse a raw puzzle input and solve the second part
+        pub fn part2(input: &str) -> <$day as $crate::Solution>::Part2 {
+            <$day as $crate::Solution>::part2(&<$day as $crate::Solution>::parse(input))
+        }
+    };
+}
+
+/* Benchmarking */
+
+pub mod alloc;
+pub mod bench;
+
+/* Visualising */
+
+pub mod viz;
+
+/* Logging */
+
+pub mod log;
+pub mod progress;
+pub use tracing;
+
+/* Submitting */
+
+mod submit;
+pub use submit::{submit, SubmitOutcome};
 
 /* Importing */
 
+pub mod cli;
+
+mod input;
+pub use input::{
+    day_from_package_name, fetch_input, input_path, normalize_newlines, piped_stdin, read_input,
+    read_puzzle_input, read_stdin, YEAR,
+};
+
+/// Read the puzzle input, from the first argument that isn't a `--flag`, stdin if that argument
+/// is `-`, or `inputs/{year}/{day}.txt` by default (see `input_path`).
+/// Missing inputs for day crates are downloaded when `AOC_SESSION` is set.
+/// The year defaults to `YEAR`, or can be given as `aoc_input!(year = 2021)`. A path can be given
+/// instead to read by default, e.g `aoc_input!("./input.txt")`.
+/// Windows line endings become `\n`, and `aoc_input!(trim)` also strips the trailing newlines.
+/// Binaries can use `aoc_input!(piped)` to also read an input piped into stdin when no path is
+/// given; it's opt in so tests calling this never wait on cargo's stdin.
 #[macro_export]
 macro_rules! aoc_input {
     () => {
-        aoc_input!("./input.txt")
+        $crate::aoc_input!(year = $crate::YEAR)
+    };
+    (trim $(, $($args:tt)+)?) => {{
+        let mut input = $crate::aoc_input!($($($args)+)?);
+        input.truncate(input.trim_end_matches('\n').len());
+        input
+    }};
+    (piped $(, $($args:tt)+)?) => {{
+        let path_given = std::env::args().skip(1).any(|arg| !arg.starts_with("--"));
+        match if path_given { None } else { $crate::piped_stdin() } {
+            Some(input) => input,
+            None => $crate::aoc_input!($($($args)+)?),
+        }
+    }};
+    (year = $year:expr) => {{
+        let day = $crate::day_from_package_name(env!("CARGO_PKG_NAME"));
+        $crate::aoc_input!(@args day.map(|day| ($year, day)), || match day {
+            Some(day) => $crate::read_puzzle_input($year, day),
+            None => $crate::read_input("./input.txt", None),
+        })
+    }};
+    (@args $puzzle:expr, $default:expr) => {
+        match std::env::args().skip(1).find(|arg| !arg.starts_with("--")) {
+            Some(arg) if arg == "-" => $crate::read_stdin(),
+            Some(path) => $crate::read_input(&path, $puzzle),
+            None => $default(),
+        }
     };
     ($path:expr) => {{
-        let arg = std::env::args().skip(1).next();
-        let path = arg.unwrap_or(($path).to_string());
-        std::fs::read_to_string((&path))
-            .unwrap_or_else(|_| panic!("Couldn't find AOC input file: {}", &path))
+        let day = $crate::day_from_package_name(env!("CARGO_PKG_NAME"));
+        let puzzle = day.map(|day| ($crate::YEAR, day));
+        $crate::aoc_input!(@args puzzle, || $crate::read_input($path, puzzle))
+    }};
+}
+
+/* Testing */
+
+/// The path to the calling crate's `sample.txt`, worked out at compile time so it can be found
+/// wherever the binary is run from
+#[macro_export]
+macro_rules! sample_path {
+    () => {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/sample.txt")
+    };
+}
+
+/// Generate a test checking a solver's answer for a sample input.
+///
+/// The sample is either a string or `file "sample.txt"` to read a file relative to the crate.
+/// The solver is either a part of a `Solution` such as `Day10::part1`, which is given the parsed
+/// sample, or a closure taking the raw sample as a `&str`.
+/// ```ignore
+/// sample_test!(test_part1, Day10::part1, file "sample.txt", 13140);
+/// sample_test!(test_row, |input| count_row(input, 10), "1,2\n3,4", 26);
+/// ```
+#[macro_export]
+macro_rules! sample_test {
+    ($name: ident, $day: ident :: $part: ident, $($sample: tt)+) => {
+        $crate::sample_test!(
+            $name,
+            |input| $day::$part(&<$day as $crate::Solution>::parse(input)),
+            $($sample)+
+        );
+    };
+    ($name: ident, |$input: ident| $solver: expr, file $path: literal, $expected: expr) => {
+        #[test]
+        fn $name() {
+            let sample = std::fs::read_to_string(
+                std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join($path),
+            )
+            .expect(concat!("Couldn't read sample ", $path));
+            let sample = $crate::normalize_newlines(sample);
+            $crate::sample_test!(@check sample, |$input| $solver, $expected);
+        }
+    };
+    ($name: ident, |$input: ident| $solver: expr, $sample: expr, $expected: expr) => {
+        #[test]
+        fn $name() {
+            $crate::sample_test!(@check $sample, |$input| $solver, $expected);
+        }
+    };
+    (@check $sample: expr, |$input: ident| $solver: expr, $expected: expr) => {{
+        let $input: &str = &$sample;
+        assert_eq!($solver, $expected);
     }};
 }
 
 #[cfg(test)]
 mod tests {
+    sample_test!(
+        test_sample_string,
+        |input| input.lines().count(),
+        "a\nb\nc",
+        3
+    );
+    sample_test!(test_sample_file, |input| input.trim(), file "input.txt", "hello world!");
+
     #[test]
     fn it_works() {
         assert_eq!(&aoc_input!(), "hello world!\n");
         assert_eq!(&aoc_input!("./input.txt"), "hello world!\n");
     }
+
+    pub struct LineCount;
+
+    solution_fns!(LineCount);
+
+    impl crate::Solution for LineCount {
+        const DAY: u8 = 1;
+        type Input = Vec<String>;
+        type Part1 = usize;
+        type Part2 = String;
+
+        fn parse(input: &str) -> Self::Input {
+            input.lines().map(str::to_owned).collect()
+        }
+
+        fn part1(input: &Self::Input) -> Self::Part1 {
+            input.len()
+        }
+
+        fn part2(input: &Self::Input) -> Self::Part2 {
+            input.concat()
+        }
+    }
+
+    #[test]
+    fn test_solution_fns() {
+        assert_eq!(part1("a\nb\nc"), 3);
+        assert_eq!(part2("a\nb\nc"), "abc");
+    }
+
+    #[test]
+    fn test_crlf_input() {
+        assert_eq!(&aoc_input!("./crlf_input.txt"), "hello\nworld!\n\n");
+        assert_eq!(&aoc_input!(trim, "./crlf_input.txt"), "hello\nworld!");
+        assert_eq!(&aoc_input!(trim), "hello world!");
+    }
 }
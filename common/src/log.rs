@@ -0,0 +1,26 @@
+//! Opt-in debug logging, written to stderr so it never mixes with the answers
+
+use tracing_subscriber::{filter::LevelFilter, EnvFilter};
+
+/// Environment variable holding a log filter, such as `debug` or `day16=trace`
+const LOG_VAR: &str = "AOC_LOG";
+
+/// Start logging, using the filter in `AOC_LOG` or every debug event if `verbose` (as a
+/// binary's `--verbose` flag asks for). Nothing is logged by default, and calling this again
+/// does nothing.
+pub fn init(verbose: bool) {
+    let filter = EnvFilter::builder()
+        .with_default_directive(if verbose {
+            LevelFilter::DEBUG.into()
+        } else {
+            LevelFilter::OFF.into()
+        })
+        .with_env_var(LOG_VAR)
+        .from_env_lossy();
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .without_time()
+        .try_init()
+        .ok();
+}
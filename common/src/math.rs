@@ -0,0 +1,111 @@
+//! Number theory for modular arithmetic puzzles
+
+use std::ops::{Div, Mul, Rem};
+
+/// Greatest common divisor of two non-negative integers
+pub fn gcd<T>(a: T, b: T) -> T
+where
+    T: Copy + PartialEq + Default + Rem<Output = T>,
+{
+    if b == T::default() {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Lowest common multiple of two non-negative integers
+pub fn lcm<T>(a: T, b: T) -> T
+where
+    T: Copy + PartialEq + Default + Rem<Output = T> + Div<Output = T> + Mul<Output = T>,
+{
+    if a == T::default() || b == T::default() {
+        return T::default();
+    }
+    a / gcd(a, b) * b
+}
+
+/// Lowest common multiple of every number, or `None` if there aren't any
+pub fn lcm_of<T>(numbers: impl IntoIterator<Item = T>) -> Option<T>
+where
+    T: Copy + PartialEq + Default + Rem<Output = T> + Div<Output = T> + Mul<Output = T>,
+{
+    numbers.into_iter().reduce(lcm)
+}
+
+/// `base` to the power of `exp`, modulo `modulus`
+pub fn mod_pow(base: u64, exp: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+    let modulus = modulus as u128;
+    let (mut base, mut exp, mut result) = (base as u128 % modulus, exp, 1);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+/// `(g, x, y)` such that `a * x + b * y = g`, where `g` is the gcd of `a` and `b`
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// Solve a system of congruences `x = residue (mod modulus)` with the chinese remainder theorem.
+/// Returns the smallest non-negative `x` and the modulus it repeats with, or `None` if the
+/// congruences contradict each other. Moduli don't need to be coprime.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(x, modulus), (residue, other_modulus)| {
+            let (g, p, _) = extended_gcd(modulus, other_modulus);
+            if (residue - x) % g != 0 {
+                return None;
+            }
+            let combined = modulus / g * other_modulus;
+            let step = ((residue - x) / g) as i128 * p as i128 % (other_modulus / g) as i128;
+            let x = (x as i128 + modulus as i128 * step).rem_euclid(combined as i128);
+            Some((x as i64, combined))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12usize, 18), 6);
+        assert_eq!(gcd(7u64, 0), 7);
+        assert_eq!(lcm(4usize, 6), 12);
+        assert_eq!(lcm(0usize, 6), 0);
+        assert_eq!(lcm_of([23usize, 19, 13, 17]), Some(96577));
+        assert_eq!(lcm_of([2u32, 4, 8]), Some(8));
+        assert_eq!(lcm_of(Vec::<u32>::new()), None);
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(3, 0, 7), 1);
+        assert_eq!(mod_pow(5, 3, 1), 0);
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+}
@@ -0,0 +1,176 @@
+//! Caching the results of expensive or recursive functions
+
+use std::{cell::Cell, hash::Hash};
+
+use crate::FastMap;
+
+/// A cache of values by key, counting how often a lookup finds a value
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    values: FastMap<K, V>,
+    limit: Option<usize>,
+    hits: Cell<usize>,
+    misses: Cell<usize>,
+}
+
+/// How well a `Memo` has been doing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub len: usize,
+}
+
+impl<K: Hash + Eq, V> Memo<K, V> {
+    pub fn new() -> Self {
+        Self {
+            values: FastMap::default(),
+            limit: None,
+            hits: Cell::new(0),
+            misses: Cell::new(0),
+        }
+    }
+
+    /// A memo that stops storing new keys once it holds `limit` values
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            limit: Some(limit),
+            ..Self::new()
+        }
+    }
+
+    /// Look up the value for a key, counting a hit or a miss
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let value = self.values.get(key);
+        let counter = if value.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.set(counter.get() + 1);
+        value
+    }
+
+    /// Store the value for a key, replacing the stored key as well as its value.
+    /// New keys are dropped once the memo is at its limit.
+    pub fn insert(&mut self, key: K, value: V) {
+        let existing = self.values.remove(&key).is_some();
+        if existing || self.limit.is_none_or(|limit| self.values.len() < limit) {
+            self.values.insert(key, value);
+        }
+    }
+
+    /// Get the value for a key, computing and storing it first if it's missing
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce(&K) -> V) -> V
+    where
+        V: Clone,
+    {
+        if let Some(value) = self.get(&key) {
+            return value.clone();
+        }
+        let value = f(&key);
+        self.insert(key, value.clone());
+        value
+    }
+
+    /// Compute a recursive function, which is given a way to call itself through the memo
+    pub fn compute<F>(&mut self, key: K, f: &F) -> V
+    where
+        K: Clone,
+        V: Clone,
+        F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+    {
+        if let Some(value) = self.get(&key) {
+            return value.clone();
+        }
+        let value = f(&mut |key| self.compute(key, f), key.clone());
+        self.insert(key, value.clone());
+        value
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            hits: self.hits.get(),
+            misses: self.misses.get(),
+            len: self.values.len(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<K: Hash + Eq, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> IntoIterator for Memo<K, V> {
+    type Item = (K, V);
+    type IntoIter = std::collections::hash_map::IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
+/// Compute a recursive function, caching its result for every key it's called with.
+/// The function is given a way to call itself along with its key.
+/// ```ignore
+/// let fib = memoize(80, |fib, n: u64| if n < 2 { n } else { fib(n - 1) + fib(n - 2) });
+/// ```
+pub fn memoize<K, V>(key: K, f: impl Fn(&mut dyn FnMut(K) -> V, K) -> V) -> V
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    Memo::new().compute(key, &f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(fib: &mut dyn FnMut(u64) -> u64, n: u64) -> u64 {
+        if n < 2 {
+            n
+        } else {
+            fib(n - 1) + fib(n - 2)
+        }
+    }
+
+    #[test]
+    fn test_memoize() {
+        assert_eq!(memoize(80, fib), 23_416_728_348_467_685);
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut memo = Memo::new();
+        assert_eq!(memo.compute(10, &fib), 55);
+        let stats = memo.stats();
+        assert_eq!(stats.len, 11);
+        assert_eq!(stats.misses, 11);
+        assert_eq!(stats.hits, 8);
+    }
+
+    #[test]
+    fn test_limit() {
+        let mut memo = Memo::with_limit(2);
+        memo.insert(1, "one");
+        memo.insert(2, "two");
+        memo.insert(3, "three");
+        memo.insert(2, "deux");
+        assert_eq!(memo.len(), 2);
+        assert_eq!(memo.get(&3), None);
+        assert_eq!(memo.get(&2), Some(&"deux"));
+        assert_eq!(memo.get_or_insert_with(4, |_| "four"), "four");
+        assert_eq!(memo.len(), 2);
+    }
+}
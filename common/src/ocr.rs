@@ -0,0 +1,100 @@
+//! Reading the block letters that puzzles draw on a screen
+
+/// Width of a screen of letters in pixels
+pub const SCREEN_WIDTH: usize = 40;
+
+/// Height of a screen of letters in pixels
+pub const SCREEN_HEIGHT: usize = 6;
+
+/// Width of each letter including the blank column after it
+const LETTER_WIDTH: usize = 5;
+
+/// The letters of the 4x6 font AoC draws, as `#` for lit pixels and `.` for dark ones
+const FONT: [(char, [&str; SCREEN_HEIGHT]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Read the letters drawn on a screen, with `?` for any that aren't in the font
+pub fn parse_letters(screen: &[[bool; SCREEN_WIDTH]; SCREEN_HEIGHT]) -> String {
+    (0..SCREEN_WIDTH / LETTER_WIDTH)
+        .map(|i| {
+            let left = i * LETTER_WIDTH;
+            FONT.iter()
+                .find(|(_, glyph)| {
+                    glyph.iter().zip(screen).all(|(glyph_row, screen_row)| {
+                        glyph_row
+                            .chars()
+                            .zip(&screen_row[left..])
+                            .all(|(c, &lit)| (c == '#') == lit)
+                    })
+                })
+                .map_or('?', |&(letter, _)| letter)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a screen from rows of `#` and `.`
+    fn screen(rows: [&str; SCREEN_HEIGHT]) -> [[bool; SCREEN_WIDTH]; SCREEN_HEIGHT] {
+        let mut screen = [[false; SCREEN_WIDTH]; SCREEN_HEIGHT];
+        for (screen_row, row) in screen.iter_mut().zip(rows) {
+            for (pixel, c) in screen_row.iter_mut().zip(row.chars()) {
+                *pixel = c == '#';
+            }
+        }
+        screen
+    }
+
+    #[test]
+    fn test_parse_letters() {
+        let screen = screen([
+            "###..####.####.#..#.####.####.#..#..##..",
+            "#..#....#.#....#.#..#....#....#..#.#..#.",
+            "#..#...#..###..##...###..###..####.#..#.",
+            "###...#...#....#.#..#....#....#..#.####.",
+            "#.#..#....#....#.#..#....#....#..#.#..#.",
+            "#..#.####.####.#..#.####.#....#..#.#..#.",
+        ]);
+        assert_eq!(parse_letters(&screen), "RZEKEFHA");
+    }
+
+    #[test]
+    fn test_every_letter_in_font() {
+        for letters in FONT.chunks(SCREEN_WIDTH / LETTER_WIDTH) {
+            let rows: [String; SCREEN_HEIGHT] = std::array::from_fn(|y| {
+                letters
+                    .iter()
+                    .map(|(_, glyph)| format!("{}.", glyph[y]))
+                    .collect()
+            });
+            let expected: String = letters.iter().map(|&(letter, _)| letter).collect();
+            let screen = screen(rows.each_ref().map(String::as_str));
+            assert_eq!(&parse_letters(&screen)[..letters.len()], expected);
+        }
+    }
+
+    #[test]
+    fn test_unknown_letters() {
+        let screen = [[true; SCREEN_WIDTH]; SCREEN_HEIGHT];
+        assert_eq!(parse_letters(&screen), "????????");
+    }
+}
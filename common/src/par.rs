@@ -0,0 +1,69 @@
+//! Helpers for embarrassingly parallel work, which run on rayon's thread pool when the `parallel`
+//! feature is enabled and fall back to plain iterators otherwise.
+
+#[cfg(feature = "parallel")]
+pub use rayon;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Find the result of `f` for any item that gives one.
+/// In parallel this isn't necessarily the first such item.
+#[cfg(feature = "parallel")]
+pub fn find_map_any<I, R, F>(items: I, f: F) -> Option<R>
+where
+    I: IntoParallelIterator,
+    F: Fn(I::Item) -> Option<R> + Sync + Send,
+    R: Send,
+{
+    items.into_par_iter().find_map_any(f)
+}
+
+/// Find the result of `f` for any item that gives one.
+/// In parallel this isn't necessarily the first such item.
+#[cfg(not(feature = "parallel"))]
+pub fn find_map_any<I, R, F>(items: I, f: F) -> Option<R>
+where
+    I: IntoIterator,
+    F: Fn(I::Item) -> Option<R> + Sync + Send,
+    R: Send,
+{
+    items.into_iter().find_map(f)
+}
+
+/// Sum the result of `f` over every item
+#[cfg(feature = "parallel")]
+pub fn sum_by<I, F>(items: I, f: F) -> usize
+where
+    I: IntoParallelIterator,
+    F: Fn(I::Item) -> usize + Sync + Send,
+{
+    items.into_par_iter().map(f).sum()
+}
+
+/// Sum the result of `f` over every item
+#[cfg(not(feature = "parallel"))]
+pub fn sum_by<I, F>(items: I, f: F) -> usize
+where
+    I: IntoIterator,
+    F: Fn(I::Item) -> usize + Sync + Send,
+{
+    items.into_iter().map(f).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_map_any() {
+        let found = find_map_any(0..1000, |n: i32| (n * n == 361).then_some(n));
+        assert_eq!(found, Some(19));
+        assert_eq!(find_map_any(0..10, |_: i32| None::<i32>), None);
+    }
+
+    #[test]
+    fn test_sum_by() {
+        assert_eq!(sum_by(vec![1usize, 2, 3], |n| n * 2), 12);
+    }
+}
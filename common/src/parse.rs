@@ -0,0 +1,200 @@
+//! Reusable nom combinators for puzzle inputs
+
+use std::str::FromStr;
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1, space0},
+    combinator::{all_consuming, map, map_res, opt, recognize},
+    multi::separated_list0,
+    sequence::{pair, preceded, separated_pair},
+    IResult, Parser,
+};
+
+use crate::{AocError, Point, Result};
+
+/// Parse an integer with an optional leading `-`
+pub fn signed_int<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(char('-')), digit1)), str::parse)(input)
+}
+
+/// Parse a non-negative integer
+pub fn unsigned_int<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+/// Parse a labelled point such as `x=-2, y=15`, given the labels before each coordinate
+pub fn point<'a>(
+    x_label: &'a str,
+    y_label: &'a str,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Point<isize>> {
+    map(
+        pair(
+            preceded(tag(x_label), signed_int),
+            preceded(tag(y_label), signed_int),
+        ),
+        |(x, y)| Point::new(x, y),
+    )
+}
+
+/// Parse a pair of values separated by a comma, such as `3,4`
+pub fn comma_pair<'a, O1, O2>(
+    first: impl Parser<&'a str, O1, nom::error::Error<&'a str>>,
+    second: impl Parser<&'a str, O2, nom::error::Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (O1, O2)> {
+    separated_pair(first, pair(char(','), space0), second)
+}
+
+/// Parse a possibly empty list of values separated by commas, with optional spaces after each comma
+pub fn comma_separated_list<'a, O>(
+    element: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list0(pair(char(','), space0), element)
+}
+
+/// Run a parser over an entire string, with the position of the error if it fails or leaves
+/// input over
+pub fn parse_all<'a, O>(
+    parser: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+    input: &'a str,
+) -> Result<O> {
+    all_consuming(parser)(input)
+        .map(|(_, output)| output)
+        .map_err(|err| match err {
+            nom::Err::Error(err) | nom::Err::Failure(err) => AocError::parse_at(
+                input,
+                err.input,
+                format!("Unexpected input ({:?})", err.code),
+            ),
+            nom::Err::Incomplete(_) => {
+                AocError::parse_at(input, &input[input.len()..], "Unexpected end of input")
+            }
+        })
+}
+
+/// Parse every line of the input, giving errors their line number
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<AocError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.parse()
+                .map_err(|err: T::Err| err.into().offset_lines(i))
+        })
+        .collect()
+}
+
+/// Parse every blank line separated block of the input, giving errors their line number and
+/// which block they're in
+pub fn blocks<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<AocError>,
+{
+    blocks_with(input, |block| block.parse().map_err(Into::into))
+}
+
+/// Parse every blank line separated block of the input with a function, giving errors their
+/// line number and which block they're in
+pub fn blocks_with<T>(input: &str, mut f: impl FnMut(&str) -> Result<T>) -> Result<Vec<T>> {
+    let mut line = 0;
+    input
+        .trim_end()
+        .split("\n\n")
+        .enumerate()
+        .map(|(i, block)| {
+            let parsed = f(block).map_err(|err| err.offset_lines(line).in_block(i + 1));
+            line += block.lines().count() + 1;
+            parsed
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ints() {
+        assert_eq!(signed_int::<i32>("-12abc"), Ok(("abc", -12)));
+        assert_eq!(signed_int::<i32>("7"), Ok(("", 7)));
+        assert_eq!(unsigned_int::<u8>("255,"), Ok((",", 255)));
+        assert!(unsigned_int::<u8>("256").is_err());
+        assert!(unsigned_int::<u32>("-1").is_err());
+    }
+
+    #[test]
+    fn test_point() {
+        let parsed = point("x=", ", y=")("x=-2, y=15: rest");
+        assert_eq!(parsed, Ok((": rest", Point::new(-2, 15))));
+    }
+
+    #[test]
+    fn test_lists() {
+        let parsed = comma_separated_list(unsigned_int::<u32>)("1,2, 3]");
+        assert_eq!(parsed, Ok(("]", vec![1, 2, 3])));
+        assert_eq!(
+            comma_pair(signed_int::<i32>, signed_int::<i32>)("4,-5"),
+            Ok(("", (4, -5)))
+        );
+    }
+
+    #[test]
+    fn test_parse_all() {
+        assert_eq!(
+            parse_all(comma_separated_list(signed_int::<i64>), "1,2"),
+            Ok(vec![1, 2])
+        );
+        assert_eq!(
+            parse_all(comma_pair(signed_int::<i64>, signed_int::<i64>), "1,x")
+                .unwrap_err()
+                .to_string(),
+            "Parse error at 1:3: Unexpected input (Digit)"
+        );
+        assert!(parse_all(signed_int::<i64>, "12 ").is_err());
+    }
+
+    #[test]
+    fn test_parse_lines_and_blocks() {
+        /// A number that must be on its own line
+        #[derive(Debug)]
+        struct Num(u32);
+
+        impl FromStr for Num {
+            type Err = AocError;
+            fn from_str(s: &str) -> Result<Self> {
+                parse_all(unsigned_int, s).map(Num)
+            }
+        }
+
+        let nums: Vec<Num> = parse_lines("1\n2\n3").unwrap();
+        assert_eq!(nums.iter().map(|n| n.0).sum::<u32>(), 6);
+        let err = parse_lines::<Num>("1\n2\nx3").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error at 3:1: Unexpected input (Digit)"
+        );
+
+        let parsed: Vec<Num> = blocks("1\n\n2\n").unwrap();
+        assert_eq!(parsed.len(), 2);
+        let err = blocks::<Num>("1\n\n2\n\n3\n4").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error at 5:2: Unexpected input (Eof) (in block 3)"
+        );
+
+        let sums = blocks_with("1\n2\n\n3", |block| {
+            Ok(parse_lines::<u32>(block)?.into_iter().sum::<u32>())
+        });
+        assert_eq!(sums, Ok(vec![3, 3]));
+        let err = blocks_with("1\n\n2\nx", parse_lines::<u32>).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error at 4:1: invalid digit found in string (in block 2)"
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A 2D point or vector
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> Point<T> {
+    /// The taxicab distance between two points
+    pub fn manhattan_dist(&self, other: &Self) -> T {
+        let abs_diff = |a: T, b: T| if a > b { a - b } else { b - a };
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+}
+
+impl<T: Copy + PartialOrd + Default + From<i8>> Point<T> {
+    /// The sign of each component, i.e a vector of -1, 0 or 1 in each axis
+    pub fn signum(&self) -> Self {
+        let signum = |v: T| {
+            let zero = T::default();
+            if v > zero {
+                T::from(1)
+            } else if v < zero {
+                T::from(-1)
+            } else {
+                zero
+            }
+        };
+        Self::new(signum(self.x), signum(self.y))
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(1, -2);
+        let b: Point<i32> = (3, 4).into();
+        assert_eq!(a + b, Point::new(4, 2));
+        assert_eq!(a - b, Point::new(-2, -6));
+        assert_eq!(-a, Point::new(-1, 2));
+        assert_eq!(b * 3, Point::new(9, 12));
+    }
+
+    #[test]
+    fn test_manhattan_dist_and_signum() {
+        let a = Point::new(1isize, -2);
+        let b = Point::new(-3isize, 4);
+        assert_eq!(a.manhattan_dist(&b), 10);
+        assert_eq!(Point::new(2usize, 7).manhattan_dist(&Point::new(5, 3)), 7);
+        assert_eq!((b - a).signum(), Point::new(-1, 1));
+        assert_eq!(Point::new(0isize, 5).signum(), Point::new(0, 1));
+    }
+}
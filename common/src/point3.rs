@@ -0,0 +1,189 @@
+use std::{
+    ops::{Add, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+use crate::AocError;
+
+/// A 3D point or vector, e.g a voxel in a grid of cubes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Copy + Add<Output = T> + From<i8>> Point3<T> {
+    /// The six points sharing a face with this one
+    pub fn neighbors6(&self) -> impl Iterator<Item = Self> + '_ {
+        [
+            (-1, 0, 0),
+            (1, 0, 0),
+            (0, -1, 0),
+            (0, 1, 0),
+            (0, 0, -1),
+            (0, 0, 1),
+        ]
+        .into_iter()
+        .map(|offset| *self + Self::from_offset(offset))
+    }
+
+    /// The 26 points sharing a face, edge or corner with this one
+    pub fn neighbors26(&self) -> impl Iterator<Item = Self> + '_ {
+        (-1..=1)
+            .flat_map(|dx| (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| (dx, dy, dz))))
+            .filter(|&offset| offset != (0, 0, 0))
+            .map(|offset| *self + Self::from_offset(offset))
+    }
+
+    fn from_offset((dx, dy, dz): (i8, i8, i8)) -> Self {
+        Self::new(T::from(dx), T::from(dy), T::from(dz))
+    }
+}
+
+impl<T: Copy + Ord> Point3<T> {
+    /// The smallest and largest corners of the box containing every point, or `None` if there
+    /// aren't any
+    pub fn bounds(points: impl IntoIterator<Item = Self>) -> Option<(Self, Self)> {
+        points.into_iter().fold(None, |bounds, p| {
+            Some(match bounds {
+                Some((min, max)) => (
+                    Self::new(p.x.min(min.x), p.y.min(min.y), p.z.min(min.z)),
+                    Self::new(p.x.max(max.x), p.y.max(max.y), p.z.max(max.z)),
+                ),
+                None => (p, p),
+            })
+        })
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> Point3<T> {
+    /// The taxicab distance between two points
+    pub fn manhattan_dist(&self, other: &Self) -> T {
+        let abs_diff = |a: T, b: T| if a > b { a - b } else { b - a };
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+}
+
+impl<T: FromStr> FromStr for Point3<T> {
+    type Err = AocError;
+
+    /// Read a point written as `x,y,z`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split(',').map(|part| {
+            part.trim().parse().map_err(|_| {
+                AocError::parse(format!("Invalid coordinate '{}' in point '{}'", part, s))
+            })
+        });
+        let mut next = || {
+            parts.next().unwrap_or_else(|| {
+                Err(AocError::parse(format!(
+                    "Too few coordinates in point '{}'",
+                    s
+                )))
+            })
+        };
+        let point = Self::new(next()?, next()?, next()?);
+        match parts.next() {
+            None => Ok(point),
+            Some(_) => Err(AocError::parse(format!(
+                "Too many coordinates in point '{}'",
+                s
+            ))),
+        }
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(point: Point3<T>) -> Self {
+        (point.x, point.y, point.z)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point3::new(1, -2, 3);
+        let b: Point3<i32> = (3, 4, -5).into();
+        assert_eq!(a + b, Point3::new(4, 2, -2));
+        assert_eq!(a - b, Point3::new(-2, -6, 8));
+        assert_eq!(-a, Point3::new(-1, 2, -3));
+        assert_eq!(b * 2, Point3::new(6, 8, -10));
+        assert_eq!(a.manhattan_dist(&b), 16);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let p = Point3::new(0i32, 0, 0);
+        assert_eq!(p.neighbors6().count(), 6);
+        assert!(p.neighbors6().all(|n| n.manhattan_dist(&p) == 1));
+        assert_eq!(p.neighbors26().count(), 26);
+        assert!(!p.neighbors26().any(|n| n == p));
+        assert!(p.neighbors26().any(|n| n == Point3::new(-1, 1, -1)));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let points = [
+            Point3::new(2, 2, 2),
+            Point3::new(1, 3, 2),
+            Point3::new(3, 2, 5),
+        ];
+        assert_eq!(
+            Point3::bounds(points),
+            Some((Point3::new(1, 2, 2), Point3::new(3, 3, 5)))
+        );
+        assert_eq!(Point3::<i32>::bounds([]), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("2,-3,15".parse(), Ok(Point3::new(2, -3, 15)));
+        assert!("1,2".parse::<Point3<i32>>().is_err());
+        assert!("1,2,3,4".parse::<Point3<i32>>().is_err());
+        assert!("1,a,3".parse::<Point3<i32>>().is_err());
+    }
+}
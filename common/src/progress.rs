@@ -0,0 +1,136 @@
+//! Progress reporting for slow loops, written to stderr so it never mixes with the answers
+
+use std::{
+    io::Write,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use tracing::{debug, enabled, Level};
+
+use crate::OutputFormat;
+
+/// Width of the bar drawn in a terminal, in characters
+const BAR_WIDTH: u64 = 40;
+
+/// Something keeping track of how far through a loop we are
+pub trait Progress: Send + Sync {
+    /// Note that another `steps` steps are done
+    fn advance(&self, steps: u64);
+
+    /// Note that one more step is done
+    fn inc(&self) {
+        self.advance(1);
+    }
+}
+
+/// Report progress through `len` steps in whichever way suits how we were run.
+/// Nothing is shown when printing JSON or when stderr isn't a terminal (such as in tests),
+/// debug logging gets a line every 10%, and otherwise a bar is drawn in the terminal.
+pub fn bar(len: u64) -> Box<dyn Progress> {
+    if OutputFormat::current() == OutputFormat::Json {
+        Box::new(Silent)
+    } else if enabled!(Level::DEBUG) {
+        Box::new(LogLines::new(len))
+    } else if atty::is(atty::Stream::Stderr) {
+        Box::new(Bar::new(len))
+    } else {
+        Box::new(Silent)
+    }
+}
+
+/// Doesn't report anything
+pub struct Silent;
+
+impl Progress for Silent {
+    fn advance(&self, _steps: u64) {}
+}
+
+/// Counts steps, noting when they cross into a new tenth or 40th of the total
+struct Counter {
+    len: u64,
+    done: AtomicU64,
+}
+
+impl Counter {
+    fn new(len: u64) -> Self {
+        Self {
+            len: len.max(1),
+            done: AtomicU64::new(0),
+        }
+    }
+
+    /// Add some steps, returning the new number of `parts`ths done if it went up
+    fn advance(&self, steps: u64, parts: u64) -> Option<u64> {
+        let before = self.done.fetch_add(steps, Ordering::Relaxed);
+        let part = |done: u64| done.min(self.len) * parts / self.len;
+        let after = part(before + steps);
+        (after > part(before)).then_some(after)
+    }
+}
+
+/// Logs a debug line every 10%
+pub struct LogLines(Counter);
+
+impl LogLines {
+    pub fn new(len: u64) -> Self {
+        Self(Counter::new(len))
+    }
+}
+
+impl Progress for LogLines {
+    fn advance(&self, steps: u64) {
+        if let Some(tenths) = self.0.advance(steps, 10) {
+            debug!("{}% done", tenths * 10);
+        }
+    }
+}
+
+/// Redraws a bar on the current line of the terminal
+pub struct Bar(Counter);
+
+impl Bar {
+    pub fn new(len: u64) -> Self {
+        let bar = Self(Counter::new(len));
+        bar.draw(0);
+        bar
+    }
+
+    fn draw(&self, filled: u64) {
+        let bar = "#".repeat(filled as usize) + &".".repeat((BAR_WIDTH - filled) as usize);
+        let mut stderr = std::io::stderr().lock();
+        write!(stderr, "\r[{}] {:>3}%", bar, filled * 100 / BAR_WIDTH).ok();
+        stderr.flush().ok();
+    }
+}
+
+impl Progress for Bar {
+    fn advance(&self, steps: u64) {
+        if let Some(filled) = self.0.advance(steps, BAR_WIDTH) {
+            self.draw(filled);
+        }
+    }
+}
+
+impl Drop for Bar {
+    fn drop(&mut self) {
+        // Clear the bar so the answers start on a clean line
+        let blank = " ".repeat(BAR_WIDTH as usize + 7);
+        eprint!("\r{}\r", blank);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter() {
+        let counter = Counter::new(20);
+        assert_eq!(counter.advance(1, 10), None);
+        assert_eq!(counter.advance(1, 10), Some(1));
+        assert_eq!(counter.advance(7, 10), Some(4));
+        assert_eq!(counter.advance(100, 10), Some(10));
+        assert_eq!(counter.advance(1, 10), None);
+        assert_eq!(Counter::new(0).advance(1, 10), Some(10));
+    }
+}
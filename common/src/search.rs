@@ -0,0 +1,305 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Find a shortest path from `start` to a node satisfying `is_goal` using a breadth first search,
+/// where `successors` gives the nodes reachable in one step from a node.
+/// The path includes both the start and goal nodes.
+pub fn bfs<N, FN, IN, FG>(start: N, mut successors: FN, mut is_goal: FG) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+{
+    let mut parents: HashMap<N, Option<N>> = HashMap::from([(start.clone(), None)]);
+    let mut frontier = VecDeque::from([start]);
+    while let Some(node) = frontier.pop_front() {
+        if is_goal(&node) {
+            return Some(backtrace(&parents, node));
+        }
+        for child in successors(&node) {
+            if !parents.contains_key(&child) {
+                parents.insert(child.clone(), Some(node.clone()));
+                frontier.push_back(child);
+            }
+        }
+    }
+    None
+}
+
+/// A breadth first search like `bfs` over nodes numbered from 0 to `count`, such as the cells of
+/// a grid. Parents are kept in a vec rather than a map, so nothing needs hashing.
+pub fn bfs_indexed<FN, IN, FG>(
+    count: usize,
+    start: usize,
+    mut successors: FN,
+    mut is_goal: FG,
+) -> Option<Vec<usize>>
+where
+    FN: FnMut(usize) -> IN,
+    IN: IntoIterator<Item = usize>,
+    FG: FnMut(usize) -> bool,
+{
+    // The start is its own parent, so it's never visited again
+    let mut parents: Vec<Option<usize>> = vec![None; count];
+    parents[start] = Some(start);
+    let mut frontier = VecDeque::from([start]);
+    while let Some(node) = frontier.pop_front() {
+        if is_goal(node) {
+            // Follow the parents back to the start
+            let mut path = vec![node];
+            while path[path.len() - 1] != start {
+                path.push(parents[path[path.len() - 1]].unwrap());
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for child in successors(node) {
+            if parents[child].is_none() {
+                parents[child] = Some(node);
+                frontier.push_back(child);
+            }
+        }
+    }
+    None
+}
+
+/// Find the number of steps to every node reachable from `start`
+pub fn bfs_distances<N, FN, IN>(start: N, mut successors: FN) -> HashMap<N, usize>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut frontier = VecDeque::from([(start, 0)]);
+    while let Some((node, distance)) = frontier.pop_front() {
+        for child in successors(&node) {
+            if !distances.contains_key(&child) {
+                distances.insert(child.clone(), distance + 1);
+                frontier.push_back((child, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Find a cheapest path from `start` to a node satisfying `is_goal` using Dijkstra's algorithm,
+/// where `successors` gives the nodes reachable in one step from a node along with their cost.
+/// Returns the total cost and the path, including both the start and goal nodes.
+pub fn dijkstra<N, C, FN, IN, FG>(start: N, successors: FN, is_goal: FG) -> Option<(C, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FG: FnMut(&N) -> bool,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Find a cheapest path from `start` to a node satisfying `is_goal` using A*,
+/// where `heuristic` estimates the remaining cost from a node and must never overestimate it.
+/// Returns the total cost and the path, including both the start and goal nodes.
+pub fn astar<N, C, FN, IN, FH, FG>(
+    start: N,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut is_goal: FG,
+) -> Option<(C, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FG: FnMut(&N) -> bool,
+{
+    // Nodes are kept in a vec so the heap only has to order costs and indices
+    let mut nodes = vec![start.clone()];
+    let mut best: HashMap<N, (C, Option<N>)> =
+        HashMap::from([(start.clone(), (C::default(), None))]);
+    let mut frontier = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    while let Some(Reverse((_, cost, index))) = frontier.pop() {
+        let node = nodes[index].clone();
+
+        // Skip stale entries that have since been reached more cheaply
+        if best[&node].0 < cost {
+            continue;
+        }
+        if is_goal(&node) {
+            let parents = best
+                .into_iter()
+                .map(|(n, (_, parent))| (n, parent))
+                .collect();
+            return Some((cost, backtrace(&parents, node)));
+        }
+
+        for (child, step_cost) in successors(&node) {
+            let child_cost = cost + step_cost;
+            if best
+                .get(&child)
+                .is_some_and(|&(known, _)| known <= child_cost)
+            {
+                continue;
+            }
+            best.insert(child.clone(), (child_cost, Some(node.clone())));
+            frontier.push(Reverse((
+                child_cost + heuristic(&child),
+                child_cost,
+                nodes.len(),
+            )));
+            nodes.push(child);
+        }
+    }
+    None
+}
+
+/// Find every node reachable from `start`, including `start` itself
+pub fn flood_fill<N, FN, IN>(start: N, mut successors: FN) -> HashSet<N>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    let mut filled = HashSet::from([start.clone()]);
+    let mut frontier = vec![start];
+    while let Some(node) = frontier.pop() {
+        for child in successors(&node) {
+            if filled.insert(child.clone()) {
+                frontier.push(child);
+            }
+        }
+    }
+    filled
+}
+
+/// Group nodes into the sets reachable from one another, in the order they are first seen.
+/// `successors` should be symmetric, i.e describe an undirected graph.
+pub fn connected_components<N, FN, IN>(
+    nodes: impl IntoIterator<Item = N>,
+    mut successors: FN,
+) -> Vec<HashSet<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    let mut components: Vec<HashSet<N>> = Vec::new();
+    for node in nodes {
+        if !components.iter().any(|component| component.contains(&node)) {
+            components.push(flood_fill(node, &mut successors));
+        }
+    }
+    components
+}
+
+/// Follow parent links back from a node to build the path that reached it
+fn backtrace<N: Eq + Hash + Clone>(parents: &HashMap<N, Option<N>>, node: N) -> Vec<N> {
+    let mut path = vec![node];
+    while let Some(Some(parent)) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Steps along a number line, either adding one or doubling
+    fn number_successors(n: &u32) -> [u32; 2] {
+        [n + 1, n * 2]
+    }
+
+    #[test]
+    fn test_bfs() {
+        let path = bfs(1, number_successors, |&n| n == 10).unwrap();
+        assert_eq!(path, vec![1, 2, 4, 5, 10]);
+        assert_eq!(bfs(1, |_| [], |&n| n == 10), None);
+        assert_eq!(bfs(1, number_successors, |&n| n == 1), Some(vec![1]));
+    }
+
+    /// A small weighted graph where the direct edge is more expensive than the detour
+    fn weighted_successors(n: &char) -> Vec<(char, u32)> {
+        match n {
+            'a' => vec![('b', 1), ('d', 10)],
+            'b' => vec![('c', 2)],
+            'c' => vec![('d', 3)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let (cost, path) = dijkstra('a', weighted_successors, |&n| n == 'd').unwrap();
+        assert_eq!(cost, 6);
+        assert_eq!(path, vec!['a', 'b', 'c', 'd']);
+        assert_eq!(dijkstra('d', weighted_successors, |&n| n == 'a'), None);
+    }
+
+    #[test]
+    fn test_astar() {
+        // Walk a 10x10 grid towards a corner, with the manhattan distance as the heuristic
+        let goal = (9i32, 9i32);
+        let successors = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|&(x, y)| (0..10).contains(&x) && (0..10).contains(&y) && (x, y) != (5, 5))
+                .map(|p| (p, 1))
+        };
+        let heuristic = |&(x, y): &(i32, i32)| (goal.0 - x).abs() + (goal.1 - y).abs();
+        let (cost, path) = astar((0, 0), successors, heuristic, |&p| p == goal).unwrap();
+        assert_eq!(cost, 18);
+        assert_eq!(path.len(), 19);
+        assert_eq!(dijkstra((0, 0), successors, |&p| p == goal).unwrap().0, 18);
+    }
+
+    #[test]
+    fn test_flood_fill_and_components() {
+        use crate::{Grid, VecGrid};
+
+        // Two islands of land, where diagonals don't connect
+        let grid: VecGrid<char> = "##..\n#..#\n...#\n#...".parse().unwrap();
+        let land = |&(x, y): &(usize, usize)| {
+            grid.neighbors4(x, y)
+                .filter(|&(x, y)| grid.get(x, y) == Some(&'#'))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(flood_fill((0, 0), land).len(), 3);
+        assert_eq!(flood_fill((3, 2), land).len(), 2);
+
+        let land_cells = (0..grid.height())
+            .flat_map(|y| (0..grid.width()).map(move |x| (x, y)))
+            .filter(|&(x, y)| grid.get(x, y) == Some(&'#'));
+        let sizes = connected_components(land_cells, land)
+            .iter()
+            .map(|component| component.len())
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_bfs_indexed() {
+        let successors = |n: usize| [n + 1, n * 2].into_iter().filter(|&n| n < 16);
+        let path = bfs_indexed(16, 1, successors, |n| n == 10).unwrap();
+        assert_eq!(path, vec![1, 2, 4, 5, 10]);
+        assert_eq!(bfs_indexed(16, 1, successors, |n| n == 1), Some(vec![1]));
+        assert_eq!(bfs_indexed(16, 9, successors, |n| n == 3), None);
+    }
+
+    #[test]
+    fn test_bfs_distances() {
+        let successors = |&n: &u32| (n < 8).then_some([n + 1, n * 2]).into_iter().flatten();
+        let distances = bfs_distances(1, successors);
+        assert_eq!(distances[&1], 0);
+        assert_eq!(distances[&8], 3);
+        assert_eq!(distances[&7], 4);
+        assert_eq!(distances.len(), 11);
+    }
+}
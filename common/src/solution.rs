@@ -0,0 +1,241 @@
+use std::{
+    fmt::Display,
+    str::FromStr,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    cli::{Flag, Flags},
+    viz::VizSettings,
+};
+
+/// A solution to a single day's puzzle
+pub trait Solution {
+    /// Day of the advent calendar that this solves
+    const DAY: u8;
+
+    /// Parsed puzzle input, shared by both parts
+    type Input;
+
+    /// Answer to the first part
+    type Part1: Display;
+
+    /// Answer to the second part
+    type Part2: Display;
+
+    /// Flags the day's binary and the runner accept for this day, besides the shared ones in
+    /// `cli::SHARED_FLAGS`. They're handed to `parse_with`.
+    const FLAGS: &'static [Flag] = &[];
+
+    /// Check any assumptions about the shape of the raw puzzle input before parsing it, so
+    /// malformed input gets a clear error rather than a panic deep inside the parser
+    fn validate(_input: &str) -> crate::Result<()> {
+        Ok(())
+    }
+
+    /// Parse the raw puzzle input
+    fn parse(input: &str) -> Self::Input;
+
+    /// Parse the raw puzzle input along with the flags a binary was given, for days with
+    /// `FLAGS` that change how they solve. An invalid flag is reported as a usage error.
+    fn parse_with(input: &str, _flags: &Flags) -> Result<Self::Input, String> {
+        Ok(Self::parse(input))
+    }
+
+    /// Solve the first part of the puzzle
+    fn part1(input: &Self::Input) -> Self::Part1;
+
+    /// Solve the second part of the puzzle
+    fn part2(input: &Self::Input) -> Self::Part2;
+}
+
+/// Which parts of a puzzle to run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+    Both,
+}
+
+impl Part {
+    pub fn includes_one(&self) -> bool {
+        *self != Part::Two
+    }
+
+    pub fn includes_two(&self) -> bool {
+        *self != Part::One
+    }
+}
+
+impl FromStr for Part {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            "both" => Ok(Part::Both),
+            _ => Err("Part must be one of 1, 2 or both"),
+        }
+    }
+}
+
+/// How answers are printed by `run`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// A line per part, printed as soon as it's found
+    Text,
+    /// A single JSON object once every part is found
+    Json,
+}
+
+/// Whether `run` is printing JSON, which other output such as progress bars keeps out of the way of
+static PRINTING_JSON: AtomicBool = AtomicBool::new(false);
+
+impl OutputFormat {
+    /// JSON if a binary was given a `--json` flag, otherwise text
+    pub fn from_flags(flags: &Flags) -> Self {
+        if flags.is_set("json") {
+            OutputFormat::Json
+        } else {
+            OutputFormat::Text
+        }
+    }
+
+    /// How `run` is printing answers, which is text until it's been given a `--json` flag
+    pub fn current() -> Self {
+        if PRINTING_JSON.load(Ordering::Relaxed) {
+            OutputFormat::Json
+        } else {
+            OutputFormat::Text
+        }
+    }
+}
+
+/// Parse the input for a solution with the flags it was given and print the answers to the
+/// requested parts, as JSON with `--json`. Logging is started first (see `log::init`), drawing
+/// is set up by the shared flags (see `viz::configure`), and statistics about the input are
+/// printed to stderr with `--inspect`. The answers are also returned alongside the part they
+/// answer, or an error if the solution or the drawing settings rejected one of the flags.
+pub fn run<S: Solution>(
+    input: &str,
+    part: Part,
+    flags: &Flags,
+) -> Result<Vec<(u8, String)>, String> {
+    crate::log::init(flags.is_set("verbose"));
+    crate::viz::configure(VizSettings::from_flags(flags)?);
+    let format = OutputFormat::from_flags(flags);
+    PRINTING_JSON.store(format == OutputFormat::Json, Ordering::Relaxed);
+    if flags.is_set("inspect") {
+        eprint!("{}", crate::inspect(input));
+    }
+    validate::<S>(input);
+    let input = S::parse_with(input, flags)?;
+    let start = Instant::now();
+    let answers = solve_with::<S>(&input, part, |part, answer| {
+        if format == OutputFormat::Text {
+            println!("[PT{}] {}", part, answer);
+        }
+    });
+    if format == OutputFormat::Json {
+        println!("{}", answers_json(S::DAY, &answers, start.elapsed()));
+    }
+    Ok(answers)
+}
+
+/// Parse the input for a solution and find the answers to the requested parts, without printing
+pub fn solve<S: Solution>(input: &str, part: Part) -> Vec<(u8, String)> {
+    validate::<S>(input);
+    solve_with::<S>(&S::parse(input), part, |_, _| {})
+}
+
+/// Check a raw input is the shape a solution expects, before it's parsed
+fn validate<S: Solution>(input: &str) {
+    if let Err(err) = S::validate(input) {
+        panic!("Invalid input for day {}: {}", S::DAY, err);
+    }
+}
+
+/// Find the answers to the requested parts, calling `on_answer` as each one is found
+fn solve_with<S: Solution>(
+    input: &S::Input,
+    part: Part,
+    mut on_answer: impl FnMut(u8, &str),
+) -> Vec<(u8, String)> {
+    let mut answers = Vec::new();
+    if part.includes_one() {
+        let answer = S::part1(input).to_string();
+        on_answer(1, &answer);
+        answers.push((1, answer));
+    }
+    if part.includes_two() {
+        let answer = S::part2(input).to_string();
+        on_answer(2, &answer);
+        answers.push((2, answer));
+    }
+    answers
+}
+
+/// Format answers as a JSON object, e.g `{"day": 1, "part1": 24000, "elapsed_ms": 0.1}`.
+/// Numeric answers are written as numbers and anything else as a string.
+fn answers_json(day: u8, answers: &[(u8, String)], elapsed: Duration) -> String {
+    let mut fields = vec![format!("\"day\": {}", day)];
+    for (part, answer) in answers {
+        let value = if answer.parse::<i128>().is_ok() {
+            answer.clone()
+        } else {
+            json_string(answer)
+        };
+        fields.push(format!("\"part{}\": {}", part, value));
+    }
+    fields.push(format!(
+        "\"elapsed_ms\": {:.3}",
+        elapsed.as_secs_f64() * 1000.0
+    ));
+    format!("{{{}}}", fields.join(", "))
+}
+
+/// Quote and escape a string for JSON
+fn json_string(s: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_part() {
+        assert_eq!("1".parse(), Ok(Part::One));
+        assert_eq!("2".parse(), Ok(Part::Two));
+        assert_eq!("both".parse(), Ok(Part::Both));
+        assert!("3".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn test_answers_json() {
+        let answers = vec![(1, "24000".to_owned()), (2, "CZ\n\"x\"".to_owned())];
+        assert_eq!(
+            answers_json(1, &answers, Duration::from_micros(1500)),
+            r#"{"day": 1, "part1": 24000, "part2": "CZ\n\"x\"", "elapsed_ms": 1.500}"#
+        );
+        assert_eq!(
+            answers_json(16, &answers[..1], Duration::ZERO),
+            r#"{"day": 16, "part1": 24000, "elapsed_ms": 0.000}"#
+        );
+    }
+}
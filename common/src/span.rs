@@ -0,0 +1,148 @@
+use std::{ops::RangeInclusive, str::FromStr};
+
+use crate::{parse, AocError};
+
+/// An inclusive range of integers, like the `2-4` section assignments of day 4.
+/// Unlike `RangeInclusive` it can be parsed, and it can be compared with other spans.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Span<T>(RangeInclusive<T>);
+
+impl<T> Span<T> {
+    /// A span of every value from `start` to `end`, including both
+    pub const fn new(start: T, end: T) -> Self {
+        Self(RangeInclusive::new(start, end))
+    }
+
+    pub fn start(&self) -> &T {
+        self.0.start()
+    }
+
+    pub fn end(&self) -> &T {
+        self.0.end()
+    }
+}
+
+impl<T: Copy + Ord> Span<T> {
+    pub fn is_empty(&self) -> bool {
+        self.end() < self.start()
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.start() <= value && value <= self.end()
+    }
+
+    /// Whether every value in another span is also in this one
+    pub fn encompasses(&self, other: &Self) -> bool {
+        self.start() <= other.start() && self.end() >= other.end()
+    }
+
+    /// Whether any value is in both spans
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start() <= other.end() && other.start() <= self.end()
+    }
+
+    /// The values in both spans, if there are any
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let span = Self::new(
+            *self.start().max(other.start()),
+            *self.end().min(other.end()),
+        );
+        (!span.is_empty()).then_some(span)
+    }
+}
+
+impl<T: Copy + Ord + TryInto<i128>> Span<T> {
+    /// The number of values in the span
+    pub fn len(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let wide = |value: T| {
+            value
+                .try_into()
+                .ok()
+                .expect("Span is too big to find its length")
+        };
+        (wide(*self.end()) - wide(*self.start()) + 1) as usize
+    }
+}
+
+impl<T> From<RangeInclusive<T>> for Span<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        Self(range)
+    }
+}
+
+impl<T> From<Span<T>> for RangeInclusive<T> {
+    fn from(span: Span<T>) -> Self {
+        span.0
+    }
+}
+
+/// Parses a span written like `2-4` or `-5--1`
+impl<T: FromStr + Ord> FromStr for Span<T> {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = parse::parse_all(
+            nom::sequence::separated_pair(
+                parse::signed_int::<T>,
+                nom::character::complete::char('-'),
+                parse::signed_int::<T>,
+            ),
+            s,
+        )?;
+        if end < start {
+            return Err(AocError::parse(format!(
+                "Span '{}' ends before it starts",
+                s
+            )));
+        }
+        Ok(Self::new(start, end))
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Span<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start(), self.end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("2-4".parse(), Ok(Span::<u8>::new(2, 4)));
+        assert_eq!("-5--1".parse(), Ok(Span::<i32>::new(-5, -1)));
+        assert_eq!(Span::new(-5, -1).to_string(), "-5--1");
+        assert!("4-2".parse::<Span<usize>>().is_err());
+        assert!("-1-2".parse::<Span<usize>>().is_err());
+        assert!("2-".parse::<Span<usize>>().is_err());
+    }
+
+    #[test]
+    fn test_encompasses() {
+        assert!(Span::new(0, 10).encompasses(&Span::new(3, 5)));
+        assert!(!Span::new(4, 5).encompasses(&Span::new(3, 5)));
+    }
+
+    #[test]
+    fn test_overlaps() {
+        assert!(Span::new(0, 3).overlaps(&Span::new(2, 4)));
+        assert!(Span::new(3, 3).overlaps(&Span::new(0, 5)));
+        assert!(!Span::new(0, 3).overlaps(&Span::new(4, 5)));
+    }
+
+    #[test]
+    fn test_intersection_and_len() {
+        let span = Span::new(0, 3).intersection(&Span::new(2, 8));
+        assert_eq!(span, Some(Span::new(2, 3)));
+        assert_eq!(span.map(|span| span.len()), Some(2));
+        assert_eq!(Span::new(0, 3).intersection(&Span::new(4, 5)), None);
+        assert_eq!(Span::new(-3isize, 3).len(), 7);
+        assert_eq!(Span::new(0u8, 255).len(), 256);
+        assert_eq!(Span::new(5usize, 4).len(), 0);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{FastMap, Point};
+
+type Position = Point<isize>;
+
+/// An unbounded grid of cells where most are empty, which keeps track of the smallest box
+/// containing every filled cell
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: FastMap<Position, T>,
+    bounds: Option<(Position, Position)>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: FastMap::default(),
+            bounds: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Fill a cell, returning what was there before
+    pub fn insert(&mut self, position: Position, value: T) -> Option<T> {
+        self.bounds = Some(extend_bounds(self.bounds, position));
+        self.cells.insert(position, value)
+    }
+
+    pub fn get(&self, position: &Position) -> Option<&T> {
+        self.cells.get(position)
+    }
+
+    pub fn get_mut(&mut self, position: &Position) -> Option<&mut T> {
+        self.cells.get_mut(position)
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        self.cells.contains_key(position)
+    }
+
+    /// Empty a cell, returning what was there
+    pub fn remove(&mut self, position: &Position) -> Option<T> {
+        let removed = self.cells.remove(position)?;
+
+        // Only a cell on the edge can shrink the bounds
+        if let Some((min, max)) = self.bounds {
+            if [min.x, max.x].contains(&position.x) || [min.y, max.y].contains(&position.y) {
+                self.recompute_bounds();
+            }
+        }
+        Some(removed)
+    }
+
+    /// Keep only the cells for which `f` returns true
+    pub fn retain(&mut self, f: impl FnMut(&Position, &mut T) -> bool) {
+        self.cells.retain(f);
+        self.recompute_bounds();
+    }
+
+    /// The top left and bottom right corners of the smallest box containing every filled cell
+    pub fn bounds(&self) -> Option<(Position, Position)> {
+        self.bounds
+    }
+
+    /// Every filled cell, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&Position, &T)> {
+        self.cells.iter()
+    }
+
+    /// Every position within the bounds and its cell, if it's filled, row by row from the top
+    /// left. Handy for drawing the grid.
+    pub fn iter_bounds(&self) -> impl Iterator<Item = (Position, Option<&T>)> {
+        let (min, max) = self
+            .bounds
+            .unwrap_or((Position::new(0, 0), Position::new(-1, -1)));
+        (min.y..=max.y).flat_map(move |y| {
+            (min.x..=max.x).map(move |x| {
+                let position = Position::new(x, y);
+                (position, self.cells.get(&position))
+            })
+        })
+    }
+
+    fn recompute_bounds(&mut self) {
+        self.bounds = self.cells.keys().fold(None, |bounds, &position| {
+            Some(extend_bounds(bounds, position))
+        });
+    }
+}
+
+/// Grow a bounding box to contain a position
+fn extend_bounds(bounds: Option<(Position, Position)>, position: Position) -> (Position, Position) {
+    match bounds {
+        Some((min, max)) => (
+            Position::new(min.x.min(position.x), min.y.min(position.y)),
+            Position::new(max.x.max(position.x), max.y.max(position.y)),
+        ),
+        None => (position, position),
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<(Position, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Position, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        for (position, value) in iter {
+            grid.insert(position, value);
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        grid.insert(Position::new(2, -1), 'a');
+        grid.insert(Position::new(-3, 4), 'b');
+        grid.insert(Position::new(0, 0), 'c');
+        assert_eq!(
+            grid.bounds(),
+            Some((Position::new(-3, -1), Position::new(2, 4)))
+        );
+
+        grid.remove(&Position::new(-3, 4));
+        assert_eq!(
+            grid.bounds(),
+            Some((Position::new(0, -1), Position::new(2, 0)))
+        );
+        grid.retain(|_, &mut c| c == 'c');
+        assert_eq!(
+            grid.bounds(),
+            Some((Position::new(0, 0), Position::new(0, 0)))
+        );
+        grid.retain(|_, _| false);
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn test_iter_bounds() {
+        let grid: SparseGrid<char> = [(Position::new(1, 1), '#'), (Position::new(0, 2), '#')]
+            .into_iter()
+            .collect();
+        let drawing: String = grid
+            .iter_bounds()
+            .map(|(position, cell)| {
+                let c = cell.copied().unwrap_or('.');
+                if position.x == 1 {
+                    format!("{}\n", c)
+                } else {
+                    c.to_string()
+                }
+            })
+            .collect();
+        assert_eq!(drawing, ".#\n#.\n");
+        assert_eq!(SparseGrid::<char>::new().iter_bounds().count(), 0);
+    }
+}
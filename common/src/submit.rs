@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use crate::input::{session_cookie, USER_AGENT};
+
+/// The verdict from submitting an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+
+    /// An answer was submitted too recently, try again after the given time
+    Wait(Duration),
+
+    /// This part has already been solved (or isn't unlocked yet)
+    WrongLevel,
+}
+
+/// Submit an answer to adventofcode.com, using the session cookie in `AOC_SESSION`
+pub fn submit(
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: impl std::fmt::Display,
+) -> Result<SubmitOutcome, String> {
+    let url = format!("https://adventofcode.com/{}/day/{}/answer", year, day);
+    let response = ureq::post(&url)
+        .set("Cookie", &session_cookie()?)
+        .set("User-Agent", USER_AGENT)
+        .send_form(&[
+            ("level", &part.to_string()),
+            ("answer", &answer.to_string()),
+        ])
+        .map_err(|err| format!("failed to submit answer: {}", err))?
+        .into_string()
+        .map_err(|err| format!("failed to read submission response: {}", err))?;
+    parse_submit_response(&response).ok_or_else(|| "unrecognised submission response".to_owned())
+}
+
+/// Determine the outcome of a submission from the response page
+fn parse_submit_response(response: &str) -> Option<SubmitOutcome> {
+    if response.contains("That's the right answer") {
+        Some(SubmitOutcome::Correct)
+    } else if response.contains("your answer is too high") {
+        Some(SubmitOutcome::TooHigh)
+    } else if response.contains("your answer is too low") {
+        Some(SubmitOutcome::TooLow)
+    } else if response.contains("That's not the right answer") {
+        Some(SubmitOutcome::Incorrect)
+    } else if response.contains("You gave an answer too recently") {
+        // e.g "You have 1m 23s left to wait."
+        let (_, rest) = response.split_once("You have ")?;
+        let (wait, _) = rest.split_once(" left to wait")?;
+        let seconds = wait
+            .split(' ')
+            .map(|amount| {
+                if let Some(minutes) = amount.strip_suffix('m') {
+                    minutes.parse::<u64>().ok().map(|m| m * 60)
+                } else {
+                    amount.strip_suffix('s')?.parse::<u64>().ok()
+                }
+            })
+            .sum::<Option<u64>>()?;
+        Some(SubmitOutcome::Wait(Duration::from_secs(seconds)))
+    } else if response.contains("You don't seem to be solving the right level") {
+        Some(SubmitOutcome::WrongLevel)
+    } else {
+        None
+    }
+}
+
+impl std::fmt::Display for SubmitOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "Correct!"),
+            SubmitOutcome::TooHigh => write!(f, "Incorrect, answer is too high"),
+            SubmitOutcome::TooLow => write!(f, "Incorrect, answer is too low"),
+            SubmitOutcome::Incorrect => write!(f, "Incorrect"),
+            SubmitOutcome::Wait(duration) => {
+                write!(f, "Answered too recently, wait {}s", duration.as_secs())
+            }
+            SubmitOutcome::WrongLevel => write!(f, "This part is already solved or locked"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_submit_response() {
+        assert_eq!(
+            parse_submit_response("<p>That's the right answer! You are one gold star closer"),
+            Some(SubmitOutcome::Correct)
+        );
+        assert_eq!(
+            parse_submit_response("<p>That's not the right answer; your answer is too high."),
+            Some(SubmitOutcome::TooHigh)
+        );
+        assert_eq!(
+            parse_submit_response("<p>That's not the right answer. If you're stuck"),
+            Some(SubmitOutcome::Incorrect)
+        );
+        assert_eq!(
+            parse_submit_response(
+                "<p>You gave an answer too recently. You have 1m 23s left to wait.</p>"
+            ),
+            Some(SubmitOutcome::Wait(Duration::from_secs(83)))
+        );
+        assert_eq!(parse_submit_response("<html></html>"), None);
+    }
+}
@@ -0,0 +1,34 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+/// The `k` largest items, largest first, without sorting everything.
+/// Only `k` items are kept at once, in a heap with the smallest of them on top.
+pub fn top_k<T: Ord>(items: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for item in items {
+        heap.push(Reverse(item));
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(item)| item)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_k() {
+        assert_eq!(top_k([5, 1, 9, 3, 7], 3), vec![9, 7, 5]);
+        assert_eq!(top_k([2, 2, 1, 2], 2), vec![2, 2]);
+        assert_eq!(top_k([4, 1], 5), vec![4, 1]);
+        assert_eq!(top_k([4, 1], 0), Vec::<i32>::new());
+        assert_eq!(top_k(Vec::<i32>::new(), 3), Vec::<i32>::new());
+    }
+}
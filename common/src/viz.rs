@@ -0,0 +1,288 @@
+//! Terminal animations, recordings and drawings of a puzzle's state
+
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use crossterm::{
+    cursor, queue,
+    style::Print,
+    terminal::{self, Clear, ClearType},
+};
+
+mod record;
+mod svg;
+
+use record::Recording;
+pub use svg::Svg;
+
+use crate::cli::Flags;
+
+/// How visualizers from `Visualizer::from_args` and drawings from `Svg::path_from_args` behave,
+/// taken from the shared flags a binary was given
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VizSettings {
+    /// Whether to animate in the terminal, with `--animate`
+    pub animate: bool,
+    /// Only draw every `N`th frame, with `--every N`
+    pub every: Option<usize>,
+    /// Where to record a GIF, with `--record PATH`
+    pub record: Option<PathBuf>,
+    /// Where to save a drawing, with `--svg PATH`
+    pub svg: Option<PathBuf>,
+}
+
+impl VizSettings {
+    /// Read the settings from a binary's flags, with an error if `--every` isn't a positive number
+    pub fn from_flags(flags: &Flags) -> Result<Self, String> {
+        let every = match flags.parse("every")? {
+            Some(0) => return Err("--every must be at least 1".to_owned()),
+            every => every,
+        };
+        Ok(Self {
+            animate: flags.is_set("animate"),
+            every,
+            record: flags.value("record").map(PathBuf::from),
+            svg: flags.value("svg").map(PathBuf::from),
+        })
+    }
+}
+
+/// The settings last handed to `configure`, which draw nothing until a binary sets them
+static SETTINGS: Mutex<VizSettings> = Mutex::new(VizSettings {
+    animate: false,
+    every: None,
+    record: None,
+    svg: None,
+});
+
+/// Set how visualizers and drawings made from now on behave, which `run` does with the flags a
+/// binary was given
+pub fn configure(settings: VizSettings) {
+    *SETTINGS.lock().unwrap() = settings;
+}
+
+/// The settings visualizers and drawings are currently made with
+pub fn settings() -> VizSettings {
+    SETTINGS.lock().unwrap().clone()
+}
+
+/// Draws frames of a simulation over each other in the terminal, and/or records them to a GIF.
+/// Does nothing unless enabled, so solvers can always hand it their state.
+#[derive(Debug)]
+pub struct Visualizer {
+    animate: bool,
+    recording: Option<Recording>,
+    delay: Duration,
+    every: usize,
+    /// Whether `every` was set by `--every N`, which wins over the solver's choice
+    every_from_args: bool,
+    frames: usize,
+}
+
+/// How many visualizers have been given a path by `--record`
+static RECORDINGS: AtomicUsize = AtomicUsize::new(0);
+
+impl Visualizer {
+    /// A visualizer set up by the flags the program was given (see `configure`), which only
+    /// animates with `--animate` and records with `--record PATH`. When several are recorded,
+    /// later paths are numbered. `--every N` only draws every `N`th frame.
+    pub fn from_args() -> Self {
+        Self::from_settings(&settings())
+    }
+
+    /// A visualizer set up by some settings rather than the program's flags
+    pub fn from_settings(settings: &VizSettings) -> Self {
+        let mut viz = Self::new(settings.animate);
+        if let Some(n) = settings.every {
+            viz = viz.every(n);
+            viz.every_from_args = true;
+        }
+        match &settings.record {
+            Some(path) => viz.record(numbered_path(
+                path,
+                RECORDINGS.fetch_add(1, Ordering::Relaxed),
+            )),
+            None => viz,
+        }
+    }
+
+    /// A visualizer that waits 50ms after drawing each frame
+    pub fn new(animate: bool) -> Self {
+        Self {
+            animate,
+            recording: None,
+            delay: Duration::from_millis(50),
+            every: 1,
+            every_from_args: false,
+            frames: 0,
+        }
+    }
+
+    /// Also write every drawn frame to an animated GIF at `path` once the visualizer is dropped
+    pub fn record(mut self, path: impl Into<PathBuf>) -> Self {
+        self.recording = Some(Recording::new(path.into()));
+        self
+    }
+
+    /// Set how long to wait after drawing each frame
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Only draw every `n`th frame, for simulations with many small steps.
+    /// Does nothing if the program was given `--every N`.
+    pub fn every(mut self, n: usize) -> Self {
+        if !self.every_from_args {
+            self.every = n.max(1);
+        }
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.animate || self.recording.is_some()
+    }
+
+    /// Draw the state of a simulation over the previous frame, then wait
+    pub fn frame(&mut self, state: &impl Display) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.frames += 1;
+        if !(self.frames - 1).is_multiple_of(self.every) {
+            return;
+        }
+        self.show(state);
+    }
+
+    /// Draw the final state of a simulation, even if `every` would skip it
+    pub fn final_frame(&mut self, state: &impl Display) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.frames += 1;
+        self.show(state);
+    }
+
+    fn show(&mut self, state: &impl Display) {
+        let frame = state.to_string();
+        if self.animate {
+            // Losing a frame isn't worth stopping the solver over
+            self.draw(&frame).ok();
+            thread::sleep(self.delay);
+        }
+        if let Some(recording) = &mut self.recording {
+            recording.push(frame);
+        }
+    }
+
+    fn draw(&self, frame: &str) -> io::Result<()> {
+        let (_, rows) = terminal::size().unwrap_or((80, 24));
+        let mut stdout = io::stdout().lock();
+        queue!(stdout, cursor::Hide, cursor::MoveTo(0, 0))?;
+        for line in visible_lines(frame, rows.saturating_sub(1).into()) {
+            queue!(
+                stdout,
+                Print(line),
+                Clear(ClearType::UntilNewLine),
+                Print("\n")
+            )?;
+        }
+        queue!(stdout, Clear(ClearType::FromCursorDown))?;
+        stdout.flush()
+    }
+}
+
+impl Drop for Visualizer {
+    fn drop(&mut self) {
+        if self.animate && self.frames > 0 {
+            crossterm::execute!(io::stdout(), cursor::Show).ok();
+        }
+        if let Some(recording) = &self.recording {
+            match recording.save(self.delay) {
+                Ok(()) => eprintln!("Recorded {}", recording),
+                Err(err) => eprintln!("Couldn't record {}: {}", recording, err),
+            }
+        }
+    }
+}
+
+/// Number a path after the first, e.g `day14.gif` then `day14-2.gif`
+pub fn numbered_path(path: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        return path.to_owned();
+    }
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{}-{}.{}", stem, index + 1, extension.to_string_lossy()),
+        None => format!("{}-{}", stem, index + 1),
+    };
+    path.with_file_name(name)
+}
+
+/// The lines of a frame that fit in the terminal, keeping the top of the frame
+fn visible_lines(frame: &str, rows: usize) -> impl Iterator<Item = &str> {
+    frame.lines().take(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_lines() {
+        let frame = "a\nb\nc\n";
+        assert_eq!(visible_lines(frame, 2).collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(visible_lines(frame, 10).count(), 3);
+    }
+
+    #[test]
+    fn test_settings_from_flags() {
+        let settings = |args: &[&str]| {
+            let args = args.iter().map(|arg| arg.to_string());
+            VizSettings::from_flags(&crate::cli::parse_from(args, &[])?.flags)
+        };
+        assert_eq!(settings(&[]), Ok(VizSettings::default()));
+        let given = settings(&["--animate", "--every", "3", "--svg=a.svg"]).unwrap();
+        assert!(given.animate);
+        assert_eq!(given.every, Some(3));
+        assert_eq!(given.svg, Some(PathBuf::from("a.svg")));
+        assert_eq!(
+            settings(&["--every", "abc"]),
+            Err("Invalid value abc for --every".to_owned())
+        );
+        assert_eq!(
+            settings(&["--every=0"]),
+            Err("--every must be at least 1".to_owned())
+        );
+
+        let viz = Visualizer::from_settings(&given);
+        assert!(viz.is_enabled());
+        assert_eq!(viz.every(5).every, 3);
+    }
+
+    #[test]
+    fn test_disabled_draws_nothing() {
+        let mut viz = Visualizer::new(false).every(0);
+        viz.frame(&"frame");
+        assert_eq!(viz.frames, 0);
+        assert!(!viz.is_enabled());
+    }
+
+    #[test]
+    fn test_numbered_path() {
+        let path = Path::new("out/day14.gif");
+        assert_eq!(numbered_path(path, 0), path);
+        assert_eq!(numbered_path(path, 1), Path::new("out/day14-2.gif"));
+        assert_eq!(numbered_path(Path::new("day9"), 2), Path::new("day9-3"));
+    }
+}
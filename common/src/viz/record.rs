@@ -0,0 +1,145 @@
+//! Writing a visualizer's frames to an animated GIF
+
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{self, BufWriter},
+    path::PathBuf,
+    time::Duration,
+};
+
+use gif::{Encoder, Frame, Repeat};
+
+/// Width and height in pixels of each character of a frame
+const CELL_SIZE: usize = 4;
+
+/// Colours characters are drawn in, with the background first
+const PALETTE: [[u8; 3]; 8] = [
+    [0x1e, 0x1e, 0x2e],
+    [0xf3, 0x8b, 0xa8],
+    [0xa6, 0xe3, 0xa1],
+    [0xf9, 0xe2, 0xaf],
+    [0x89, 0xb4, 0xfa],
+    [0xcb, 0xa6, 0xf7],
+    [0x94, 0xe2, 0xd5],
+    [0xcd, 0xd6, 0xf4],
+];
+
+/// Frames of a simulation as text, waiting to be drawn to a GIF
+#[derive(Debug)]
+pub(super) struct Recording {
+    path: PathBuf,
+    frames: Vec<String>,
+}
+
+impl Recording {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, frame: String) {
+        self.frames.push(frame);
+    }
+
+    /// Draw every frame as a block of pixels per character and write them to the GIF,
+    /// each shown for `delay`
+    pub fn save(&self, delay: Duration) -> io::Result<()> {
+        let frames: Vec<Vec<Vec<char>>> = self.frames.iter().map(|f| frame_cells(f)).collect();
+        let columns = frames.iter().flatten().map(Vec::len).max().unwrap_or(0);
+        let rows = frames.iter().map(Vec::len).max().unwrap_or(0);
+        let (width, height) = (columns.max(1) * CELL_SIZE, rows.max(1) * CELL_SIZE);
+        if width > u16::MAX as usize || height > u16::MAX as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "frames are too big for a GIF",
+            ));
+        }
+
+        let file = BufWriter::new(File::create(&self.path)?);
+        let mut encoder = Encoder::new(file, width as u16, height as u16, &PALETTE.concat())
+            .map_err(io::Error::other)?;
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(io::Error::other)?;
+        for cells in frames {
+            let pixels = rasterize(&cells, width, height);
+            let mut frame = Frame::from_indexed_pixels(width as u16, height as u16, &pixels, None);
+            frame.delay = (delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+            encoder.write_frame(&frame).map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Recording {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} frames to {}", self.frames.len(), self.path.display())
+    }
+}
+
+/// The characters of each line of a frame, without any terminal colour codes
+fn frame_cells(frame: &str) -> Vec<Vec<char>> {
+    frame
+        .lines()
+        .map(|line| {
+            let mut cells = Vec::new();
+            let mut chars = line.chars();
+            while let Some(c) = chars.next() {
+                if c == '\x1b' {
+                    // Skip to the end of the escape sequence
+                    chars.by_ref().find(|c| c.is_ascii_alphabetic());
+                } else {
+                    cells.push(c);
+                }
+            }
+            cells
+        })
+        .collect()
+}
+
+/// Palette index to draw a character in, so each kind of cell keeps the same colour
+fn cell_color(c: char) -> u8 {
+    match c {
+        ' ' | '.' => 0,
+        c => 1 + (c as u32 % (PALETTE.len() as u32 - 1)) as u8,
+    }
+}
+
+/// Draw the cells of a frame as palette indices, padding with the background
+fn rasterize(cells: &[Vec<char>], width: usize, height: usize) -> Vec<u8> {
+    let mut pixels = vec![0; width * height];
+    for (y, row) in cells.iter().enumerate() {
+        for (x, &c) in row.iter().enumerate() {
+            let color = cell_color(c);
+            for py in y * CELL_SIZE..(y + 1) * CELL_SIZE {
+                pixels[py * width + x * CELL_SIZE..py * width + (x + 1) * CELL_SIZE].fill(color);
+            }
+        }
+    }
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_cells() {
+        let cells = frame_cells("\x1b[33mo\x1b[0m.\n#");
+        assert_eq!(cells, vec![vec!['o', '.'], vec!['#']]);
+    }
+
+    #[test]
+    fn test_rasterize() {
+        let width = 2 * CELL_SIZE;
+        let pixels = rasterize(&[vec!['.', '#']], width, 2 * CELL_SIZE);
+        assert_eq!(pixels.len(), width * 2 * CELL_SIZE);
+        assert_eq!(pixels[0], 0);
+        assert_eq!(pixels[CELL_SIZE], cell_color('#'));
+        assert_ne!(cell_color('#'), 0);
+        assert_eq!(pixels[width * CELL_SIZE], 0);
+    }
+}
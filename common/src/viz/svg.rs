@@ -0,0 +1,143 @@
+//! Drawing puzzle state to SVG files, for inputs too big to fit in a terminal
+
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// A drawing of grid cells, lines, polygons and circles, sized to fit everything drawn.
+/// Strokes stay one pixel wide however far the drawing is zoomed.
+#[derive(Debug, Clone, Default)]
+pub struct Svg {
+    elements: Vec<String>,
+    bounds: Option<((f64, f64), (f64, f64))>,
+}
+
+impl Svg {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Where to save a drawing, if the program was given an `--svg PATH` flag (see
+    /// `viz::configure`)
+    pub fn path_from_args() -> Option<PathBuf> {
+        super::settings().svg
+    }
+
+    /// Fill the unit square with its top left corner at `(x, y)`
+    pub fn cell(&mut self, x: f64, y: f64, fill: &str) -> &mut Self {
+        self.include((x, y));
+        self.include((x + 1.0, y + 1.0));
+        self.elements.push(format!(
+            r#"<rect x="{}" y="{}" width="1" height="1" fill="{}"/>"#,
+            x, y, fill
+        ));
+        self
+    }
+
+    /// Draw a line through each point in turn
+    pub fn line(
+        &mut self,
+        points: impl IntoIterator<Item = (f64, f64)>,
+        stroke: &str,
+    ) -> &mut Self {
+        let points = self.points(points);
+        self.elements.push(format!(
+            r#"<polyline points="{}" fill="none" stroke="{}" vector-effect="non-scaling-stroke"/>"#,
+            points, stroke
+        ));
+        self
+    }
+
+    /// Draw a closed shape with corners at each point
+    pub fn polygon(
+        &mut self,
+        points: impl IntoIterator<Item = (f64, f64)>,
+        fill: &str,
+        stroke: &str,
+    ) -> &mut Self {
+        let points = self.points(points);
+        self.elements.push(format!(
+            r#"<polygon points="{}" fill="{}" stroke="{}" vector-effect="non-scaling-stroke"/>"#,
+            points, fill, stroke
+        ));
+        self
+    }
+
+    pub fn circle(&mut self, (x, y): (f64, f64), radius: f64, fill: &str) -> &mut Self {
+        self.include((x - radius, y - radius));
+        self.include((x + radius, y + radius));
+        self.elements.push(format!(
+            r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+            x, y, radius, fill
+        ));
+        self
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Grow the drawing to fit a point
+    fn include(&mut self, (x, y): (f64, f64)) {
+        let ((min_x, min_y), (max_x, max_y)) = self.bounds.unwrap_or(((x, y), (x, y)));
+        self.bounds = Some(((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y))));
+    }
+
+    /// Format points for a `points` attribute, growing the drawing to fit them
+    fn points(&mut self, points: impl IntoIterator<Item = (f64, f64)>) -> String {
+        points
+            .into_iter()
+            .map(|point| {
+                self.include(point);
+                format!("{},{}", point.0, point.1)
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl Display for Svg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ((min_x, min_y), (max_x, max_y)) = self.bounds.unwrap_or_default();
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+            min_x,
+            min_y,
+            (max_x - min_x).max(1.0),
+            (max_y - min_y).max(1.0)
+        )?;
+        for element in &self.elements {
+            writeln!(f, "  {}", element)?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svg() {
+        let mut svg = Svg::new();
+        svg.cell(0.0, 0.0, "red")
+            .line([(0.5, 0.5), (3.5, 0.5)], "blue")
+            .circle((2.0, -1.0), 0.5, "green");
+        assert_eq!(
+            svg.to_string(),
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 -1.5 3.5 2.5">"#,
+                "\n",
+                r#"  <rect x="0" y="0" width="1" height="1" fill="red"/>"#,
+                "\n",
+                r#"  <polyline points="0.5,0.5 3.5,0.5" fill="none" stroke="blue" vector-effect="non-scaling-stroke"/>"#,
+                "\n",
+                r#"  <circle cx="2" cy="-1" r="0.5" fill="green"/>"#,
+                "\n</svg>\n"
+            )
+        );
+    }
+}
@@ -0,0 +1,92 @@
+//! Finding runs of items that are all different, like the markers in day 6's datastream
+
+use std::{collections::VecDeque, hash::Hash};
+
+use crate::FastMap;
+
+/// How many items have been read once the last `k` were all different, or `None` if there are
+/// never `k` different items in a row. Keeps a count of each item in the window and how many
+/// are in it more than once, so each item is only looked at when it enters and leaves.
+pub fn first_unique_window<T: Hash + Eq + Clone>(
+    items: impl IntoIterator<Item = T>,
+    k: usize,
+) -> Option<usize> {
+    if k == 0 {
+        return Some(0);
+    }
+
+    let mut window = VecDeque::with_capacity(k + 1);
+    let mut counts = FastMap::default();
+    let mut duplicates = 0;
+    for (i, item) in items.into_iter().enumerate() {
+        // Add the new item
+        let count = counts.entry(item.clone()).or_insert(0);
+        *count += 1;
+        if *count == 2 {
+            duplicates += 1;
+        }
+        window.push_back(item);
+
+        // Remove the item that fell out of the window
+        if window.len() > k {
+            let old = window.pop_front()?;
+            let count = counts.get_mut(&old)?;
+            if *count == 2 {
+                duplicates -= 1;
+            }
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(&old);
+            }
+        }
+
+        if window.len() == k && duplicates == 0 {
+            return Some(i + 1);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Set;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    /// Check every window from the start
+    fn brute_force<T: Hash + Eq>(items: &[T], k: usize) -> Option<usize> {
+        (k..=items.len()).find(|&end| items[end - k..end].iter().collect::<Set<_>>().len() == k)
+    }
+
+    #[test]
+    fn test_first_unique_window() {
+        assert_eq!(
+            first_unique_window("mjqjpqmgbljsphdztnvjfqwrcgsmlb".chars(), 4),
+            Some(7)
+        );
+        assert_eq!(first_unique_window([1, 2, 1, 2, 3], 3), Some(5));
+        assert_eq!(first_unique_window([1, 1, 1], 2), None);
+        assert_eq!(first_unique_window([1], 2), None);
+        assert_eq!(first_unique_window(Vec::<u8>::new(), 0), Some(0));
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut rng = StdRng::seed_from_u64(6);
+        for _ in 0..2000 {
+            // Small alphabets so windows often have repeats
+            let alphabet = rng.gen_range(1..8);
+            let items = (0..rng.gen_range(0..40))
+                .map(|_| rng.gen_range(0..alphabet))
+                .collect::<Vec<u8>>();
+            let k = rng.gen_range(0..10);
+            assert_eq!(
+                first_unique_window(items.iter(), k),
+                brute_force(&items, k),
+                "{:?} with k = {}",
+                items,
+                k
+            );
+        }
+    }
+}
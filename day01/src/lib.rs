@@ -0,0 +1,99 @@
+use common::{parse, top_k, Solution};
+
+/// The calories of each snack an elf is carrying
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    pub snacks: Vec<usize>,
+}
+
+impl Elf {
+    pub fn total(&self) -> usize {
+        self.snacks.iter().sum()
+    }
+}
+
+/// Read each elf's snacks, with elves separated by blank lines. Blank lines at the end are
+/// ignored.
+pub fn parse_inventories(input: &str) -> common::Result<Vec<Elf>> {
+    parse::blocks_with(input, |block| {
+        Ok(Elf {
+            snacks: parse::parse_lines(block)?,
+        })
+    })
+}
+
+/// The most calories any elf is carrying
+pub fn max_total(elves: &[Elf]) -> Option<usize> {
+    elves.iter().map(Elf::total).max()
+}
+
+/// The calories carried by the `k` elves carrying the most
+pub fn top_k_total(elves: &[Elf], k: usize) -> usize {
+    top_k(elves.iter().map(Elf::total), k).into_iter().sum()
+}
+
+pub struct Day1;
+
+common::solution_fns!(Day1);
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Input = Vec<Elf>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn validate(input: &str) -> common::Result<()> {
+        parse_inventories(input).map(|_| ())
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        parse_inventories(input).unwrap()
+    }
+
+    fn part1(elves: &Self::Input) -> Self::Part1 {
+        max_total(elves).expect("No elves")
+    }
+
+    fn part2(elves: &Self::Input) -> Self::Part2 {
+        top_k_total(elves, 3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::sample_test;
+
+    #[test]
+    fn test_parse_inventories() {
+        let elves = parse_inventories(include_str!("../sample.txt")).unwrap();
+        assert_eq!(elves.len(), 5);
+        assert_eq!(elves[0].snacks, vec![1000, 2000, 3000]);
+        assert_eq!(
+            elves.iter().map(Elf::total).collect::<Vec<_>>(),
+            vec![6000, 4000, 11000, 24000, 10000]
+        );
+
+        // Trailing blank lines don't make an empty elf
+        assert_eq!(parse_inventories("1\n2\n\n3\n\n\n\n").unwrap().len(), 2);
+
+        let err = parse_inventories("1\n\n2\nthree").unwrap_err();
+        assert!(
+            matches!(err, common::AocError::Parse { line: 4, .. }),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_totals() {
+        let elves = parse_inventories(include_str!("../sample.txt")).unwrap();
+        assert_eq!(max_total(&elves), Some(24000));
+        assert_eq!(max_total(&[]), None);
+        assert_eq!(top_k_total(&elves, 2), 24000 + 11000);
+        assert_eq!(top_k_total(&elves, 10), 55000);
+    }
+
+    sample_test!(test_part1_with_sample, Day1::part1, file "sample.txt", 24000);
+    sample_test!(test_part2_with_sample, Day1::part2, file "sample.txt", 45000);
+}
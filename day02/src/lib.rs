@@ -0,0 +1,270 @@
+use std::{iter::Sum, ops::Add, str::FromStr};
+
+use common::{parse, AocError, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl Outcome {
+    pub fn score(&self) -> usize {
+        match self {
+            Outcome::Win => 6,
+            Outcome::Draw => 3,
+            Outcome::Loss => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Move {
+    pub fn score(&self) -> usize {
+        match self {
+            Move::Rock => 1,
+            Move::Paper => 2,
+            Move::Scissors => 3,
+        }
+    }
+
+    /// The move this one wins against
+    pub fn beats(&self) -> Move {
+        match self {
+            Move::Rock => Move::Scissors,
+            Move::Paper => Move::Rock,
+            Move::Scissors => Move::Paper,
+        }
+    }
+
+    /// The move this one loses against
+    pub fn loses_to(&self) -> Move {
+        self.beats().beats()
+    }
+
+    pub fn outcome_against(&self, other: &Move) -> Outcome {
+        if self.beats() == *other {
+            Outcome::Win
+        } else if self.loses_to() == *other {
+            Outcome::Loss
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// The move to play against this one to get an outcome
+    pub fn for_outcome_against(&self, outcome: &Outcome) -> Self {
+        match outcome {
+            Outcome::Win => self.loses_to(),
+            Outcome::Draw => *self,
+            Outcome::Loss => self.beats(),
+        }
+    }
+}
+
+/// The second column of the strategy guide, which means a move in part 1 but the outcome to
+/// aim for in part 2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Response {
+    X,
+    Y,
+    Z,
+}
+
+impl Response {
+    /// Rock, paper or scissors
+    pub fn as_move(&self) -> Move {
+        match self {
+            Response::X => Move::Rock,
+            Response::Y => Move::Paper,
+            Response::Z => Move::Scissors,
+        }
+    }
+
+    /// Lose, draw or win
+    pub fn as_outcome(&self) -> Outcome {
+        match self {
+            Response::X => Outcome::Loss,
+            Response::Y => Outcome::Draw,
+            Response::Z => Outcome::Win,
+        }
+    }
+}
+
+/// A round of the strategy guide, of what the opponent will play and how to respond
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    pub theirs: Move,
+    pub response: Response,
+}
+
+impl Round {
+    /// The score when the response is the move to play
+    pub fn score_by_move(&self) -> ScoreBreakdown {
+        let mine = self.response.as_move();
+        ScoreBreakdown::new(mine, mine.outcome_against(&self.theirs))
+    }
+
+    /// The score when the response is the outcome to aim for
+    pub fn score_by_outcome(&self) -> ScoreBreakdown {
+        let outcome = self.response.as_outcome();
+        ScoreBreakdown::new(self.theirs.for_outcome_against(&outcome), outcome)
+    }
+}
+
+/// A score split into the points for the shapes played and the points for the outcomes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScoreBreakdown {
+    pub shape: usize,
+    pub outcome: usize,
+}
+
+impl ScoreBreakdown {
+    fn new(mine: Move, outcome: Outcome) -> Self {
+        Self {
+            shape: mine.score(),
+            outcome: outcome.score(),
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.shape + self.outcome
+    }
+}
+
+impl Add for ScoreBreakdown {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            shape: self.shape + other.shape,
+            outcome: self.outcome + other.outcome,
+        }
+    }
+}
+
+impl Sum for ScoreBreakdown {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+/// The score from following the guide with the second column as moves
+pub fn part1_breakdown(rounds: &[Round]) -> ScoreBreakdown {
+    rounds.iter().map(Round::score_by_move).sum()
+}
+
+/// The score from following the guide with the second column as outcomes
+pub fn part2_breakdown(rounds: &[Round]) -> ScoreBreakdown {
+    rounds.iter().map(Round::score_by_outcome).sum()
+}
+
+pub struct Day2;
+
+common::solution_fns!(Day2);
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Input = Vec<Round>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn validate(input: &str) -> common::Result<()> {
+        parse::parse_lines::<Round>(input).map(|_| ())
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        parse::parse_lines(input).unwrap()
+    }
+
+    fn part1(rounds: &Self::Input) -> Self::Part1 {
+        part1_breakdown(rounds).total()
+    }
+
+    fn part2(rounds: &Self::Input) -> Self::Part2 {
+        part2_breakdown(rounds).total()
+    }
+}
+
+/* Parsing */
+
+impl FromStr for Round {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (theirs, response) = s
+            .split_once(' ')
+            .ok_or_else(|| AocError::parse("Expected two columns"))?;
+        let theirs = match theirs {
+            "A" => Move::Rock,
+            "B" => Move::Paper,
+            "C" => Move::Scissors,
+            _ => return Err(AocError::parse_at(s, theirs, "Expected A, B or C")),
+        };
+        let response = match response {
+            "X" => Response::X,
+            "Y" => Response::Y,
+            "Z" => Response::Z,
+            _ => return Err(AocError::parse_at(s, response, "Expected X, Y or Z")),
+        };
+        Ok(Round { theirs, response })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::sample_test;
+
+    #[test]
+    fn test_moves() {
+        for mine in [Move::Rock, Move::Paper, Move::Scissors] {
+            assert_eq!(mine.outcome_against(&mine.beats()), Outcome::Win);
+            assert_eq!(mine.outcome_against(&mine.loses_to()), Outcome::Loss);
+            assert_eq!(mine.outcome_against(&mine), Outcome::Draw);
+            for outcome in [Outcome::Win, Outcome::Draw, Outcome::Loss] {
+                let response = mine.for_outcome_against(&outcome);
+                assert_eq!(response.outcome_against(&mine), outcome);
+            }
+        }
+    }
+
+    #[test]
+    fn test_score_breakdowns() {
+        let rounds = Day2::parse(include_str!("../sample.txt"));
+        assert_eq!(
+            part1_breakdown(&rounds),
+            ScoreBreakdown {
+                shape: 6,
+                outcome: 9
+            }
+        );
+        assert_eq!(
+            part2_breakdown(&rounds),
+            ScoreBreakdown {
+                shape: 3,
+                outcome: 9
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "A W".parse::<Round>().unwrap_err().to_string(),
+            "Parse error at 1:3: Expected X, Y or Z"
+        );
+        assert!("D X".parse::<Round>().is_err());
+        assert!("AX".parse::<Round>().is_err());
+    }
+
+    sample_test!(test_part1_with_sample, Day2::part1, file "sample.txt", 15);
+    sample_test!(test_part2_with_sample, Day2::part2, file "sample.txt", 12);
+}
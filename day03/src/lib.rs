@@ -0,0 +1,191 @@
+use std::str::FromStr;
+
+use common::{parse, AocError, Solution};
+
+/// The items in each half of a rucksack, as bitmasks with bit `p` set for each item with
+/// priority `p`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rucksack {
+    compartment_1: u64,
+    compartment_2: u64,
+}
+
+impl Rucksack {
+    /// Every item in either compartment
+    pub fn items_mask(&self) -> u64 {
+        self.compartment_1 | self.compartment_2
+    }
+
+    /// The item in both compartments
+    pub fn common_item(&self) -> Option<char> {
+        item_in_mask(self.compartment_1 & self.compartment_2)
+    }
+
+    /// The item carried by every elf in a group
+    pub fn badge(group: &[Rucksack; 3]) -> Option<char> {
+        item_in_mask(group.iter().fold(!0, |mask, r| mask & r.items_mask()))
+    }
+
+    pub fn item_priority(ch: char) -> u8 {
+        let ord = ch as u8;
+        if ch.is_uppercase() {
+            ord - b'A' + 27
+        } else {
+            ord - b'a' + 1
+        }
+    }
+
+    /// The item with a given priority, from 1 to 52
+    pub fn item_with_priority(priority: u8) -> char {
+        match priority {
+            1..=26 => (b'a' + priority - 1) as char,
+            _ => (b'A' + priority - 27) as char,
+        }
+    }
+}
+
+/// The lowest priority item in a bitmask of items
+fn item_in_mask(mask: u64) -> Option<char> {
+    (mask != 0).then(|| Rucksack::item_with_priority(mask.trailing_zeros() as u8))
+}
+
+/// Split rucksacks into the groups of three elves that carry them, failing if any are left over
+pub fn groups<T>(items: impl IntoIterator<Item = T>) -> common::Result<Vec<[T; 3]>> {
+    let mut items = items.into_iter();
+    let mut groups = Vec::new();
+    loop {
+        match (items.next(), items.next(), items.next()) {
+            (Some(a), Some(b), Some(c)) => groups.push([a, b, c]),
+            (None, _, _) => return Ok(groups),
+            (Some(_), left_over, _) => {
+                return Err(AocError::BadInput(format!(
+                    "{} rucksacks left over after the last group of three",
+                    1 + left_over.iter().count()
+                )))
+            }
+        }
+    }
+}
+
+impl FromStr for Rucksack {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((i, c)) = s.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+            return Err(AocError::parse_at(
+                s,
+                &s[i..],
+                format!("Unexpected item {:?}", c),
+            ));
+        }
+        if !s.len().is_multiple_of(2) {
+            return Err(AocError::parse(format!(
+                "Rucksack has an odd number of items ({})",
+                s.len()
+            )));
+        }
+
+        // Set a bit for each item
+        let mask = |items: &str| {
+            items
+                .chars()
+                .fold(0, |mask, c| mask | 1 << Rucksack::item_priority(c))
+        };
+        let (compartment_1, compartment_2) = s.split_at(s.len() / 2);
+        Ok(Rucksack {
+            compartment_1: mask(compartment_1),
+            compartment_2: mask(compartment_2),
+        })
+    }
+}
+
+pub struct Day3;
+
+common::solution_fns!(Day3);
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Input = Vec<Rucksack>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn validate(input: &str) -> common::Result<()> {
+        groups(parse::parse_lines::<Rucksack>(input)?).map(|_| ())
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        // Parse input into rucksacks
+        parse::parse_lines(input).unwrap()
+    }
+
+    fn part1(rucksacks: &Self::Input) -> Self::Part1 {
+        // Sum priorities
+        rucksacks
+            .iter()
+            .map(|r| Rucksack::item_priority(r.common_item().unwrap()) as usize)
+            .sum()
+    }
+
+    fn part2(rucksacks: &Self::Input) -> Self::Part2 {
+        groups(rucksacks.iter().copied())
+            .unwrap()
+            .iter()
+            .map(|group| Rucksack::badge(group).unwrap())
+            .map(|item| Rucksack::item_priority(item) as usize)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::sample_test;
+
+    #[test]
+    fn test_item_prio() {
+        assert_eq!(Rucksack::item_priority('a'), 1);
+        assert_eq!(Rucksack::item_priority('p'), 16);
+        assert_eq!(Rucksack::item_priority('t'), 20);
+        assert_eq!(Rucksack::item_priority('A'), 27);
+        assert_eq!(Rucksack::item_priority('Z'), 52);
+        for priority in 1..=52 {
+            let item = Rucksack::item_with_priority(priority);
+            assert_eq!(Rucksack::item_priority(item), priority);
+        }
+    }
+
+    #[test]
+    fn test_rucksack_items() {
+        let rucksack: Rucksack = "vJrwpWtwJgWrhcsFMMfFFhFp".parse().unwrap();
+        assert_eq!(rucksack.common_item(), Some('p'));
+        assert_eq!(rucksack.items_mask().count_ones(), 14);
+        assert!("abc".parse::<Rucksack>().is_err());
+        assert_eq!(
+            "ab1d".parse::<Rucksack>().unwrap_err().to_string(),
+            "Parse error at 1:3: Unexpected item '1'"
+        );
+    }
+
+    #[test]
+    fn test_groups() {
+        let rucksacks = Day3::parse(include_str!("../sample.txt"));
+        let sample_groups = groups(rucksacks).unwrap();
+        assert_eq!(Rucksack::badge(&sample_groups[0]), Some('r'));
+        assert_eq!(Rucksack::badge(&sample_groups[1]), Some('Z'));
+        assert_eq!(
+            groups(0..7),
+            Err(AocError::BadInput(
+                "1 rucksacks left over after the last group of three".into()
+            ))
+        );
+        assert_eq!(
+            groups(0..5),
+            Err(AocError::BadInput(
+                "2 rucksacks left over after the last group of three".into()
+            ))
+        );
+    }
+
+    sample_test!(test_part1_with_sample, Day3::part1, file "sample.txt", 157);
+    sample_test!(test_part2_with_sample, Day3::part2, file "sample.txt", 70);
+}
@@ -0,0 +1,82 @@
+use common::{parse, AocError, Solution, Span};
+
+type Range = Span<usize>;
+
+#[derive(Debug)]
+pub struct Assignment(Range, Range);
+
+impl std::str::FromStr for Assignment {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (s1, s2) = s
+            .split_once(',')
+            .ok_or_else(|| AocError::parse("Missing section"))?;
+        Ok(Self(section(s, s1)?, section(s, s2)?))
+    }
+}
+
+/// Parse one elf's sections, giving errors their place in the line
+fn section(line: &str, section: &str) -> common::Result<Range> {
+    section.parse().map_err(|err| match err {
+        AocError::Parse { message, .. } => AocError::parse_at(line, section, message),
+        err => err,
+    })
+}
+
+pub struct Day4;
+
+common::solution_fns!(Day4);
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Input = Vec<Assignment>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn validate(input: &str) -> common::Result<()> {
+        parse::parse_lines::<Assignment>(input).map(|_| ())
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        // Parse assignment
+        parse::parse_lines(input).unwrap()
+    }
+
+    fn part1(assignments: &Self::Input) -> Self::Part1 {
+        // Find encompassing assignments
+        assignments
+            .iter()
+            .filter(|ass| ass.0.encompasses(&ass.1) || ass.1.encompasses(&ass.0))
+            .count()
+    }
+
+    fn part2(assignments: &Self::Input) -> Self::Part2 {
+        // Find overlapping assignments
+        assignments
+            .iter()
+            .filter(|ass| ass.0.overlaps(&ass.1))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::sample_test;
+
+    const SAMPLE: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+
+    #[test]
+    fn test_parse_errors() {
+        let err = "2-4,8-6".parse::<Assignment>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error at 1:5: Span '8-6' ends before it starts"
+        );
+        assert!("2-4".parse::<Assignment>().is_err());
+    }
+
+    sample_test!(test_part1_with_sample, Day4::part1, SAMPLE, 2);
+    sample_test!(test_part2_with_sample, Day4::part2, SAMPLE, 4);
+}
@@ -0,0 +1,341 @@
+use std::{fmt::Display, str::FromStr};
+
+use common::{parse, viz::Visualizer, AocError, Solution};
+use nom::bytes::complete::tag;
+
+// Bottom to top stack
+type Stack = Vec<char>;
+
+// Stacks from left to right
+#[derive(Debug, Clone)]
+pub struct Stacks(Vec<Stack>);
+
+impl Display for Stacks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, stack) in self.0.iter().enumerate() {
+            let s = stack.iter().collect::<String>();
+            f.write_str(&format!("{} {} \n", i + 1, s))?;
+        }
+        Ok(())
+    }
+}
+
+/// Which crane is doing the rearranging, since they move crates differently
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CraneModel {
+    /// Moves one crate at a time, so a group of crates ends up reversed
+    CrateMover9000,
+
+    /// Moves many crates at once, keeping them in order
+    CrateMover9001,
+}
+
+impl Stacks {
+    pub fn apply_instruction(&mut self, instruction: &Instruction, model: CraneModel) {
+        match model {
+            CraneModel::CrateMover9000 => {
+                // Repeatedly shift items between stacks
+                (0..instruction.amount).for_each(|_| {
+                    let item = self.0.get_mut(instruction.from).unwrap().pop().unwrap();
+                    self.0.get_mut(instruction.to).unwrap().push(item);
+                });
+            }
+            CraneModel::CrateMover9001 => {
+                // Drain the last N items and then push them onto the other
+                let from_stack = self.0.get_mut(instruction.from).unwrap();
+                let tail_items = from_stack.split_off(from_stack.len() - instruction.amount);
+                for item in tail_items {
+                    self.0.get_mut(instruction.to).unwrap().push(item);
+                }
+            }
+        }
+    }
+
+    /// Undo an instruction, by moving the crates back the way the crane brought them
+    pub fn apply_inverse(&mut self, instruction: &Instruction, model: CraneModel) {
+        let reversed = Instruction {
+            from: instruction.to,
+            to: instruction.from,
+            ..*instruction
+        };
+        self.apply_instruction(&reversed, model);
+    }
+
+    /// Carry out every instruction in order
+    pub fn apply_all(&mut self, instructions: &[Instruction], model: CraneModel) {
+        self.rearrange(instructions, model, &mut Visualizer::new(false));
+    }
+
+    /// Carry out every instruction in order, drawing the stacks before starting and after
+    /// each instruction
+    pub fn rearrange(
+        &mut self,
+        instructions: &[Instruction],
+        model: CraneModel,
+        viz: &mut Visualizer,
+    ) {
+        viz.frame(self);
+        for instruction in instructions {
+            self.apply_instruction(instruction, model);
+            viz.frame(self);
+        }
+    }
+
+    pub fn get_stack_tops(&self) -> String {
+        self.0
+            .iter()
+            .flat_map(|stack| stack.iter().last())
+            .collect()
+    }
+}
+
+impl FromStr for Stacks {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The last line numbers the stacks from 1
+        let mut rows = s.lines().collect::<Vec<_>>();
+        let labels = rows
+            .pop()
+            .ok_or_else(|| AocError::BadInput("No stacks found".into()))?;
+        let count = labels.split_whitespace().count();
+        if count == 0
+            || labels
+                .split_whitespace()
+                .ne((1..=count).map(|i| i.to_string()))
+        {
+            return Err(AocError::parse_at(
+                s,
+                labels,
+                format!("Expected stacks numbered from 1, found '{}'", labels.trim()),
+            ));
+        }
+
+        // Each stack is a 4 character wide slot, with the crate's letter second
+        let width = count * 4 - 1;
+        let mut stacks = vec![Stack::new(); count];
+        for &row in rows.iter().chain([&labels]) {
+            if row.len() != width {
+                return Err(AocError::parse_at(
+                    s,
+                    row,
+                    format!("Stack rows must be padded to {} characters", width),
+                ));
+            }
+        }
+        for row in rows.iter().rev() {
+            for (stack, c) in stacks.iter_mut().zip(row.chars().skip(1).step_by(4)) {
+                if !c.is_whitespace() {
+                    stack.push(c);
+                }
+            }
+        }
+
+        if stacks.iter().all(|stack| stack.is_empty()) {
+            return Err(AocError::BadInput("No crates found in stacks".into()));
+        }
+        Ok(Stacks(stacks))
+    }
+}
+
+#[derive(Debug)]
+pub struct Instruction {
+    /// Amount of crates to move
+    amount: usize,
+
+    /// Index of stack to move from
+    from: usize,
+
+    /// Index of stack to move to
+    to: usize,
+}
+
+impl FromStr for Instruction {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rest, _, amount) = keyword_number(s, s, "move ")?;
+        let (rest, from_at, from) = keyword_number(s, rest, " from ")?;
+        let (rest, to_at, to) = keyword_number(s, rest, " to ")?;
+        if !rest.is_empty() {
+            return Err(AocError::parse_at(
+                s,
+                rest,
+                "Unexpected input after instruction",
+            ));
+        }
+
+        // Stacks are numbered from 1
+        let index = |stack: usize, at: &str| {
+            stack
+                .checked_sub(1)
+                .ok_or_else(|| AocError::parse_at(s, at, "Stacks are numbered from 1"))
+        };
+        Ok(Instruction {
+            amount,
+            from: index(from, from_at)?,
+            to: index(to, to_at)?,
+        })
+    }
+}
+
+/// Parse a keyword followed by a number, like ` from 2`, giving the input after it, where the
+/// number started and the number
+fn keyword_number<'a>(
+    input: &'a str,
+    rest: &'a str,
+    keyword: &str,
+) -> common::Result<(&'a str, &'a str, usize)> {
+    let (digits, _) = tag::<_, _, nom::error::Error<&str>>(keyword)(rest)
+        .map_err(|_| AocError::parse_at(input, rest, format!("Expected '{}'", keyword.trim())))?;
+    let (rest, number) = parse::unsigned_int::<usize>(digits).map_err(|_| {
+        AocError::parse_at(
+            input,
+            digits,
+            format!("Expected a number after '{}'", keyword.trim()),
+        )
+    })?;
+    Ok((rest, digits, number))
+}
+
+pub struct Day5;
+
+common::solution_fns!(Day5);
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Input = (Stacks, Vec<Instruction>);
+    type Part1 = String;
+    type Part2 = String;
+
+    fn validate(input: &str) -> common::Result<()> {
+        // The stacks are drawn above the instructions
+        let (stacks, _) = input
+            .split_once("\n\n")
+            .ok_or_else(|| AocError::parse("Missing blank line between stacks and instructions"))?;
+        stacks.parse::<Stacks>().map(|_| ())
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        let (stacks, instructions) = input
+            .split_once("\n\n")
+            .expect("Missing blank line between stacks and instructions");
+        let instruction_line = stacks.lines().count() + 1;
+        let stacks: Stacks = stacks.parse().unwrap();
+        let instructions: Vec<Instruction> = parse::parse_lines(instructions)
+            .map_err(|err| err.offset_lines(instruction_line))
+            .unwrap();
+        (stacks, instructions)
+    }
+
+    fn part1((stacks, instructions): &Self::Input) -> Self::Part1 {
+        let mut stacks = stacks.clone();
+
+        // Apply instructions
+        let mut viz = Visualizer::from_args();
+        stacks.rearrange(instructions, CraneModel::CrateMover9000, &mut viz);
+
+        // Get top of each stacks
+        stacks.get_stack_tops()
+    }
+
+    fn part2((stacks, instructions): &Self::Input) -> Self::Part2 {
+        let mut stacks = stacks.clone();
+
+        // Apply instructions
+        let mut viz = Visualizer::from_args();
+        stacks.rearrange(instructions, CraneModel::CrateMover9001, &mut viz);
+
+        // Get top of each stacks
+        stacks.get_stack_tops()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::sample_test;
+
+    #[test]
+    fn test_parse_stacks() {
+        let (stacks, instructions) = Day5::parse(include_str!("../sample.txt"));
+        assert_eq!(
+            stacks.0,
+            vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]
+        );
+        assert_eq!(instructions.len(), 4);
+    }
+
+    #[test]
+    fn test_ragged_stacks() {
+        let err = "[A]\n[B] [C]\n 1   2 ".parse::<Stacks>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error at 1:1: Stack rows must be padded to 7 characters"
+        );
+        let err = "[A] [B]\n 1   3 ".parse::<Stacks>().unwrap_err();
+        assert!(matches!(err, AocError::Parse { line: 2, .. }), "{}", err);
+        assert!("[A]\n   ".parse::<Stacks>().is_err());
+    }
+
+    #[test]
+    fn test_parse_instructions() {
+        let instruction: Instruction = "move 3 from 1 to 12".parse().unwrap();
+        assert_eq!(
+            (instruction.amount, instruction.from, instruction.to),
+            (3, 0, 11)
+        );
+
+        let error = |s: &str| s.parse::<Instruction>().unwrap_err().to_string();
+        assert_eq!(
+            error("move 3 form 1 to 2"),
+            "Parse error at 1:7: Expected 'from'"
+        );
+        assert_eq!(
+            error("move three from 1 to 2"),
+            "Parse error at 1:6: Expected a number after 'move'"
+        );
+        assert_eq!(
+            error("move 3 from 1 to 0"),
+            "Parse error at 1:18: Stacks are numbered from 1"
+        );
+        assert_eq!(
+            error("move 3 from 1 to 2 now"),
+            "Parse error at 1:19: Unexpected input after instruction"
+        );
+    }
+
+    #[test]
+    fn test_crane_models() {
+        let (stacks, instructions) = Day5::parse(include_str!("../sample.txt"));
+        let after_two_moves = |model| {
+            let mut stacks = stacks.clone();
+            stacks.apply_all(&instructions[..2], model);
+            stacks.0
+        };
+        assert_eq!(
+            after_two_moves(CraneModel::CrateMover9000)[2],
+            vec!['P', 'D', 'N', 'Z']
+        );
+        assert_eq!(
+            after_two_moves(CraneModel::CrateMover9001)[2],
+            vec!['P', 'Z', 'N', 'D']
+        );
+    }
+
+    #[test]
+    fn test_undo_instructions() {
+        let (stacks, instructions) = Day5::parse(include_str!("../sample.txt"));
+        for model in [CraneModel::CrateMover9000, CraneModel::CrateMover9001] {
+            let mut undone = stacks.clone();
+            undone.apply_all(&instructions, model);
+            for instruction in instructions.iter().rev() {
+                undone.apply_inverse(instruction, model);
+            }
+            assert_eq!(undone.0, stacks.0, "{:?}", model);
+        }
+    }
+
+    sample_test!(test_part1_with_sample, Day5::part1, file "sample.txt", "CMZ");
+    sample_test!(test_part2_with_sample, Day5::part2, file "sample.txt", "MCD");
+}
@@ -0,0 +1,94 @@
+use common::{windows::first_unique_window, AocError, Solution};
+
+pub struct Day6;
+
+common::solution_fns!(Day6);
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn validate(input: &str) -> common::Result<()> {
+        // Markers are counted in lowercase letters
+        match input
+            .trim_end()
+            .char_indices()
+            .find(|(_, c)| !c.is_ascii_lowercase())
+        {
+            Some((i, c)) => Err(AocError::parse_at(
+                input,
+                &input[i..],
+                format!("Unexpected character {:?}", c),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        input.trim_end().to_owned()
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        first_unique_window(input.bytes(), 4).expect("No start-of-packet marker")
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        first_unique_window(input.bytes(), 14).expect("No start-of-message marker")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::sample_test;
+
+    const SAMPLES: [(&str, usize, usize); 5] = [
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+
+    #[test]
+    fn test_samples() {
+        for (stream, packet, message) in SAMPLES {
+            assert_eq!(
+                first_unique_window(stream.bytes(), 4),
+                Some(packet),
+                "{}",
+                stream
+            );
+            assert_eq!(
+                first_unique_window(stream.bytes(), 14),
+                Some(message),
+                "{}",
+                stream
+            );
+        }
+    }
+
+    #[test]
+    fn test_no_marker() {
+        assert_eq!(first_unique_window(*b"abcabcabc", 4), None);
+        assert_eq!(first_unique_window(*b"abc", 4), None);
+        assert_eq!(first_unique_window(*b"abcd", 4), Some(4));
+        assert!(Day6::validate("abcd\n").is_ok());
+        assert!(Day6::validate("abCd").is_err());
+    }
+
+    sample_test!(
+        test_part1_with_sample,
+        Day6::part1,
+        "bvwbjplbgvbhsrlpgdmjqwftvncz\n",
+        5
+    );
+    sample_test!(
+        test_part2_with_sample,
+        Day6::part2,
+        "bvwbjplbgvbhsrlpgdmjqwftvncz\n",
+        23
+    );
+}
@@ -0,0 +1,203 @@
+//! A filesystem of directories and files, built up from what the terminal output shows
+
+/// A directory or file in a filesystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    Dir,
+    File,
+}
+
+#[derive(Debug)]
+struct Node {
+    name: String,
+    parent: Option<NodeId>,
+    contents: Contents,
+}
+
+#[derive(Debug)]
+enum Contents {
+    Dir(Vec<NodeId>),
+    File(usize),
+}
+
+/// Every node is kept in one list, with each added after its parent
+#[derive(Debug)]
+pub struct FileSystem {
+    nodes: Vec<Node>,
+}
+
+impl FileSystem {
+    /// The root directory, `/`
+    pub const ROOT: NodeId = NodeId(0);
+
+    /// A filesystem with nothing but an empty root directory
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![Node {
+                name: "/".to_owned(),
+                parent: None,
+                contents: Contents::Dir(Vec::new()),
+            }],
+        }
+    }
+
+    /// Add a directory inside another, or find it if it's already there
+    pub fn add_dir(&mut self, parent: NodeId, name: &str) -> NodeId {
+        self.add(parent, name, Contents::Dir(Vec::new()))
+    }
+
+    /// Add a file inside a directory, or find it if it's already there
+    pub fn add_file(&mut self, parent: NodeId, name: &str, size: usize) -> NodeId {
+        self.add(parent, name, Contents::File(size))
+    }
+
+    fn add(&mut self, parent: NodeId, name: &str, contents: Contents) -> NodeId {
+        if let Some(child) = self.child(parent, name) {
+            return child;
+        }
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(Node {
+            name: name.to_owned(),
+            parent: Some(parent),
+            contents,
+        });
+        match &mut self.nodes[parent.0].contents {
+            Contents::Dir(children) => children.push(id),
+            Contents::File(_) => panic!("Can't add {} to a file", name),
+        }
+        id
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.nodes[id.0].name
+    }
+
+    pub fn kind(&self, id: NodeId) -> NodeKind {
+        match self.nodes[id.0].contents {
+            Contents::Dir(_) => NodeKind::Dir,
+            Contents::File(_) => NodeKind::File,
+        }
+    }
+
+    /// The directory a node is in, which only the root doesn't have
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes[id.0].parent
+    }
+
+    /// What's inside a directory, in the order it was listed
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        match &self.nodes[id.0].contents {
+            Contents::Dir(children) => children,
+            Contents::File(_) => &[],
+        }
+    }
+
+    /// Something in a directory with the given name
+    pub fn child(&self, id: NodeId, name: &str) -> Option<NodeId> {
+        self.children(id)
+            .iter()
+            .copied()
+            .find(|&child| self.name(child) == name)
+    }
+
+    /// Find a node by its path from the root, like `/a/e/i`
+    pub fn resolve(&self, path: &str) -> Option<NodeId> {
+        path.strip_prefix('/')?
+            .split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(Self::ROOT, |dir, name| self.child(dir, name))
+    }
+
+    /// The path from the root to a node, like `/a/e/i`
+    pub fn path(&self, id: NodeId) -> String {
+        let mut names = vec![];
+        let mut node = id;
+        while let Some(parent) = self.parent(node) {
+            names.push(self.name(node));
+            node = parent;
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    /// The size of a file, or the total size of everything inside a directory
+    pub fn size(&self, id: NodeId) -> usize {
+        match &self.nodes[id.0].contents {
+            Contents::Dir(children) => children.iter().map(|&child| self.size(child)).sum(),
+            Contents::File(size) => *size,
+        }
+    }
+
+    /// The size of every node at once. Children always come after their parents, so going
+    /// backwards each node is finished before it's added to its parent.
+    fn sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![0; self.nodes.len()];
+        for (i, node) in self.nodes.iter().enumerate().rev() {
+            if let Contents::File(size) = node.contents {
+                sizes[i] = size;
+            }
+            if let Some(parent) = node.parent {
+                sizes[parent.0] += sizes[i];
+            }
+        }
+        sizes
+    }
+
+    /// The total size of each directory
+    pub fn dir_sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        let sizes = self.sizes();
+        self.nodes
+            .iter()
+            .zip(sizes)
+            .filter(|(node, _)| matches!(node.contents, Contents::Dir(_)))
+            .map(|(_, size)| size)
+    }
+
+    /// Every node with its path, size and kind, going depth first from the root like `tree`
+    pub fn walk(&self) -> impl Iterator<Item = (String, usize, NodeKind)> + '_ {
+        let sizes = self.sizes();
+        let mut open = vec![Self::ROOT];
+        std::iter::from_fn(move || {
+            let id = open.pop()?;
+            open.extend(self.children(id).iter().rev());
+            Some((self.path(id), sizes[id.0], self.kind(id)))
+        })
+    }
+
+    fn fmt_node(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        id: NodeId,
+        depth: usize,
+    ) -> std::fmt::Result {
+        let indent = "  ".repeat(depth);
+        match &self.nodes[id.0].contents {
+            Contents::Dir(children) => {
+                writeln!(f, "{}- {} (dir)", indent, self.name(id))?;
+                for &child in children {
+                    self.fmt_node(f, child, depth + 1)?;
+                }
+                Ok(())
+            }
+            Contents::File(size) => {
+                writeln!(f, "{}- {} (file, size={})", indent, self.name(id), size)
+            }
+        }
+    }
+}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Draws the filesystem as a tree like the puzzle does
+impl std::fmt::Display for FileSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_node(f, Self::ROOT, 0)
+    }
+}
@@ -0,0 +1,130 @@
+mod filesystem;
+mod session;
+
+use common::Solution;
+
+pub use filesystem::{FileSystem, NodeId, NodeKind};
+pub use session::{DirPath, Session, SessionLine};
+
+const SMALL_DIR_SIZE: usize = 100000;
+const FILESYSTEM_SPACE: usize = 70000000;
+const REQUIRED_SPACE: usize = 30000000;
+
+pub struct Day7;
+
+common::solution_fns!(Day7);
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    type Input = FileSystem;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn validate(input: &str) -> common::Result<()> {
+        input.parse::<Session>().map(|_| ())
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        // Construct file system
+        input.parse::<Session>().unwrap().filesystem()
+    }
+
+    fn part1(fs: &Self::Input) -> Self::Part1 {
+        // Find small directories
+        fs.dir_sizes().filter(|&size| size <= SMALL_DIR_SIZE).sum()
+    }
+
+    fn part2(fs: &Self::Input) -> Self::Part2 {
+        // Compute available space and required cleanup amount
+        let used_space = fs.size(FileSystem::ROOT);
+        let unused_space = FILESYSTEM_SPACE - used_space;
+        let cleanup_space = REQUIRED_SPACE - unused_space;
+
+        // Find smallest directory larger than the required cleanup amount
+        fs.dir_sizes()
+            .filter(|&size| size >= cleanup_space)
+            .min()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::sample_test;
+
+    const SAMPLE_TREE: &str = "- / (dir)
+  - a (dir)
+    - e (dir)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir)
+    - j (file, size=4060174)
+    - d.log (file, size=8033020)
+    - d.ext (file, size=5626152)
+    - k (file, size=7214296)
+";
+
+    fn sample() -> FileSystem {
+        Day7::parse(include_str!("../sample.txt"))
+    }
+
+    #[test]
+    fn test_resolve() {
+        let fs = sample();
+        let i = fs.resolve("/a/e/i").unwrap();
+        assert_eq!(
+            (fs.name(i), fs.kind(i), fs.size(i)),
+            ("i", NodeKind::File, 584)
+        );
+        assert_eq!(fs.path(i), "/a/e/i");
+        assert_eq!(fs.resolve("/"), Some(FileSystem::ROOT));
+        assert_eq!(fs.resolve("/a/"), fs.resolve("/a"));
+        assert_eq!(fs.resolve("/a/i"), None);
+        assert_eq!(fs.resolve("a/e"), None);
+    }
+
+    #[test]
+    fn test_walk() {
+        let fs = sample();
+        let dirs = fs
+            .walk()
+            .filter(|(_, _, kind)| *kind == NodeKind::Dir)
+            .map(|(path, size, _)| (path, size))
+            .collect::<Vec<_>>();
+        let expected = [
+            ("/", 48381165),
+            ("/a", 94853),
+            ("/a/e", 584),
+            ("/d", 24933642),
+        ];
+        assert_eq!(dirs, expected.map(|(path, size)| (path.to_owned(), size)));
+        assert_eq!(fs.walk().count(), 14);
+    }
+
+    #[test]
+    fn test_display_tree() {
+        assert_eq!(sample().to_string(), SAMPLE_TREE);
+    }
+
+    #[test]
+    fn test_relisting() {
+        // Listing a directory again or going into one before it's listed doesn't duplicate it
+        let fs = Day7::parse("$ cd /\n$ cd a\n$ ls\n5 x\n$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\n5 x");
+        assert_eq!(fs.walk().count(), 3);
+        assert_eq!(fs.size(FileSystem::ROOT), 5);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(Day7::validate(include_str!("../sample.txt")), Ok(()));
+        assert!(Day7::validate("$ cd /\n$ ls\ndir\n").is_err());
+    }
+
+    sample_test!(test_part1_with_sample, Day7::part1, file "sample.txt", 95437);
+    sample_test!(test_part2_with_sample, Day7::part2, file "sample.txt", 24933642);
+}
@@ -0,0 +1,162 @@
+//! The terminal session in the puzzle input, of commands and what `ls` printed
+
+use std::str::FromStr;
+
+use common::{parse, AocError};
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while1},
+    character::complete::char,
+    combinator::{map, value},
+    sequence::{preceded, separated_pair},
+    IResult,
+};
+
+use crate::FileSystem;
+
+/// Where `cd` goes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DirPath {
+    To(String),
+    Parent,
+    Root,
+}
+
+/// A command or a line of what `ls` printed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionLine {
+    ChangeDir(DirPath),
+    ListFiles,
+    DirListing(String),
+    FileListing(usize, String),
+}
+
+/// Every line of a terminal session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    pub lines: Vec<SessionLine>,
+}
+
+impl Session {
+    /// Build the filesystem the session looked around. Listings go in the current directory
+    /// whether or not they follow an `ls`, and going into a directory that hasn't been listed
+    /// adds it.
+    pub fn filesystem(&self) -> FileSystem {
+        let mut fs = FileSystem::new();
+        let mut cwd = FileSystem::ROOT;
+        for line in &self.lines {
+            match line {
+                SessionLine::ChangeDir(DirPath::To(name)) => cwd = fs.add_dir(cwd, name),
+                SessionLine::ChangeDir(DirPath::Parent) => {
+                    cwd = fs.parent(cwd).unwrap_or(FileSystem::ROOT)
+                }
+                SessionLine::ChangeDir(DirPath::Root) => cwd = FileSystem::ROOT,
+                SessionLine::ListFiles => {}
+                SessionLine::DirListing(name) => {
+                    fs.add_dir(cwd, name);
+                }
+                SessionLine::FileListing(size, name) => {
+                    fs.add_file(cwd, name, *size);
+                }
+            }
+        }
+        fs
+    }
+}
+
+impl FromStr for Session {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            lines: parse::parse_lines(s)?,
+        })
+    }
+}
+
+impl FromStr for SessionLine {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse::parse_all(session_line, s).map_err(|err| {
+            let command = s.strip_prefix("$ ").unwrap_or_default();
+            match command.split(' ').next() {
+                Some(name) if !name.is_empty() && name != "cd" && name != "ls" => {
+                    AocError::parse_at(s, command, format!("Unknown command '{}'", name))
+                }
+                _ => err,
+            }
+        })
+    }
+}
+
+/* Parsing */
+
+fn session_line(input: &str) -> IResult<&str, SessionLine> {
+    alt((
+        map(preceded(tag("$ cd "), dir_path), SessionLine::ChangeDir),
+        value(SessionLine::ListFiles, tag("$ ls")),
+        map(preceded(tag("dir "), name), SessionLine::DirListing),
+        map(
+            separated_pair(parse::unsigned_int, char(' '), name),
+            |(size, name)| SessionLine::FileListing(size, name),
+        ),
+    ))(input)
+}
+
+fn dir_path(input: &str) -> IResult<&str, DirPath> {
+    map(name, |name| match name.as_str() {
+        "/" => DirPath::Root,
+        ".." => DirPath::Parent,
+        _ => DirPath::To(name),
+    })(input)
+}
+
+/// The name of a file or directory, which is everything up to the end of the line
+fn name(input: &str) -> IResult<&str, String> {
+    map(take_while1(|c| c != '\n'), str::to_owned)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        let session: Session = "$ cd /\n$ ls\ndir a b\n14848514 b.txt\n$ cd .."
+            .parse()
+            .unwrap();
+        assert_eq!(
+            session.lines,
+            vec![
+                SessionLine::ChangeDir(DirPath::Root),
+                SessionLine::ListFiles,
+                SessionLine::DirListing("a b".to_owned()),
+                SessionLine::FileListing(14848514, "b.txt".to_owned()),
+                SessionLine::ChangeDir(DirPath::Parent),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = "$ cd /\n$ ls\n$ rm -rf a".parse::<Session>().unwrap_err();
+        assert_eq!(err.to_string(), "Parse error at 3:3: Unknown command 'rm'");
+
+        let err = "$ cd /\n$ ls\n12x b.txt".parse::<Session>().unwrap_err();
+        assert!(matches!(err, AocError::Parse { line: 3, .. }), "{}", err);
+        assert!("$ cd ".parse::<Session>().is_err());
+        assert!("$ ls -a".parse::<Session>().is_err());
+    }
+
+    #[test]
+    fn test_listing_anywhere() {
+        // Files are put wherever the session is, even without an `ls` or listing their parent
+        let session: Session = "$ cd a\n5 x\n$ cd /\n$ ls\ndir a\n$ ls\ndir a\n$ cd a\n$ ls\n5 x"
+            .parse()
+            .unwrap();
+        let fs = session.filesystem();
+        assert_eq!(fs.walk().count(), 3);
+        assert_eq!(fs.resolve("/a/x").map(|x| fs.size(x)), Some(5));
+    }
+}
@@ -0,0 +1,283 @@
+use colored::Colorize;
+use common::{viz::Visualizer, AocError, Grid, Solution, VecGrid};
+pub use forest::Forest;
+
+/// Utilities for working with a 2D grid of tree heights
+mod forest {
+    use common::{Grid, VecGrid};
+    use itertools::Itertools;
+
+    /// A grid of tree heights. Both parts walk whole rows and columns (`rows()`/`cols()`, and a
+    /// monotonic stack for scenic scores) rather than casting a `ray` in each `Direction` from
+    /// every tree, which would look at O(n) trees per tree instead of each tree a constant
+    /// number of times. The ray version is kept as `scenic_score` to check against in tests.
+    #[derive(Debug)]
+    pub struct Forest {
+        tree_heights: VecGrid<u8>,
+    }
+
+    impl Forest {
+        pub fn new(tree_heights: VecGrid<u8>) -> Self {
+            Self { tree_heights }
+        }
+
+        /// A square forest of pseudorandom heights, for benchmarking on something bigger than
+        /// the puzzle input
+        pub fn synthetic(size: usize) -> Self {
+            let mut state = 0x2022_0008_u64;
+            Self::new(VecGrid::from_fn(size, size, |_, _| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state % 10) as u8
+            }))
+        }
+
+        pub fn tree_heights(&self) -> &VecGrid<u8> {
+            &self.tree_heights
+        }
+
+        /// Which trees can be seen from outside the forest, looking along every row and column
+        /// from both ends
+        pub fn visible(&self) -> VecGrid<bool> {
+            let (width, height) = (self.tree_heights.width(), self.tree_heights.height());
+            let mut visible = VecGrid::new(width, height);
+            for (y, row) in self.tree_heights.rows().enumerate() {
+                let row = row.enumerate().collect_vec();
+                for x in visible_from_start(row.iter().copied())
+                    .chain(visible_from_start(row.iter().rev().copied()))
+                {
+                    *visible.get_mut(x, y).unwrap() = true;
+                }
+            }
+            for (x, col) in self.tree_heights.cols().enumerate() {
+                let col = col.enumerate().collect_vec();
+                for y in visible_from_start(col.iter().copied())
+                    .chain(visible_from_start(col.iter().rev().copied()))
+                {
+                    *visible.get_mut(x, y).unwrap() = true;
+                }
+            }
+            visible
+        }
+
+        /// The scenic score of every tree. Rather than looking out from every tree, each row and
+        /// column is walked in both directions keeping a stack of the trees that could still
+        /// block the view, so the whole forest takes linear time.
+        pub fn scenic_scores(&self) -> VecGrid<usize> {
+            let (width, height) = (self.tree_heights.width(), self.tree_heights.height());
+            let mut scores = VecGrid::from_fn(width, height, |_, _| 1);
+            let rows = (0..height).map(|y| (0..width).map(|x| (x, y)).collect_vec());
+            let cols = (0..width).map(|x| (0..height).map(|y| (x, y)).collect_vec());
+            for line in rows.chain(cols) {
+                self.multiply_viewing_distances(&mut scores, line.iter().copied());
+                self.multiply_viewing_distances(&mut scores, line.iter().rev().copied());
+            }
+            scores
+        }
+
+        /// Multiply each tree's score by how far it can see back towards the start of a line
+        fn multiply_viewing_distances(
+            &self,
+            scores: &mut VecGrid<usize>,
+            line: impl Iterator<Item = (usize, usize)>,
+        ) {
+            // Trees behind a taller one can never block the view of anything further along
+            let mut blockers: Vec<(usize, u8)> = vec![];
+            for (i, (x, y)) in line.enumerate() {
+                let height = *self.tree_heights.get(x, y).unwrap();
+                while blockers
+                    .last()
+                    .is_some_and(|&(_, blocker)| blocker < height)
+                {
+                    blockers.pop();
+                }
+                let distance = blockers.last().map_or(i, |&(j, _)| i - j);
+                *scores.get_mut(x, y).unwrap() *= distance;
+                blockers.push((i, height));
+            }
+        }
+
+        /// The product of how many trees can be seen from a tree in each direction, found by
+        /// looking out from it
+        #[cfg(test)]
+        pub fn scenic_score(&self, x: usize, y: usize) -> usize {
+            let tree_height = *self.tree_heights.get(x, y).unwrap();
+            common::Direction::ALL
+                .iter()
+                .map(|&direction| {
+                    self.tree_heights
+                        .ray((x, y), direction)
+                        .until(|&height| height >= tree_height)
+                        .count()
+                })
+                .product()
+        }
+    }
+
+    /// The indices of the trees in a line which can be seen from its start
+    fn visible_from_start<'a, I>(line: I) -> impl Iterator<Item = usize> + use<'a, I>
+    where
+        I: Iterator<Item = (usize, &'a u8)>,
+    {
+        let mut tallest = None;
+        line.filter(move |&(_, &height)| {
+            let taller = Some(height) > tallest;
+            tallest = tallest.max(Some(height));
+            taller
+        })
+        .map(|(i, _)| i)
+    }
+}
+
+pub struct Day8;
+
+common::solution_fns!(Day8);
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Input = Forest;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn validate(input: &str) -> common::Result<()> {
+        // Every row needs a single digit height for each column
+        let width = input.lines().next().map_or(0, str::len);
+        for line in input.lines() {
+            if let Some(col) = line.find(|c: char| !c.is_ascii_digit()) {
+                return Err(AocError::parse_at(
+                    input,
+                    &line[col..],
+                    "Expected a tree height",
+                ));
+            }
+            if line.len() != width {
+                return Err(AocError::parse_at(
+                    input,
+                    line,
+                    format!("Expected {} trees in every row", width),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        forest::Forest::new(input.parse().unwrap())
+    }
+
+    fn part1(forest: &Self::Input) -> Self::Part1 {
+        // Count visible trees
+        ForestView::show(forest, None);
+        forest.visible().cells().into_iter().filter(|&v| v).count()
+    }
+
+    fn part2(forest: &Self::Input) -> Self::Part2 {
+        // Find the best scenic score
+        let scores = forest.scenic_scores();
+        let (x, y, &best) = scores
+            .iter_with_coords()
+            .max_by_key(|&(_, _, &score)| score)
+            .unwrap();
+        ForestView::show(forest, Some((x, y)));
+        best
+    }
+}
+
+/* Visualization */
+
+/// The forest with the trees that can be seen from outside it highlighted, and the tree with the
+/// best scenic score marked with a `*` once it's been found
+struct ForestView<'a> {
+    forest: &'a Forest,
+    visible: VecGrid<bool>,
+    best: Option<(usize, usize)>,
+}
+
+impl<'a> ForestView<'a> {
+    /// Draw the forest with `--animate`, or save it with `--record=PATH`
+    fn show(forest: &'a Forest, best: Option<(usize, usize)>) {
+        let mut viz = Visualizer::from_args();
+        if viz.is_enabled() {
+            viz.final_frame(&Self::new(forest, best));
+        }
+    }
+
+    fn new(forest: &'a Forest, best: Option<(usize, usize)>) -> Self {
+        Self {
+            forest,
+            visible: forest.visible(),
+            best,
+        }
+    }
+}
+
+impl std::fmt::Display for ForestView<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.forest.tree_heights().rows().enumerate() {
+            for (x, height) in row.enumerate() {
+                let tree = height.to_string();
+                let tree = if self.best == Some((x, y)) {
+                    "*".black().on_bright_yellow()
+                } else if self.visible.get(x, y) == Some(&true) {
+                    tree.bright_green().bold()
+                } else {
+                    tree.green().dimmed()
+                };
+                write!(f, "{}", tree)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scenic_scores_match_looking_out() {
+        let sample = Day8::parse(&std::fs::read_to_string("./sample.txt").unwrap());
+        for forest in [sample, Forest::synthetic(40)] {
+            let scores = forest.scenic_scores();
+            for (x, y, &score) in scores.iter_with_coords() {
+                assert_eq!(score, forest.scenic_score(x, y), "tree at ({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_visible_interior_trees() {
+        let forest = Day8::parse(&std::fs::read_to_string("./sample.txt").unwrap());
+        let visible = forest.visible();
+        let interior = (1..4)
+            .map(|y| {
+                (1..4)
+                    .map(|x| visible.get(x, y) == Some(&true))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            interior,
+            [
+                [true, true, false],
+                [true, false, true],
+                [false, true, false]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_forest_view() {
+        let forest = Day8::parse(&std::fs::read_to_string("./sample.txt").unwrap());
+        colored::control::set_override(false);
+        assert_eq!(
+            ForestView::new(&forest, Some((2, 3))).to_string(),
+            "30373\n25512\n65332\n33*49\n35390\n"
+        );
+        assert!(!ForestView::new(&forest, None).to_string().contains('*'));
+    }
+
+    common::sample_test!(test_part2_with_sample, Day8::part2, file "sample.txt", 8);
+}
@@ -0,0 +1,327 @@
+use std::str::FromStr;
+
+use common::{
+    cli::{Flag, Flags},
+    parse,
+    viz::Visualizer,
+    AocError, Direction, Point, Set, Solution,
+};
+
+pub struct Action {
+    offset: Vector,
+    repetitions: usize,
+}
+
+impl FromStr for Action {
+    type Err = AocError;
+
+    /// Read a motion like `R 4`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (dir, amt) = s
+            .split_once(' ')
+            .ok_or_else(|| AocError::parse("Expected a direction and a distance"))?;
+        let mut chars = dir.chars();
+        let direction = match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c).map_err(AocError::parse)?,
+            _ => return Err(AocError::parse(format!("Unknown direction '{}'", dir))),
+        };
+        Ok(Action {
+            offset: direction.offset(),
+            repetitions: amt.parse()?,
+        })
+    }
+}
+
+pub type Vector = Point<isize>;
+
+/// How many knots part 2's rope has, unless given `--knots N`
+const PT2_KNOTS: usize = 10;
+
+/// The motions of the rope's head, and how many knots part 2's rope has
+pub struct Motions {
+    pub actions: Vec<Action>,
+    pub knots: usize,
+}
+
+/// A rope made of knots, from its head to its tail, which all start at the origin
+#[derive(Debug, Clone)]
+pub struct Rope {
+    knots: Vec<Vector>,
+}
+
+impl Rope {
+    /// A rope with some number of knots, including its head and tail
+    pub fn new(knots: usize) -> Self {
+        assert!(knots > 0, "A rope needs at least one knot");
+        Self {
+            knots: vec![Default::default(); knots],
+        }
+    }
+
+    /// Where every knot is, from the head to the tail
+    pub fn knots(&self) -> &[Vector] {
+        &self.knots
+    }
+
+    fn head_mut(&mut self) -> &mut Vector {
+        self.knots.first_mut().unwrap()
+    }
+
+    fn head(&self) -> &Vector {
+        self.knots.first().unwrap()
+    }
+
+    fn tail(&self) -> &Vector {
+        self.knots.last().unwrap()
+    }
+
+    /// Move the head through every action, returning each position the tail visited
+    pub fn track_tail_positions(
+        &mut self,
+        actions: &[Action],
+        viz: &mut Visualizer,
+    ) -> Set<Vector> {
+        let mut visited = Set::default();
+        for action in actions {
+            for _ in 0..action.repetitions {
+                self.move_head(action.offset);
+                visited.insert(*self.tail());
+                viz.frame(&RopeFrame {
+                    rope: self,
+                    visited: &visited,
+                });
+            }
+        }
+        viz.final_frame(&TailVisits(&visited));
+        visited
+    }
+
+    /// Move the head, with each following knot catching up to the one before it
+    pub fn move_head(&mut self, movement: Vector) {
+        // Move head
+        *self.head_mut() = *self.head() + movement;
+
+        // Move tail
+        (0..self.knots.len())
+            .collect::<Vec<_>>()
+            .windows(2)
+            .for_each(|inds| {
+                // Some light hacks here to convince the
+                // borrow checker to give us two refs into the vec
+                let (l, r) = self.knots.split_at_mut(inds[1]);
+                Self::resolve_knot_pair(&l[inds[0]], &mut r[0]);
+            });
+    }
+
+    fn resolve_knot_pair(a: &Vector, b: &mut Vector) {
+        let diff = *a - *b;
+        if diff.x.abs() > 1 || diff.y.abs() > 1 {
+            *b = *b + diff.signum();
+        }
+    }
+}
+
+pub struct Day9;
+
+common::solution_fns!(Day9);
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    type Input = Motions;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const FLAGS: &'static [Flag] = &[Flag::with_value("knots", "N")];
+
+    fn validate(input: &str) -> common::Result<()> {
+        parse::parse_lines::<Action>(input).map(|_| ())
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        Motions {
+            actions: parse::parse_lines(input).unwrap(),
+            knots: PT2_KNOTS,
+        }
+    }
+
+    fn parse_with(input: &str, flags: &Flags) -> Result<Self::Input, String> {
+        let knots = match flags.parse("knots")? {
+            Some(0) => return Err("--knots must be at least 1".to_owned()),
+            Some(knots) => knots,
+            None => PT2_KNOTS,
+        };
+        Ok(Motions {
+            knots,
+            ..Self::parse(input)
+        })
+    }
+
+    fn part1(motions: &Self::Input) -> Self::Part1 {
+        // Move rope around
+        let mut rope = Rope::new(2);
+        let mut viz = Visualizer::from_args().every(5);
+        rope.track_tail_positions(&motions.actions, &mut viz).len()
+    }
+
+    fn part2(motions: &Self::Input) -> Self::Part2 {
+        // Move a bigger rope around
+        let mut big_rope = Rope::new(motions.knots);
+        let mut viz = Visualizer::from_args().every(5);
+        big_rope
+            .track_tail_positions(&motions.actions, &mut viz)
+            .len()
+    }
+}
+
+/* Visualisation */
+
+/// A rope along with every position its tail has visited
+struct RopeFrame<'a> {
+    rope: &'a Rope,
+    visited: &'a Set<Vector>,
+}
+
+/// Every position a rope's tail visited, drawn like the puzzle's worked example
+pub struct TailVisits<'a>(pub &'a Set<Vector>);
+
+impl std::fmt::Display for RopeFrame<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let positions = self.visited.iter().chain(&self.rope.knots);
+        draw_positions(f, positions, |position| {
+            match self.rope.knots.iter().position(|&knot| knot == position) {
+                Some(0) => 'H',
+                Some(i) => char::from_digit(i as u32, 10).unwrap_or('T'),
+                None => visit_symbol(self.visited, position),
+            }
+        })
+    }
+}
+
+impl std::fmt::Display for TailVisits<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        draw_positions(f, self.0.iter(), |position| visit_symbol(self.0, position))
+    }
+}
+
+/// The start is drawn as `s`, and anywhere else the tail has been as `#`
+fn visit_symbol(visited: &Set<Vector>, position: Vector) -> char {
+    match position {
+        _ if position == Vector::default() => 's',
+        _ if visited.contains(&position) => '#',
+        _ => '.',
+    }
+}
+
+/// Draw every position in the box around some positions, and around the start
+fn draw_positions<'a>(
+    f: &mut std::fmt::Formatter<'_>,
+    positions: impl Iterator<Item = &'a Vector> + Clone,
+    symbol: impl Fn(Vector) -> char,
+) -> std::fmt::Result {
+    let positions = positions.chain([&Vector { x: 0, y: 0 }]);
+    let min_x = positions.clone().map(|p| p.x).min().unwrap_or(0);
+    let max_x = positions.clone().map(|p| p.x).max().unwrap_or(0);
+    let min_y = positions.clone().map(|p| p.y).min().unwrap_or(0);
+    let max_y = positions.map(|p| p.y).max().unwrap_or(0);
+    for y in min_y..=max_y {
+        for x in min_x..=max_x {
+            write!(f, "{}", symbol(Vector::new(x, y)))?;
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::sample_test;
+
+    const LARGER_SAMPLE: &str = "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20";
+
+    #[test]
+    fn test_with_puzzle_sample() {
+        let actions = parse::parse_lines(include_str!("../sample.txt")).unwrap();
+        let mut rope = Rope::new(2);
+        let tail_positions = rope.track_tail_positions(&actions, &mut Visualizer::new(false));
+        assert_eq!(tail_positions.len(), 13);
+        assert_eq!(rope.knots(), [Vector::new(2, -2), Vector::new(1, -2)]);
+    }
+
+    #[test]
+    fn test_draw_tail_visits() {
+        let actions = parse::parse_lines(include_str!("../sample.txt")).unwrap();
+        let visited = Rope::new(2).track_tail_positions(&actions, &mut Visualizer::new(false));
+        assert_eq!(
+            TailVisits(&visited).to_string(),
+            "..##.\n...##\n.####\n....#\ns###.\n"
+        );
+
+        // Drawing a rope too
+        let rope = Rope::new(3);
+        let frame = RopeFrame {
+            rope: &rope,
+            visited: &visited,
+        };
+        assert_eq!(frame.to_string().lines().last(), Some("H###."));
+    }
+
+    #[test]
+    fn test_knot_counts() {
+        let actions = parse::parse_lines(LARGER_SAMPLE).unwrap();
+        let visits = |knots| {
+            Rope::new(knots)
+                .track_tail_positions(&actions, &mut Visualizer::new(false))
+                .len()
+        };
+        assert_eq!(visits(10), 36);
+        assert!(visits(2) > visits(10));
+    }
+
+    #[test]
+    fn test_knots_flag() {
+        let parse = |args: &[&str]| {
+            let args = args.iter().map(|arg| arg.to_string());
+            let flags = common::cli::parse_from(args, Day9::FLAGS)?.flags;
+            Day9::parse_with(LARGER_SAMPLE, &flags).map(|motions| motions.knots)
+        };
+        assert_eq!(parse(&[]), Ok(PT2_KNOTS));
+        assert_eq!(parse(&["--knots", "3"]), Ok(3));
+        assert_eq!(parse(&["--knots=2"]), Ok(2));
+        assert_eq!(
+            parse(&["--knots", "0"]),
+            Err("--knots must be at least 1".to_owned())
+        );
+        assert_eq!(
+            parse(&["--knots=-1"]),
+            Err("Invalid value -1 for --knots".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(Day9::validate(LARGER_SAMPLE), Ok(()));
+        assert_eq!(
+            Day9::validate("R 4\nX 2").unwrap_err().to_string(),
+            "Parse error at 2:1: Unknown direction character: 'X'"
+        );
+        assert!(Day9::validate("R 4\nU").is_err());
+        assert!(Day9::validate("R four").is_err());
+    }
+
+    sample_test!(test_part1_with_sample, Day9::part1, file "sample.txt", 13);
+    sample_test!(test_part2_with_sample, Day9::part2, file "sample.txt", 1);
+    sample_test!(
+        test_part2_with_larger_sample,
+        Day9::part2,
+        LARGER_SAMPLE,
+        36
+    );
+}
@@ -0,0 +1,269 @@
+use common::{
+    ocr::{self, SCREEN_HEIGHT, SCREEN_WIDTH},
+    parse, AocError, Solution,
+};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Command {
+    Noop,
+    Add(isize),
+}
+
+impl FromStr for Command {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (command, rest) = s.split_once(' ').unwrap_or((s, ""));
+        match command {
+            "noop" => Ok(Command::Noop),
+            "addx" => Ok(Command::Add(rest.parse()?)),
+            _ => Err(AocError::parse(format!("Unknown command '{}'", command))),
+        }
+    }
+}
+
+/// The registers instructions can read and change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Registers {
+    pub x: isize,
+}
+
+impl Default for Registers {
+    fn default() -> Self {
+        Self { x: 1 }
+    }
+}
+
+/// Something the CPU can run, which takes some cycles and then changes the registers
+pub trait Instruction {
+    /// How many cycles it takes to run, which must be at least one
+    fn cycles(&self) -> usize;
+
+    /// Change the registers once the instruction's last cycle is over
+    fn execute(&self, registers: &mut Registers);
+}
+
+impl Instruction for Command {
+    fn cycles(&self) -> usize {
+        match self {
+            Command::Noop => 1,
+            Command::Add(_) => 2,
+        }
+    }
+
+    fn execute(&self, registers: &mut Registers) {
+        if let Command::Add(add) = self {
+            registers.x += add;
+        }
+    }
+}
+
+/// Runs instructions a cycle at a time, remembering the registers during every cycle
+pub struct Cpu {
+    /// The registers during each cycle, from the first, and then once every instruction is done
+    history: Vec<Registers>,
+}
+
+impl Cpu {
+    pub fn new() -> Self {
+        Self {
+            history: vec![Registers::default()],
+        }
+    }
+
+    /// Run each instruction in turn
+    pub fn process_commands(&mut self, commands: &[impl Instruction]) {
+        for command in commands {
+            self.execute(command);
+        }
+    }
+
+    /// Run an instruction, with the registers unchanged until its last cycle is over
+    pub fn execute(&mut self, instruction: &impl Instruction) {
+        let mut registers = *self.registers();
+        for _ in 1..instruction.cycles() {
+            self.history.push(registers);
+        }
+        instruction.execute(&mut registers);
+        self.history.push(registers);
+    }
+
+    /// The registers as they are now, during the cycle after the last instruction
+    pub fn registers(&self) -> &Registers {
+        self.history.last().unwrap()
+    }
+
+    /// The value of the X register during a cycle, counting from 1
+    pub fn x_at_cycle(&self, cycle: usize) -> Option<isize> {
+        self.history
+            .get(cycle.checked_sub(1)?)
+            .map(|registers| registers.x)
+    }
+
+    /// How many cycles have been run so far
+    pub fn cycles(&self) -> usize {
+        self.history.len() - 1
+    }
+
+    pub fn signal_strength_sum(&self) -> isize {
+        (20..=220)
+            .step_by(40)
+            .flat_map(|cycle| Some(cycle as isize * self.x_at_cycle(cycle)?))
+            .sum()
+    }
+
+    /// Which pixels of the CRT are lit as the beam passes over them
+    pub fn crt(&self) -> [[bool; SCREEN_WIDTH]; SCREEN_HEIGHT] {
+        let mut screen = [[false; SCREEN_WIDTH]; SCREEN_HEIGHT];
+        for (i, registers) in self
+            .history
+            .iter()
+            .take(SCREEN_WIDTH * SCREEN_HEIGHT)
+            .enumerate()
+        {
+            let (row, column) = (i / SCREEN_WIDTH, i % SCREEN_WIDTH);
+            screen[row][column] = (column as isize).abs_diff(registers.x) <= 1;
+        }
+        screen
+    }
+}
+
+impl Default for Cpu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Draws the CRT's image
+impl std::fmt::Display for Cpu {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.crt() {
+            let row: String = row
+                .iter()
+                .map(|&lit| if lit { '\u{2588}' } else { ' ' })
+                .collect();
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Day10;
+
+common::solution_fns!(Day10);
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Cpu;
+    type Part1 = isize;
+    type Part2 = String;
+
+    fn validate(input: &str) -> common::Result<()> {
+        parse::parse_lines::<Command>(input).map(|_| ())
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        let commands: Vec<Command> = parse::parse_lines(input).unwrap();
+
+        // Compute registers
+        let mut register = Cpu::new();
+        register.process_commands(&commands);
+        register
+    }
+
+    fn part1(register: &Self::Input) -> Self::Part1 {
+        register.signal_strength_sum()
+    }
+
+    fn part2(register: &Self::Input) -> Self::Part2 {
+        // Draw CRT and read it
+        ocr::parse_letters(&register.crt())
+    }
+}
+
+#[test]
+fn test_processing_commands_small() {
+    let sample = "noop\naddx 3\naddx -5";
+    let commands: Vec<Command> = sample.lines().flat_map(FromStr::from_str).collect();
+    let mut register = Cpu::new();
+    register.process_commands(&commands);
+    assert_eq!(register.x_at_cycle(4), Some(4));
+    assert_eq!(register.x_at_cycle(6), Some(-1));
+    assert_eq!(register.x_at_cycle(7), None);
+    assert_eq!(register.cycles(), 5);
+}
+
+#[test]
+fn test_processing_commands_large() {
+    let sample = std::fs::read_to_string("./sample.txt").unwrap();
+    let commands: Vec<Command> = sample.lines().flat_map(FromStr::from_str).collect();
+    let mut register = Cpu::new();
+    register.process_commands(&commands);
+    assert_eq!(register.x_at_cycle(20), Some(21));
+}
+
+#[test]
+fn test_screen_with_sample() {
+    let sample = std::fs::read_to_string("./sample.txt").unwrap();
+    let cpu = Day10::parse(&sample);
+    let image = cpu.crt().map(|row| {
+        row.iter()
+            .map(|&lit| if lit { '#' } else { '.' })
+            .collect::<String>()
+    });
+    assert_eq!(
+        image,
+        [
+            "##..##..##..##..##..##..##..##..##..##..",
+            "###...###...###...###...###...###...###.",
+            "####....####....####....####....####....",
+            "#####.....#####.....#####.....#####.....",
+            "######......######......######......####",
+            "#######.......#######.......#######.....",
+        ]
+    );
+
+    // Its stripes aren't letters, so they can't be read
+    assert_eq!(Day10::part2(&cpu), "????????");
+    assert_eq!(cpu.to_string().lines().count(), SCREEN_HEIGHT);
+}
+
+#[test]
+fn test_custom_instructions() {
+    /// Multiply X over three cycles
+    struct Mulx(isize);
+
+    impl Instruction for Mulx {
+        fn cycles(&self) -> usize {
+            3
+        }
+
+        fn execute(&self, registers: &mut Registers) {
+            registers.x *= self.0;
+        }
+    }
+
+    let mut cpu = Cpu::new();
+    cpu.execute(&Command::Add(2));
+    cpu.execute(&Mulx(4));
+    cpu.execute(&Command::Noop);
+    let xs = (1..=7)
+        .map(|cycle| cpu.x_at_cycle(cycle))
+        .collect::<Vec<_>>();
+    let expected = [1, 1, 3, 3, 3, 12, 12].map(Some);
+    assert_eq!(xs, expected);
+}
+
+#[test]
+fn test_validate() {
+    assert_eq!(Day10::validate("noop\naddx -5\n"), Ok(()));
+    assert_eq!(
+        Day10::validate("noop\nmulx 3").unwrap_err().to_string(),
+        "Parse error at 2:1: Unknown command 'mulx'"
+    );
+    assert!(Day10::validate("addx").is_err());
+}
+
+#[cfg(test)]
+common::sample_test!(test_part1_with_sample, Day10::part1, file "sample.txt", 13140);
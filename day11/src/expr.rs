@@ -0,0 +1,156 @@
+//! Arithmetic expressions for what a monkey does to an item's worry level, like `old * (old - 3)`
+
+use std::str::FromStr;
+
+use common::{parse, AocError};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, one_of, space0},
+    combinator::map,
+    multi::many0,
+    sequence::{delimited, pair, preceded},
+    IResult,
+};
+
+use crate::MonkeyNum;
+
+/// An expression in terms of the old worry level
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Old,
+    Value(usize),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Work out the new worry level. Worry can't go below zero, so subtracting too much panics.
+    pub fn eval<N: MonkeyNum>(&self, old: &N) -> N {
+        match self {
+            Expr::Old => old.clone(),
+            Expr::Value(value) => N::from(*value),
+            Expr::Add(a, b) => a.eval(old) + b.eval(old),
+            Expr::Sub(a, b) => a.eval(old) - b.eval(old),
+            Expr::Mul(a, b) => a.eval(old) * b.eval(old),
+        }
+    }
+
+    /// How tightly the expression binds, so it knows when it needs parentheses
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Add(..) | Expr::Sub(..) => 1,
+            Expr::Mul(..) => 2,
+            Expr::Old | Expr::Value(_) => 3,
+        }
+    }
+}
+
+impl FromStr for Expr {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse::parse_all(delimited(space0, sum, space0), s)
+    }
+}
+
+/* Parsing */
+
+/// Terms added or subtracted from left to right
+fn sum(input: &str) -> IResult<&str, Expr> {
+    let (input, first) = product(input)?;
+    let (input, rest) = many0(pair(operator("+-"), product))(input)?;
+    let expr = rest.into_iter().fold(first, |a, (op, b)| match op {
+        '+' => Expr::Add(Box::new(a), Box::new(b)),
+        _ => Expr::Sub(Box::new(a), Box::new(b)),
+    });
+    Ok((input, expr))
+}
+
+/// Factors multiplied together
+fn product(input: &str) -> IResult<&str, Expr> {
+    let (input, first) = factor(input)?;
+    let (input, rest) = many0(preceded(operator("*"), factor))(input)?;
+    let expr = rest
+        .into_iter()
+        .fold(first, |a, b| Expr::Mul(Box::new(a), Box::new(b)));
+    Ok((input, expr))
+}
+
+/// The old worry level, a number or a bracketed expression
+fn factor(input: &str) -> IResult<&str, Expr> {
+    alt((
+        map(tag("old"), |_| Expr::Old),
+        map(parse::unsigned_int, Expr::Value),
+        delimited(pair(char('('), space0), sum, pair(space0, char(')'))),
+    ))(input)
+}
+
+/// One of the operator symbols, with optional spaces around it
+fn operator<'a>(symbols: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, char> {
+    delimited(space0, one_of(symbols), space0)
+}
+
+/* Display Implementations */
+
+impl std::fmt::Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (a, symbol, b) = match self {
+            Expr::Old => return write!(f, "old"),
+            Expr::Value(value) => return write!(f, "{}", value),
+            Expr::Add(a, b) => (a, '+', b),
+            Expr::Sub(a, b) => (a, '-', b),
+            Expr::Mul(a, b) => (a, '*', b),
+        };
+
+        // Bracket looser operands, and the right of an equal one since they group to the left
+        if a.precedence() < self.precedence() {
+            write!(f, "({})", a)?;
+        } else {
+            write!(f, "{}", a)?;
+        }
+        write!(f, " {} ", symbol)?;
+        if b.precedence() <= self.precedence() {
+            write!(f, "({})", b)
+        } else {
+            write!(f, "{}", b)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_puzzle_operations() {
+        assert_eq!(
+            "old * 19".parse(),
+            Ok(Expr::Mul(Box::new(Expr::Old), Box::new(Expr::Value(19))))
+        );
+        assert_eq!("old + 6".parse::<Expr>().unwrap().eval(&10usize), 16);
+        assert_eq!("old * old".parse::<Expr>().unwrap().eval(&10usize), 100);
+    }
+
+    #[test]
+    fn test_precedence_and_brackets() {
+        let expr: Expr = "old * (old - 3) + 2 * 5".parse().unwrap();
+        assert_eq!(expr.eval(&10usize), 80);
+        assert_eq!(expr.to_string(), "old * (old - 3) + 2 * 5");
+
+        let expr: Expr = "20 - (4 - 1) - old".parse().unwrap();
+        assert_eq!(expr.eval(&2usize), 15);
+        assert_eq!(expr.to_string(), "20 - (4 - 1) - old");
+
+        let expr: Expr = "( old+1 )*(old)".parse().unwrap();
+        assert_eq!(expr.to_string(), "(old + 1) * old");
+    }
+
+    #[test]
+    fn test_malformed_operations() {
+        for s in ["old * x19", "old +", "(old * 2", "old ^ 2", ""] {
+            assert!(s.parse::<Expr>().is_err(), "{}", s);
+        }
+    }
+}
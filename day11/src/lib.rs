@@ -0,0 +1,490 @@
+mod expr;
+
+use itertools::Itertools;
+use num_bigint::BigUint;
+use std::{
+    fmt::{Debug, Display},
+    ops::{Add, Mul, Sub},
+    str::FromStr,
+};
+
+use common::{math, parse, top_k, AocError, Map, Solution};
+
+pub use expr::Expr;
+
+/// A type worry levels can be kept in. `usize` is fast but needs reducing by the divisors' lcm to
+/// stay small, while `BigUint` can grow as big as the monkeys make it.
+pub trait MonkeyNum:
+    Clone + Debug + Display + From<usize> + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    /// Divide, rounding down
+    fn div_by(&self, divisor: usize) -> Self;
+
+    /// The remainder after dividing
+    fn rem_by(&self, divisor: usize) -> Self;
+
+    /// Whether dividing leaves no remainder
+    fn divisible_by(&self, divisor: usize) -> bool;
+}
+
+impl MonkeyNum for usize {
+    fn div_by(&self, divisor: usize) -> Self {
+        self / divisor
+    }
+
+    fn rem_by(&self, divisor: usize) -> Self {
+        self % divisor
+    }
+
+    fn divisible_by(&self, divisor: usize) -> bool {
+        self.is_multiple_of(divisor)
+    }
+}
+
+impl MonkeyNum for BigUint {
+    fn div_by(&self, divisor: usize) -> Self {
+        self / divisor
+    }
+
+    fn rem_by(&self, divisor: usize) -> Self {
+        self % divisor
+    }
+
+    fn divisible_by(&self, divisor: usize) -> bool {
+        (self % divisor).bits() == 0
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DivisibleTest(usize);
+
+impl From<usize> for DivisibleTest {
+    fn from(divisor: usize) -> Self {
+        Self(divisor)
+    }
+}
+
+struct MonkeyThrowResult<N> {
+    item: N,
+    to: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey<N = usize> {
+    items: Vec<N>,
+    operation: Expr,
+    test: DivisibleTest,
+    test_actions: (usize, usize),
+
+    /// Whether worry level is divided by 3 after an inspection
+    ///     false -> do divide,
+    ///     true -> don't divide,
+    extra_intimidating: bool,
+}
+
+impl FromStr for Monkey {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (starting_items, operation, test_cond, test_action_1, test_action_2) = s
+            .lines()
+            .skip(1)
+            .collect_tuple::<(_, _, _, _, _)>()
+            .ok_or_else(|| AocError::BadInput("Monkey is missing components".into()))?;
+        let items: Vec<usize> = starting_items
+            .split(": ")
+            .nth(1)
+            .ok_or_else(|| AocError::parse("missing items").offset_lines(1))?
+            .split(',')
+            .map(|num| {
+                let num = num.strip_prefix(' ').unwrap_or(num);
+                num.parse().map_err(|_| {
+                    AocError::parse_at(starting_items, num, format!("Invalid item '{}'", num))
+                        .offset_lines(1)
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        let test: usize = take_first(test_cond)
+            .ok_or_else(|| AocError::parse("cant parse test condition").offset_lines(3))?;
+        let test_action_1 = take_first(test_action_1)
+            .ok_or_else(|| AocError::parse("cant parse test action 1").offset_lines(4))?;
+        let test_action_2 = take_first(test_action_2)
+            .ok_or_else(|| AocError::parse("cant parse test action 2").offset_lines(5))?;
+        let operation = operation
+            .split_once("= ")
+            .ok_or_else(|| AocError::parse("missing operation").offset_lines(2))?
+            .1
+            .parse::<Expr>()
+            .map_err(|err| err.offset_lines(2))?;
+        Ok(Monkey {
+            items,
+            test: test.into(),
+            operation,
+            test_actions: (test_action_1, test_action_2),
+            extra_intimidating: false,
+        })
+    }
+}
+
+impl Monkey {
+    /// The same monkey with its worry levels kept in another type
+    pub fn worry_in<N: MonkeyNum>(&self) -> Monkey<N> {
+        Monkey {
+            items: self.items.iter().map(|&item| N::from(item)).collect(),
+            operation: self.operation.clone(),
+            test: self.test,
+            test_actions: self.test_actions,
+            extra_intimidating: self.extra_intimidating,
+        }
+    }
+}
+
+impl<N: MonkeyNum> Monkey<N> {
+    fn inspect_item(&self, item: N, lcm: Option<usize>) -> MonkeyThrowResult<N> {
+        // Apply operation
+        let item = self.operation.eval(&item);
+
+        // Divide by three (if not intimidating)
+        let item = if self.extra_intimidating {
+            if let Some(lcm) = lcm {
+                item.rem_by(lcm)
+            } else {
+                item
+            }
+        } else {
+            item.div_by(3)
+        };
+
+        // Perform test
+        let to = if self.test.test(&item) {
+            self.test_actions.0
+        } else {
+            self.test_actions.1
+        };
+
+        // Return result
+        MonkeyThrowResult { item, to }
+    }
+}
+
+impl DivisibleTest {
+    fn test<N: MonkeyNum>(&self, value: &N) -> bool {
+        value.divisible_by(self.0)
+    }
+}
+
+/// Simulate some rounds with worry levels kept in `N`, returning how many items each monkey
+/// inspected, in the monkeys' order. Once worry isn't divided by 3, it's only kept down by the lcm if one is given,
+/// which a `usize` can't go without for long.
+pub fn count_inspections<N: MonkeyNum>(
+    monkeys: &[Monkey],
+    rounds: usize,
+    intimidating: bool,
+    lcm: Option<usize>,
+) -> Vec<usize> {
+    let mut sim = MonkeySim::<N>::new(monkeys, intimidating, lcm);
+    for _ in 0..rounds {
+        sim.round();
+    }
+    sim.inspection_counts().to_vec()
+}
+
+/// Steps through the monkeys' rounds one at a time, so the items they hold can be looked at
+/// along the way
+#[derive(Debug, Clone)]
+pub struct MonkeySim<N = usize> {
+    monkeys: Vec<Monkey<N>>,
+    lcm: Option<usize>,
+    inspection_counts: Vec<usize>,
+    rounds: usize,
+}
+
+/// Which items each monkey held and how many they had inspected after a round
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundSnapshot<N = usize> {
+    pub round: usize,
+    pub items: Vec<Vec<N>>,
+    pub inspection_counts: Vec<usize>,
+}
+
+impl<N: MonkeyNum> MonkeySim<N> {
+    /// Start simulating the monkeys, with worry levels reduced like `count_inspections` does
+    pub fn new(monkeys: &[Monkey], intimidating: bool, lcm: Option<usize>) -> Self {
+        Self {
+            monkeys: monkeys
+                .iter()
+                .map(|monkey| Monkey {
+                    extra_intimidating: intimidating,
+                    ..monkey.worry_in::<N>()
+                })
+                .collect(),
+            lcm,
+            inspection_counts: vec![0; monkeys.len()],
+            rounds: 0,
+        }
+    }
+
+    /// Perform a round, returning how many items each monkey inspected during it
+    pub fn round(&mut self) -> Map<usize, usize> {
+        let counts = perform_monkey_round(&mut self.monkeys, self.lcm);
+        for (&monkey, &count) in &counts {
+            self.inspection_counts[monkey] += count;
+        }
+        self.rounds += 1;
+        counts
+    }
+
+    /// How many rounds have been performed
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    /// The worry levels of the items a monkey is holding, in the order it'll inspect them
+    pub fn items_of(&self, monkey: usize) -> &[N] {
+        &self.monkeys[monkey].items
+    }
+
+    /// How many items each monkey has inspected over every round so far
+    pub fn inspection_counts(&self) -> &[usize] {
+        &self.inspection_counts
+    }
+
+    /// The state after the rounds performed so far
+    pub fn snapshot(&self) -> RoundSnapshot<N> {
+        RoundSnapshot {
+            round: self.rounds,
+            items: self
+                .monkeys
+                .iter()
+                .map(|monkey| monkey.items.clone())
+                .collect(),
+            inspection_counts: self.inspection_counts.clone(),
+        }
+    }
+
+    /// Perform rounds forever, giving the state after each
+    pub fn snapshots(mut self) -> impl Iterator<Item = RoundSnapshot<N>> {
+        std::iter::from_fn(move || {
+            self.round();
+            Some(self.snapshot())
+        })
+    }
+}
+
+/// The lcm of every monkey's divisor, which worry levels can be reduced by without changing any test
+pub fn divisors_lcm(monkeys: &[Monkey]) -> usize {
+    math::lcm_of(monkeys.iter().map(|monkey| monkey.test.0)).unwrap_or(1)
+}
+
+fn perform_monkey_round<N: MonkeyNum>(
+    monkeys: &mut [Monkey<N>],
+    lcm: Option<usize>,
+) -> Map<usize, usize> {
+    let mut inspection_counts = Map::default();
+    for i in 0..monkeys.len() {
+        // Drain monkeys current items
+        let to_inspect = monkeys[i].items.drain(0..).collect_vec();
+
+        // Inspect each item in turn and throw it to recipient monkey
+        for item in to_inspect {
+            let result = monkeys[i].inspect_item(item, lcm);
+            monkeys[result.to].items.push(result.item);
+            *inspection_counts.entry(i).or_insert(0) += 1;
+        }
+    }
+    inspection_counts
+}
+
+pub struct Day11;
+
+common::solution_fns!(Day11);
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = Vec<Monkey>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn validate(input: &str) -> common::Result<()> {
+        parse::blocks::<Monkey>(input).map(|_| ())
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        parse::blocks(input).unwrap()
+    }
+
+    fn part1(monkeys: &Self::Input) -> Self::Part1 {
+        // Perform 20 monkey rounds
+        let inspection_counts = count_inspections::<usize>(monkeys, 20, false, None);
+
+        // Find busiest monkeys
+        top_k(inspection_counts, 2).into_iter().product()
+    }
+
+    fn part2(monkeys: &Self::Input) -> Self::Part2 {
+        // Compute LCM of divisors
+        let lcm = divisors_lcm(monkeys);
+
+        // Perform 10000 monkey rounds with intimidating monkeys
+        let inspection_counts = count_inspections::<usize>(monkeys, 10000, true, Some(lcm));
+
+        // Find busiest monkeys
+        top_k(inspection_counts, 2).into_iter().product()
+    }
+}
+
+/* Util */
+
+/// Take first whitespace-seperated segment of string that can be parsed into desired type
+fn take_first<V>(s: &str) -> Option<V>
+where
+    V: FromStr,
+{
+    s.split(' ').flat_map(|v| v.parse()).next()
+}
+
+/* Display Implementations */
+
+impl<N: Display> std::fmt::Display for Monkey<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Items: {}", self.items.iter().join(", "))
+    }
+}
+
+impl<N: Display> std::fmt::Debug for MonkeyThrowResult<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "throw {} to {}", self.item, self.to)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn test_monkey_inspection_single_round() {
+        let mut monkeys: Vec<Monkey> =
+            parse::blocks(&read_to_string("./sample.txt").unwrap()).unwrap();
+        perform_monkey_round(&mut monkeys, None);
+        assert_eq!(monkeys[0].items, vec![20, 23, 27, 26]);
+        assert_eq!(monkeys[1].items, vec![2080, 25, 167, 207, 401, 1046]);
+        assert!(monkeys[2].items.is_empty());
+        assert!(monkeys[3].items.is_empty());
+    }
+
+    #[test]
+    fn test_monkey_inspection_twenty_rounds() {
+        let monkeys: Vec<Monkey> = parse::blocks(&read_to_string("./sample.txt").unwrap()).unwrap();
+        let inspection_counts = count_inspections::<usize>(&monkeys, 20, false, None);
+        let monkey_business: usize = top_k(&inspection_counts, 2).into_iter().product();
+        assert_eq!(inspection_counts, vec![101, 95, 7, 105]);
+        assert_eq!(monkey_business, 10605);
+    }
+
+    #[test]
+    fn test_monkey_starting_empty() {
+        // Monkey 0 has nothing to inspect until monkey 1 throws it an item at the end of round 1
+        let mut monkeys = Day11::parse(
+            "Monkey 0:
+  Starting items: 1
+  Operation: new = old + 1
+  Test: divisible by 1
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 5
+  Operation: new = old + 1
+  Test: divisible by 1
+    If true: throw to monkey 0
+    If false: throw to monkey 0",
+        );
+        monkeys[0].items.clear();
+        assert_eq!(count_inspections::<usize>(&monkeys, 3, false, None), [2, 3]);
+    }
+
+    #[test]
+    fn test_parse_error_context() {
+        let monkey = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * x19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3";
+        let err = Monkey::from_str(monkey).unwrap_err();
+        assert!(matches!(err, AocError::Parse { line: 3, .. }), "{}", err);
+    }
+
+    #[test]
+    fn test_rounds_from_puzzle_text() {
+        let monkeys = Day11::parse(&read_to_string("./sample.txt").unwrap());
+        let expected: [(usize, [&[usize]; 2]); 12] = [
+            (1, [&[20, 23, 27, 26], &[2080, 25, 167, 207, 401, 1046]]),
+            (2, [&[695, 10, 71, 135, 350], &[43, 49, 58, 55, 362]]),
+            (3, [&[16, 18, 21, 20, 122], &[1468, 22, 150, 286, 739]]),
+            (4, [&[491, 9, 52, 97, 248, 34], &[39, 45, 43, 258]]),
+            (5, [&[15, 17, 16, 88, 1037], &[20, 110, 205, 524, 72]]),
+            (6, [&[8, 70, 176, 26, 34], &[481, 32, 36, 186, 2190]]),
+            (7, [&[162, 12, 14, 64, 732, 17], &[148, 372, 55, 72]]),
+            (8, [&[51, 126, 20, 26, 136], &[343, 26, 30, 1546, 36]]),
+            (9, [&[116, 10, 12, 517, 14], &[108, 267, 43, 55, 288]]),
+            (10, [&[91, 16, 20, 98], &[481, 245, 22, 26, 1092, 30]]),
+            (15, [&[83, 44, 8, 184, 9, 20, 26, 102], &[110, 36]]),
+            (20, [&[10, 12, 14, 26, 34], &[245, 93, 53, 199, 115]]),
+        ];
+        let snapshots = MonkeySim::<usize>::new(&monkeys, false, None)
+            .snapshots()
+            .take(20)
+            .collect_vec();
+        for (round, [first, second]) in expected {
+            let snapshot = &snapshots[round - 1];
+            assert_eq!(snapshot.round, round);
+            assert_eq!(snapshot.items[0], first, "monkey 0 after round {}", round);
+            assert_eq!(snapshot.items[1], second, "monkey 1 after round {}", round);
+            assert!(snapshot.items[2].is_empty() && snapshot.items[3].is_empty());
+        }
+        assert_eq!(snapshots[19].inspection_counts, vec![101, 95, 7, 105]);
+    }
+
+    #[test]
+    fn test_inspection_counts_from_puzzle_text() {
+        let monkeys = Day11::parse(&read_to_string("./sample.txt").unwrap());
+        let mut sim = MonkeySim::<usize>::new(&monkeys, true, Some(divisors_lcm(&monkeys)));
+        let counts = sim.round();
+        assert_eq!(
+            (0..4).map(|monkey| counts[&monkey]).collect_vec(),
+            [2, 4, 3, 6]
+        );
+        while sim.rounds() < 20 {
+            sim.round();
+        }
+        assert_eq!(sim.inspection_counts(), [99, 97, 8, 103]);
+        while sim.rounds() < 1000 {
+            sim.round();
+        }
+        assert_eq!(sim.inspection_counts(), [5204, 4792, 199, 5192]);
+        assert_eq!(sim.items_of(2).len() + sim.items_of(3).len(), 0);
+    }
+
+    #[test]
+    fn test_big_worry_levels() {
+        let monkeys = Day11::parse(&read_to_string("./sample.txt").unwrap());
+
+        // Relieved worry stays small, so both types agree
+        let counts = count_inspections::<BigUint>(&monkeys, 20, false, None);
+        assert_eq!(
+            counts,
+            count_inspections::<usize>(&monkeys, 20, false, None)
+        );
+
+        // Reducing by the lcm doesn't change where any item goes
+        let lcm = divisors_lcm(&monkeys);
+        let counts = count_inspections::<BigUint>(&monkeys, 20, true, None);
+        assert_eq!(
+            counts,
+            count_inspections::<usize>(&monkeys, 20, true, Some(lcm))
+        );
+        assert_eq!(counts, vec![99, 97, 8, 103]);
+    }
+}
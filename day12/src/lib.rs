@@ -0,0 +1,597 @@
+use colored::{ColoredString, Colorize};
+use common::{
+    search,
+    tracing::{debug, trace},
+    viz::{Svg, Visualizer},
+    AocError, Solution,
+};
+use itertools::Itertools;
+
+mod rules;
+pub use rules::{Climb, Diagonal, MoveRule, Reversed, ALL_DIRECTIONS, ORTHOGONAL};
+
+#[derive(Eq, PartialEq, Hash, Clone, Copy)]
+struct MapPosition {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+pub struct Map {
+    heights: Vec<u8>,
+    width: usize,
+    height: usize,
+    start_position: MapPosition,
+    goal_position: MapPosition,
+}
+
+struct Path<'a> {
+    map: &'a Map,
+    path: Vec<MapPosition>,
+}
+
+impl Map {
+    /// Number each position on the map, row by row
+    fn index(&self, position: MapPosition) -> usize {
+        position.y * self.width + position.x
+    }
+
+    fn position(&self, index: usize) -> MapPosition {
+        MapPosition {
+            x: index % self.width,
+            y: index / self.width,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// The height of a square from 0 (`a`) to 25 (`z`)
+    pub fn height_at(&self, (x, y): (usize, usize)) -> u8 {
+        self.heights[y * self.width + x]
+    }
+
+    /// The cost of the cheapest path from the start to the goal under a rule
+    pub fn cheapest_path(&self, rule: &impl MoveRule) -> Option<usize> {
+        let path = Path::find_path_with(self, self.start_position, rule, |position| {
+            position == self.goal_position
+        })?;
+        Some(path.cost(rule))
+    }
+
+    /// Search the map by position index, keeping parents in a vec rather than hashing positions
+    fn bfs<I: IntoIterator<Item = MapPosition>>(
+        &self,
+        start: MapPosition,
+        successors: impl Fn(MapPosition) -> I,
+        is_goal: impl Fn(MapPosition) -> bool,
+    ) -> Option<Vec<MapPosition>> {
+        let path = search::bfs_indexed(
+            self.heights.len(),
+            self.index(start),
+            |index| {
+                successors(self.position(index))
+                    .into_iter()
+                    .map(|position| self.index(position))
+            },
+            |index| is_goal(self.position(index)),
+        )?;
+        Some(path.into_iter().map(|index| self.position(index)).collect())
+    }
+
+    /// Get the neighbors of a position that a rule lets you step to, with what each step costs
+    fn get_neighbors<'a, R: MoveRule>(
+        &'a self,
+        position: MapPosition,
+        rule: &'a R,
+    ) -> impl Iterator<Item = (MapPosition, usize)> + 'a {
+        rule.offsets()
+            .iter()
+            .flat_map(move |&offset| position + offset)
+            .filter_map(move |to| Some((to, rule.cost(self, position.into(), to.into())?)))
+    }
+}
+
+impl<'a> Path<'a> {
+    fn len(&self) -> usize {
+        self.path.len() - 1
+    }
+
+    /// What following the path costs under a rule
+    fn cost(&self, rule: &impl MoveRule) -> usize {
+        self.path
+            .iter()
+            .tuple_windows()
+            .map(|(&from, &to)| rule.cost(self.map, from.into(), to.into()).unwrap())
+            .sum()
+    }
+
+    /// Find a cheapest path from a position to one satisfying `is_goal`, moving under a rule.
+    /// Uses BFS when every step costs the same, or Dijkstra when they don't.
+    fn find_path_with(
+        map: &'a Map,
+        start_position: MapPosition,
+        rule: &impl MoveRule,
+        is_goal: impl Fn(MapPosition) -> bool,
+    ) -> Option<Self> {
+        let path = if rule.is_uniform() {
+            map.bfs(
+                start_position,
+                |position| map.get_neighbors(position, rule).map(|(to, _)| to),
+                is_goal,
+            )?
+        } else {
+            search::dijkstra(
+                start_position,
+                |&position| map.get_neighbors(position, rule),
+                |&position| is_goal(position),
+            )?
+            .1
+        };
+        Some(Self { map, path })
+    }
+
+    /// Use BFS to find a path
+    fn find_path(map: &'a Map, start_position: MapPosition) -> Option<Self> {
+        Self::find_path_with(map, start_position, &Climb, |position| {
+            position == map.goal_position
+        })
+    }
+
+    /// Use BFS backwards from the goal to find the shortest path from any of the lowest squares
+    fn find_path_reverse(map: &'a Map) -> Option<Self> {
+        Self::find_path_with(map, map.goal_position, &Reversed(Climb), |position| {
+            map[position] == 0
+        })
+        .map(|mut path| {
+            path.path.reverse();
+            path
+        })
+    }
+}
+
+pub struct Day12;
+
+common::solution_fns!(Day12);
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = Map;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn validate(input: &str) -> common::Result<()> {
+        input.parse::<Map>().map(|_| ())
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        // Parse input as map
+        input.parse().unwrap()
+    }
+
+    fn part1(map: &Self::Input) -> Self::Part1 {
+        // Find length of path from start
+        let path = Path::find_path(map, map.start_position).unwrap();
+        debug!(steps = path.len(), "Found path from start");
+        Exploration::animate(&path, map.start_position, map.goal_position, &Climb);
+        trace!("Path from start: {:?}", path);
+        if let Some(svg_path) = Svg::path_from_args() {
+            path.to_svg()
+                .save(&svg_path)
+                .unwrap_or_else(|err| eprintln!("Couldn't save {}: {}", svg_path.display(), err));
+        }
+        path.len()
+    }
+
+    fn part2(map: &Self::Input) -> Self::Part2 {
+        // Search backwards from the goal to the nearest 'a' location
+        let path = Path::find_path_reverse(map).unwrap();
+        debug!(steps = path.len(), "Found nearest lowest square");
+        Exploration::animate(&path, map.goal_position, path.path[0], &Reversed(Climb));
+        trace!("Path from nearest lowest square: {:?}", path);
+        path.len()
+    }
+}
+
+/* Std Implementations */
+
+impl std::ops::Index<MapPosition> for Map {
+    type Output = u8;
+    fn index(&self, position: MapPosition) -> &Self::Output {
+        &self[&position]
+    }
+}
+
+impl std::ops::Index<&MapPosition> for Map {
+    type Output = u8;
+    fn index(&self, position: &MapPosition) -> &Self::Output {
+        assert!(position.x < self.width && position.y < self.height);
+        &self.heights[position.y * self.width + position.x]
+    }
+}
+
+impl From<MapPosition> for (usize, usize) {
+    fn from(position: MapPosition) -> Self {
+        (position.x, position.y)
+    }
+}
+
+impl std::ops::Add<(isize, isize)> for MapPosition {
+    type Output = Option<MapPosition>;
+    fn add(self, rhs: (isize, isize)) -> Self::Output {
+        let x_in_bounds = (0..(self.width as isize)).contains(&((self.x as isize) + rhs.0));
+        let y_in_bounds = (0..(self.height as isize)).contains(&((self.y as isize) + rhs.1));
+        (x_in_bounds && y_in_bounds).then_some(Self {
+            x: ((self.x as isize) + rhs.0) as usize,
+            y: ((self.y as isize) + rhs.1) as usize,
+            ..self
+        })
+    }
+}
+
+impl std::str::FromStr for Map {
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut start = None;
+        let mut goal = None;
+        let grid: Vec<Vec<_>> = s
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(x, c)| {
+                        let height_symbol = match c {
+                            'S' => {
+                                start = Some((x, y));
+                                'a'
+                            }
+                            'E' => {
+                                goal = Some((x, y));
+                                'z'
+                            }
+                            'a'..='z' => c,
+                            _ => {
+                                return Err(AocError::Parse {
+                                    line: y + 1,
+                                    column: x + 1,
+                                    message: format!("Invalid height '{}'", c),
+                                })
+                            }
+                        };
+                        Ok((height_symbol as u8) - b'a')
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+        let (height, width) = (grid.len(), grid.first().map_or(0, Vec::len));
+        if let Some(y) = grid.iter().position(|row| row.len() != width) {
+            return Err(AocError::parse("Rows have different lengths").offset_lines(y));
+        }
+        let heights = grid.into_iter().flatten().collect();
+        if let (Some(start), Some(goal)) = (start, goal) {
+            Ok(Self {
+                heights,
+                height,
+                width,
+                start_position: MapPosition {
+                    x: start.0,
+                    y: start.1,
+                    width,
+                    height,
+                },
+                goal_position: MapPosition {
+                    x: goal.0,
+                    y: goal.1,
+                    width,
+                    height,
+                },
+            })
+        } else {
+            Err(AocError::parse("Didn't find start and end"))
+        }
+    }
+}
+
+/* Display Implementations */
+
+impl std::fmt::Debug for MapPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl std::fmt::Debug for Path<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f)?;
+        let s = self
+            .map
+            .heights
+            .chunks(self.map.width)
+            .enumerate()
+            .map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(x, &height)| {
+                        let found = self
+                            .path
+                            .iter()
+                            .enumerate()
+                            .find(|(_, p)| p.x == x && p.y == y);
+                        if let Some((i, _)) = found {
+                            self.step_symbol(i)
+                        } else {
+                            height_to_color_string(height).black()
+                        }
+                    })
+                    .join("")
+            })
+            .join("\n");
+        write!(f, "{}", s)
+    }
+}
+
+impl std::fmt::Debug for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "\n{}",
+            self.heights
+                .chunks(self.width)
+                .map(|row| row
+                    .iter()
+                    .map(|&height| height_to_color_string(height))
+                    .join(""))
+                .join("\n")
+        )
+    }
+}
+
+/* Visualisation */
+
+impl Path<'_> {
+    /// An arrow pointing from a step of the path to the next, or a star at the end
+    fn step_symbol(&self, i: usize) -> ColoredString {
+        let node = self.path[i];
+        match self.path.get(i + 1) {
+            Some(next) => {
+                let diffx = (next.x as isize) - (node.x as isize);
+                let diffy = (next.y as isize) - (node.y as isize);
+                match (diffx, diffy) {
+                    (1, 0) => ">",
+                    (-1, 0) => "<",
+                    (0, -1) => "^",
+                    (0, 1) => "v",
+                    _ => "?",
+                }
+                .red()
+            }
+            None => "*".green(),
+        }
+    }
+}
+
+/// A breadth first search spreading over the map, for animating with `--animate`
+struct Exploration<'a> {
+    map: &'a Map,
+    /// Whether each position has been reached yet, by index
+    visited: Vec<bool>,
+    /// The positions reached in the latest step
+    frontier: Vec<MapPosition>,
+    /// The path found, drawn once the search is over
+    path: Option<&'a Path<'a>>,
+}
+
+impl<'a> Exploration<'a> {
+    /// Animate a search from `start` spreading one step at a time until it reaches `goal`, then
+    /// draw the path it found. Big maps only draw some of the steps.
+    fn animate(path: &'a Path<'a>, start: MapPosition, goal: MapPosition, rule: &impl MoveRule) {
+        let map = path.map;
+        let mut viz = Visualizer::from_args().every(map.heights.len() / 2000 + 1);
+        if !viz.is_enabled() {
+            return;
+        }
+
+        let mut exploration = Self {
+            map,
+            visited: vec![false; map.heights.len()],
+            frontier: vec![start],
+            path: None,
+        };
+        exploration.visited[map.index(start)] = true;
+        while !exploration.frontier.is_empty() && !exploration.frontier.contains(&goal) {
+            viz.frame(&exploration);
+            let mut next = Vec::new();
+            for &position in &exploration.frontier {
+                for (child, _) in map.get_neighbors(position, rule) {
+                    let visited = &mut exploration.visited[map.index(child)];
+                    if !*visited {
+                        *visited = true;
+                        next.push(child);
+                    }
+                }
+            }
+            exploration.frontier = next;
+        }
+
+        exploration.path = Some(path);
+        viz.final_frame(&exploration);
+    }
+}
+
+impl std::fmt::Display for Exploration<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path_steps: common::Map<MapPosition, usize> = self
+            .path
+            .map(|path| path.path.iter().enumerate().map(|(i, &p)| (p, i)).collect())
+            .unwrap_or_default();
+        for y in 0..self.map.height {
+            for x in 0..self.map.width {
+                let position = self.map.position(y * self.map.width + x);
+                let height = height_to_color_string(self.map[position]);
+                let c = match (self.path, path_steps.get(&position)) {
+                    (Some(path), Some(&i)) => path.step_symbol(i),
+                    _ if self.frontier.contains(&position) => height.black().on_bright_white(),
+                    _ if self.visited[self.map.index(position)] => height.dimmed(),
+                    _ => height,
+                };
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Path<'_> {
+    /// Draw the heightmap shaded from low to high, with the path over it
+    fn to_svg(&self) -> Svg {
+        let mut svg = Svg::new();
+        for (i, &height) in self.map.heights.iter().enumerate() {
+            let (x, y) = (i % self.map.width, i / self.map.width);
+            let lightness = 15 + height as usize * 3;
+            svg.cell(
+                x as f64,
+                y as f64,
+                &format!("hsl(140, 40%, {}%)", lightness),
+            );
+        }
+        svg.line(
+            self.path
+                .iter()
+                .map(|p| (p.x as f64 + 0.5, p.y as f64 + 0.5)),
+            "#f38ba8",
+        );
+        svg
+    }
+}
+
+/* Util */
+
+fn height_to_color_string(height: u8) -> ColoredString {
+    let s = ((height + b'a') as char).to_string();
+    match height {
+        0..=1 => s.cyan(),
+        2..=4 => s.green(),
+        5..=12 => s.yellow(),
+        13..=18 => s.red(),
+        19..=26 => s.bright_magenta(),
+        _ => s.white(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_sample() {
+        let input = std::fs::read_to_string("./sample.txt").unwrap();
+        let map = Day12::parse(&input);
+        assert_eq!(Day12::part1(&map), 31);
+        assert_eq!(Day12::part2(&map), 29);
+    }
+
+    /// The shortest of the paths from every lowest square, which the reverse search should match
+    fn shortest_from_any_lowest(map: &Map) -> Option<usize> {
+        (0..map.heights.len())
+            .filter(|&i| map.heights[i] == 0)
+            .map(|i| map.position(i))
+            .filter_map(|start| Path::find_path(map, start))
+            .map(|path| path.len())
+            .min()
+    }
+
+    #[test]
+    fn test_path_lengths_unchanged() {
+        // Searching by index finds paths as short as searching by hashed position
+        let map = Day12::parse(include_str!("../sample.txt"));
+        let hashed = search::bfs(
+            map.start_position,
+            |&position| {
+                map.get_neighbors(position, &Climb)
+                    .map(|(to, _)| to)
+                    .collect_vec()
+            },
+            |&position| position == map.goal_position,
+        )
+        .unwrap();
+        let path = Path::find_path(&map, map.start_position).unwrap();
+        assert_eq!(path.len(), hashed.len() - 1);
+        assert_eq!(path.len(), 31);
+        assert_eq!(path.path[0], map.start_position);
+        assert_eq!(Path::find_path_reverse(&map).unwrap().len(), 29);
+    }
+
+    #[test]
+    fn test_exploration() {
+        let map = Day12::parse(include_str!("../sample.txt"));
+        let path = Path::find_path(&map, map.start_position).unwrap();
+        let mut exploration = Exploration {
+            map: &map,
+            visited: vec![false; map.heights.len()],
+            frontier: vec![map.start_position],
+            path: None,
+        };
+        let frame = exploration.to_string();
+        assert_eq!(frame.lines().count(), map.height);
+        assert!(!frame.contains('>'));
+
+        exploration.path = Some(&path);
+        assert!(exploration.to_string().contains('>'));
+    }
+
+    #[test]
+    fn test_move_rules() {
+        let map = Day12::parse(include_str!("../sample.txt"));
+        assert_eq!(map.cheapest_path(&Climb), Some(31));
+
+        // Stepping diagonally can only help
+        let diagonal = map.cheapest_path(&Diagonal(Climb)).unwrap();
+        assert!(diagonal < 31);
+
+        // Climbing anything but only dropping one at a time, which walks straight to the goal
+        let descend =
+            |map: &Map, from, to| (map.height_at(from) <= map.height_at(to) + 1).then_some(1);
+        assert_eq!(map.cheapest_path(&descend), Some(7));
+
+        // Climbing costs more the steeper it is, which needs Dijkstra
+        let steep = |map: &Map, from, to| {
+            let (from, to) = (map.height_at(from), map.height_at(to));
+            (to <= from + 1).then_some(1 + to.saturating_sub(from) as usize * 10)
+        };
+        let cost = map.cheapest_path(&steep).unwrap();
+        assert!(cost >= 31 + 25 * 10);
+        let path = Path::find_path_with(&map, map.start_position, &steep, |position| {
+            position == map.goal_position
+        })
+        .unwrap();
+        assert_eq!(path.cost(&steep), cost);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(Day12::validate(include_str!("../sample.txt")), Ok(()));
+        assert_eq!(
+            Day12::validate("Sab\nc?E").unwrap_err().to_string(),
+            "Parse error at 2:2: Invalid height '?'"
+        );
+        assert!(Day12::validate("SabE\nbc").is_err());
+        assert!(Day12::validate("abc\ndeE").is_err());
+        assert!(Day12::validate("").is_err());
+    }
+
+    #[test]
+    fn test_find_path_reverse() {
+        let map = Day12::parse(include_str!("../sample.txt"));
+        let path = Path::find_path_reverse(&map).unwrap();
+        assert_eq!(Some(path.len()), shortest_from_any_lowest(&map));
+        assert_eq!(map[path.path[0]], 0);
+        assert_eq!(path.path.last(), Some(&map.goal_position));
+
+        // Each step could have been climbed going forwards
+        for (&from, &to) in path.path.iter().tuple_windows() {
+            assert!(map.get_neighbors(from, &Climb).any(|(next, _)| next == to));
+        }
+    }
+}
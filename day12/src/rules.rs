@@ -0,0 +1,91 @@
+//! Rules for how you can move around the heightmap, so variations on the puzzle can be searched
+
+use crate::Map;
+
+/// Steps up, down, left and right
+pub const ORTHOGONAL: &[(isize, isize)] = &[(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// Steps to any of the eight surrounding squares
+pub const ALL_DIRECTIONS: &[(isize, isize)] = &[
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// How you're allowed to move around the map, where squares are given as `(x, y)`.
+/// Closures taking the map and the squares to step from and to are rules too.
+pub trait MoveRule {
+    /// The offsets of the squares you could step to, which must go both ways
+    fn offsets(&self) -> &[(isize, isize)] {
+        ORTHOGONAL
+    }
+
+    /// What stepping between neighbouring squares costs, or `None` if it isn't allowed
+    fn cost(&self, map: &Map, from: (usize, usize), to: (usize, usize)) -> Option<usize>;
+
+    /// Whether every allowed step costs the same, so a breadth first search finds the cheapest path
+    fn is_uniform(&self) -> bool {
+        false
+    }
+}
+
+/// The puzzle's rule, where you can climb at most one higher but drop any distance
+pub struct Climb;
+
+impl MoveRule for Climb {
+    fn cost(&self, map: &Map, from: (usize, usize), to: (usize, usize)) -> Option<usize> {
+        (map.height_at(to) <= map.height_at(from) + 1).then_some(1)
+    }
+
+    fn is_uniform(&self) -> bool {
+        true
+    }
+}
+
+/// Follow a rule backwards, for searching from where a path ends
+pub struct Reversed<R>(pub R);
+
+impl<R: MoveRule> MoveRule for Reversed<R> {
+    fn offsets(&self) -> &[(isize, isize)] {
+        self.0.offsets()
+    }
+
+    fn cost(&self, map: &Map, from: (usize, usize), to: (usize, usize)) -> Option<usize> {
+        self.0.cost(map, to, from)
+    }
+
+    fn is_uniform(&self) -> bool {
+        self.0.is_uniform()
+    }
+}
+
+/// Allow diagonal steps under a rule as well as its own
+pub struct Diagonal<R>(pub R);
+
+impl<R: MoveRule> MoveRule for Diagonal<R> {
+    fn offsets(&self) -> &[(isize, isize)] {
+        ALL_DIRECTIONS
+    }
+
+    fn cost(&self, map: &Map, from: (usize, usize), to: (usize, usize)) -> Option<usize> {
+        self.0.cost(map, from, to)
+    }
+
+    fn is_uniform(&self) -> bool {
+        self.0.is_uniform()
+    }
+}
+
+impl<F> MoveRule for F
+where
+    F: Fn(&Map, (usize, usize), (usize, usize)) -> Option<usize>,
+{
+    fn cost(&self, map: &Map, from: (usize, usize), to: (usize, usize)) -> Option<usize> {
+        self(map, from, to)
+    }
+}
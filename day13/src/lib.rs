@@ -0,0 +1,353 @@
+use common::{parse, AocError, Solution};
+
+use itertools::Itertools;
+use nom::{branch::alt, bytes::complete::tag, combinator::map, sequence::delimited, IResult};
+use std::{cmp::Ordering, str::FromStr};
+
+pub struct PacketPair {
+    left: Packet,
+    right: Packet,
+}
+
+/// A packet, which is also valid JSON. With the `serde` feature it can be read and written as JSON.
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(untagged)
+)]
+pub enum Packet {
+    Number(u32),
+    List(Vec<Packet>),
+}
+
+pub struct Day13;
+
+common::solution_fns!(Day13);
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = Vec<PacketPair>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn validate(input: &str) -> common::Result<()> {
+        // Packets come in pairs
+        parse::blocks_with(input, |block| match block.lines().count() {
+            2 => Ok(()),
+            n => Err(AocError::parse(format!("Expected 2 packets, found {}", n))),
+        })
+        .map(|_| ())
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        parse::blocks(input).unwrap()
+    }
+
+    fn part1(pairs: &Self::Input) -> Self::Part1 {
+        pairs
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.correct_order())
+            .map(|(i, _)| i + 1)
+            .sum()
+    }
+
+    fn part2(pairs: &Self::Input) -> Self::Part2 {
+        decoder_key(pairs.iter().flat_map(|p| [&p.left, &p.right]))
+    }
+}
+
+/// Multiply where the divider packets `[[2]]` and `[[6]]` would end up if they were sorted in
+/// with the packets. Each divider's place is just one more than how many packets come before it,
+/// so they can be counted without sorting. Dividers go before any packets equal to them.
+pub fn decoder_key<'a>(packets: impl IntoIterator<Item = &'a Packet>) -> usize {
+    let dividers = [divider(2), divider(6)];
+    let mut places = [1, 2];
+    for packet in packets {
+        for (place, divider) in places.iter_mut().zip(&dividers) {
+            if packet < divider {
+                *place += 1;
+            }
+        }
+    }
+    places.into_iter().product()
+}
+
+/// A divider packet holding a single number, like `[[2]]`
+fn divider(number: u32) -> Packet {
+    Packet::List(vec![Packet::List(vec![Packet::Number(number)])])
+}
+
+impl PacketPair {
+    fn correct_order(&self) -> bool {
+        Packet::correct_order(&self.left, &self.right)
+    }
+}
+
+impl Packet {
+    /// Whether a pair of packets is in the right order, which packets that compare equal aren't
+    fn correct_order(x: &Packet, y: &Packet) -> bool {
+        x < y
+    }
+
+    /// Read a packet as JSON rather than with the puzzle's parser
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, AocError> {
+        serde_json::from_str(json).map_err(|err| AocError::Parse {
+            line: err.line(),
+            column: err.column(),
+            message: format!("Invalid packet ({})", err),
+        })
+    }
+
+    /// Write a packet as JSON, which is the same as how the puzzle writes it
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Packets are always valid JSON")
+    }
+
+    fn wrap(&self) -> Self {
+        Packet::List(vec![self.clone()])
+    }
+
+    fn parse(input: &str) -> IResult<&str, Self> {
+        alt((
+            map(parse::unsigned_int, Packet::Number),
+            map(
+                delimited(
+                    tag("["),
+                    parse::comma_separated_list(Packet::parse),
+                    tag("]"),
+                ),
+                Packet::List,
+            ),
+        ))(input)
+    }
+}
+
+/// Packets are ordered by the puzzle's rules, so a number and a list holding just that number
+/// are equal
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Number(a), Packet::Number(b)) => a.cmp(b),
+
+            // Compare elements in turn, with the shorter list first if they run out
+            (Packet::List(a), Packet::List(b)) => a.iter().cmp(b.iter()),
+
+            // If only one is a list, wrap it in a list
+            (Packet::Number(_), Packet::List(_)) => self.wrap().cmp(other),
+            (Packet::List(_), Packet::Number(_)) => self.cmp(&other.wrap()),
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+impl FromStr for Packet {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // This is a shrine to Max
+        parse::parse_all(Packet::parse, s)
+    }
+}
+
+impl FromStr for PacketPair {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (left, right) = s
+            .lines()
+            .collect_tuple()
+            .ok_or_else(|| AocError::BadInput(format!("Expected a pair of packets: '{}'", s)))?;
+        Ok(Self {
+            left: left.parse()?,
+            right: right.parse().map_err(|err: AocError| err.offset_lines(1))?,
+        })
+    }
+}
+
+impl std::fmt::Debug for Packet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Packet::Number(num) => write!(f, "{}", num),
+            Packet::List(elements) => write!(
+                f,
+                "[{}]",
+                elements.iter().map(|el| format!("{:?}", el)).join(",")
+            ),
+        }
+    }
+}
+
+impl std::fmt::Debug for PacketPair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "L{:?}", self.left)?;
+        writeln!(f, "R{:?}", self.right)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::sample_test;
+    use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(Day13::validate("[1]\n[2]\n\n[3]\n[4]\n"), Ok(()));
+        assert_eq!(
+            Day13::validate("[1]\n[2]\n\n[3]\n")
+                .unwrap_err()
+                .to_string(),
+            "Parse error at 4:1: Expected 2 packets, found 1 (in block 2)"
+        );
+    }
+
+    macro_rules! assert_correct {
+        ($a: expr, $b: expr) => {{
+            let a = Packet::from_str($a).unwrap();
+            let b = Packet::from_str($b).unwrap();
+            assert!(Packet::correct_order(&a, &b));
+        }};
+    }
+
+    macro_rules! assert_incorrect {
+        ($a: expr, $b: expr) => {{
+            let a = Packet::from_str($a).unwrap();
+            let b = Packet::from_str($b).unwrap();
+            assert!(!Packet::correct_order(&a, &b));
+        }};
+    }
+
+    #[test]
+    fn test_pair_correctness() {
+        assert_correct!("[1,1,3,1,1]", "[1,1,5,1,1]");
+        assert_correct!("[[1],[2,3,4]]", "[[1],4]");
+        assert_incorrect!("[9]", "[[8,7,6]]");
+        assert_correct!("[[4,4],4,4]", "[[4,4],4,4,4]");
+        assert_incorrect!("[7,7,7,7]", "[7,7,7]");
+        assert_correct!("[]", "[3]");
+        assert_incorrect!("[[[]]]", "[[]]");
+        assert_incorrect!("[1,[2,[3,[4,[5,6,7]]]],8,9]", "[1,[2,[3,[4,[5,6,0]]]],8,9]");
+    }
+
+    #[test]
+    fn test_equal_packets() {
+        let packet = |s| Packet::from_str(s).unwrap();
+        assert_eq!(packet("[1]").cmp(&packet("[[1]]")), Ordering::Equal);
+        assert_eq!(packet("[[1],[1]]").cmp(&packet("[1,1]")), Ordering::Equal);
+        assert_eq!(
+            packet("[[1],[1]]").cmp(&packet("[[[1]],2]")),
+            Ordering::Less
+        );
+        assert_eq!(
+            packet("[[[1]],2]").cmp(&packet("[[1],[1]]")),
+            Ordering::Greater
+        );
+        assert_incorrect!("[[1],[1]]", "[1,[[1]]]");
+    }
+
+    /// A random packet with small numbers and short lists, so lots of them compare equal
+    fn random_packet(rng: &mut StdRng, depth: usize) -> Packet {
+        if depth == 0 || rng.gen_bool(0.3) {
+            Packet::Number(rng.gen_range(0..4))
+        } else {
+            Packet::List(
+                (0..rng.gen_range(0..4))
+                    .map(|_| random_packet(rng, depth - 1))
+                    .collect(),
+            )
+        }
+    }
+
+    #[test]
+    fn test_ordering_is_total() {
+        let mut rng = StdRng::seed_from_u64(13);
+        let packets = (0..60).map(|_| random_packet(&mut rng, 4)).collect_vec();
+        for (a, b) in packets.iter().cartesian_product(&packets) {
+            assert_eq!(a.cmp(b), b.cmp(a).reverse(), "{:?} and {:?}", a, b);
+            for c in &packets {
+                if a <= b && b <= c {
+                    assert!(a <= c, "{:?} <= {:?} <= {:?}", a, b, c);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sorting_is_stable() {
+        let mut rng = StdRng::seed_from_u64(2022);
+        let mut packets = (0..500)
+            .map(|i| (random_packet(&mut rng, 3), i))
+            .collect_vec();
+        packets.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for ((a, i), (b, j)) in packets.iter().tuple_windows() {
+            assert!(a <= b);
+            if a == b {
+                assert!(i < j, "{:?} moved before {:?}", b, a);
+            }
+        }
+
+        // Sorting in another order ends up with equal packets
+        let mut shuffled = packets.iter().map(|(p, _)| p.clone()).collect_vec();
+        shuffled.shuffle(&mut rng);
+        shuffled.sort();
+        assert!(shuffled.iter().eq(packets.iter().map(|(p, _)| p)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_round_trip() {
+        let sample = std::fs::read_to_string("./sample.txt").unwrap();
+        for line in sample.lines().filter(|line| !line.is_empty()) {
+            // Both parsers agree, including on the exact structure
+            let packet = Packet::from_str(line).unwrap();
+            let json = Packet::from_json(line).unwrap();
+            assert_eq!(format!("{:?}", json), format!("{:?}", packet));
+            assert_eq!(packet.to_json(), line);
+        }
+        assert!(Packet::from_json("[1,[2,\"3\"]]").is_err());
+        assert!(Packet::from_json("[1,-2]").is_err());
+    }
+
+    #[test]
+    fn test_decoder_key() {
+        let packets = ["[1,1,3,1,1]", "[[1],4]", "[[8,7,6]]", "[5]", "[7,7,7]"]
+            .map(|s| Packet::from_str(s).unwrap());
+        assert_eq!(decoder_key(&packets), 3 * 5);
+        assert_eq!(decoder_key(&[]), 2);
+
+        // Matches sorting the dividers in with the packets
+        let mut rng = StdRng::seed_from_u64(140);
+        let packets = (0..200).map(|_| random_packet(&mut rng, 3)).collect_vec();
+        let mut sorted = packets.iter().map(|p| (p, false)).collect_vec();
+        let dividers = [divider(2), divider(6)];
+        sorted.extend(dividers.iter().map(|p| (p, true)));
+        sorted.sort_by(|(a, a_divider), (b, b_divider)| a.cmp(b).then(b_divider.cmp(a_divider)));
+        let expected: usize = sorted
+            .iter()
+            .positions(|&(_, is_divider)| is_divider)
+            .map(|i| i + 1)
+            .product();
+        assert_eq!(decoder_key(&packets), expected);
+    }
+
+    sample_test!(test_part1_with_sample, Day13::part1, file "sample.txt", 13);
+    sample_test!(test_part2_with_sample, Day13::part2, file "sample.txt", 140);
+    sample_test!(test_part1_with_crlf_sample, Day13::part1, file "sample_crlf.txt", 13);
+}
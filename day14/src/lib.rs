@@ -0,0 +1,541 @@
+use std::str::FromStr;
+
+use colored::Colorize;
+use common::{
+    cli::{Flag, Flags},
+    parse,
+    viz::Visualizer,
+    AocError, FastSet, Grid, Point, Solution, SparseGrid, VecGrid,
+};
+use itertools::Itertools;
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum SandCell {
+    #[default]
+    Empty,
+    Rock,
+    Sand,
+}
+
+/// Where a world keeps its cells
+pub trait SandCells {
+    /// Storage for every cell from `min` to `max`, which are all that can ever be filled
+    fn with_bounds(min: Position, max: Position) -> Self;
+
+    fn get(&self, position: Position) -> SandCell;
+
+    fn set(&mut self, position: Position, cell: SandCell);
+
+    /// The number of cells holding a kind of cell
+    fn count(&self, cell: SandCell) -> usize;
+
+    /// The top left and bottom right corners of the smallest box containing every filled cell
+    fn filled_bounds(&self) -> Option<(Position, Position)>;
+}
+
+/// Cells in a grid covering everything the sand can reach, which is fast to look up
+#[derive(Debug)]
+pub struct DenseCells {
+    grid: VecGrid<SandCell>,
+    /// The position of the grid's top left cell
+    origin: Position,
+}
+
+/// Where falling sand tries to move to, in order
+const FALL_OFFSETS: [Position; 3] = [
+    Position { x: 0, y: 1 },
+    Position { x: -1, y: 1 },
+    Position { x: 1, y: 1 },
+];
+
+#[derive(Debug)]
+struct SandWorld<C = DenseCells> {
+    cells: C,
+    sand_spawn: Position,
+    floor_offset: Option<isize>,
+    lowest_rock: isize,
+    /// Whether a grain is falling down the end of `path` right now
+    falling: bool,
+    /// Where the last grain of sand fell through, from the spawn down. The next grain follows
+    /// the same path until the cell the last one came to rest in, so it can start from there.
+    path: Vec<Position>,
+}
+
+struct SandWorldBuilder {
+    rock_sequences: Vec<RockLineSequence>,
+    sand_spawn: Option<Position>,
+    floor_offset: Option<isize>,
+}
+
+pub type Position = Point<isize>;
+
+#[derive(Debug, Clone)]
+pub struct RockLineSequence {
+    points: Vec<Position>,
+}
+
+#[derive(Debug, PartialEq)]
+enum SandOutcome {
+    SourceBlocked,
+    AtRest,
+    FellIntoVoid,
+}
+
+impl SandWorldBuilder {
+    fn new() -> Self {
+        Self {
+            rock_sequences: Vec::new(),
+            sand_spawn: None,
+            floor_offset: None,
+        }
+    }
+
+    fn rock_sequences(mut self, rock_sequences: &[RockLineSequence]) -> Self {
+        self.rock_sequences = rock_sequences.to_vec();
+        self
+    }
+
+    fn sand_spawn(mut self, sand_spawn: Position) -> Self {
+        self.sand_spawn = Some(sand_spawn);
+        self
+    }
+
+    fn floor_offset(mut self, floor_offset: isize) -> Self {
+        self.floor_offset = Some(floor_offset);
+        self
+    }
+
+    fn build<C: SandCells>(&self) -> Result<SandWorld<C>, &'static str> {
+        // Draw lines
+        let rocks = self
+            .rock_sequences
+            .iter()
+            .flat_map(|rock_sequence| {
+                let mut sequence_points = vec![];
+                rock_sequence.points.windows(2).for_each(|points| {
+                    let (point, next_point) = (points[0], points[1]);
+                    let mut curr = point;
+                    while curr != next_point {
+                        sequence_points.push(curr);
+                        curr.x += (next_point.x - point.x).signum();
+                        curr.y += (next_point.y - point.y).signum();
+                    }
+                    sequence_points.push(curr);
+                });
+                sequence_points
+            })
+            .collect_vec();
+        let lowest_rock = rocks
+            .iter()
+            .map(|pos| pos.y)
+            .max()
+            .ok_or("There must be at least one rock")?;
+        let sand_spawn = self.sand_spawn.ok_or("Sand spawn field is required")?;
+
+        // Sand stops at the floor or once it falls past the rocks, and only moves one column
+        // to the side for each row it falls, so it can't spread further than it falls
+        let bottom = lowest_rock + self.floor_offset.unwrap_or(3);
+        let spread = bottom - sand_spawn.y + 1;
+        let min = Position::new(
+            rocks
+                .iter()
+                .map(|pos| pos.x)
+                .min()
+                .unwrap()
+                .min(sand_spawn.x - spread),
+            rocks
+                .iter()
+                .map(|pos| pos.y)
+                .min()
+                .unwrap()
+                .min(sand_spawn.y),
+        );
+        let max = Position::new(
+            rocks
+                .iter()
+                .map(|pos| pos.x)
+                .max()
+                .unwrap()
+                .max(sand_spawn.x + spread),
+            bottom,
+        );
+        let mut cells = C::with_bounds(min, max);
+        for position in rocks {
+            cells.set(position, SandCell::Rock);
+        }
+
+        Ok(SandWorld {
+            cells,
+            sand_spawn,
+            floor_offset: self.floor_offset,
+            lowest_rock,
+            falling: false,
+            path: vec![sand_spawn],
+        })
+    }
+}
+
+impl<C: SandCells> SandWorld<C> {
+    fn empty(&self, position: &Position) -> bool {
+        self.cells.get(*position) == SandCell::Empty
+    }
+
+    fn sand_count(&self) -> usize {
+        self.cells.count(SandCell::Sand)
+    }
+
+    /// Bring a grain of sand to rest where it is, so the next grain starts from the cell before
+    fn settle(&mut self) -> SandOutcome {
+        self.falling = false;
+        let curr = self.path.pop().unwrap();
+        self.cells.set(curr, SandCell::Sand);
+        SandOutcome::AtRest
+    }
+
+    fn step(&mut self) -> SandOutcome {
+        self.step_with(|_| {})
+    }
+
+    /// Drop a grain of sand, calling `on_move` with the world each time the grain moves
+    fn step_with(&mut self, mut on_move: impl FnMut(&Self)) -> SandOutcome {
+        // Spawn location free?
+        if !self.empty(&self.sand_spawn) {
+            return SandOutcome::SourceBlocked;
+        }
+
+        // Move sand until at rest or in void, starting where the last grain came to rest from
+        let mut curr = *self.path.last().unwrap();
+        loop {
+            // Where will sand move?
+            let next_location = FALL_OFFSETS
+                .iter()
+                .map(|&offset| curr + offset)
+                .find(|pos| self.empty(pos));
+
+            // Is sand now at rest?
+            if let Some(next_location) = next_location {
+                curr = next_location;
+                self.path.push(curr);
+                self.falling = true;
+                on_move(self);
+            } else {
+                return self.settle();
+            }
+
+            // In void?
+            if let Some(floor_offset) = self.floor_offset {
+                // Hit floor?
+                if curr.y >= (self.lowest_rock + floor_offset) - 1 {
+                    return self.settle();
+                }
+            } else {
+                // In void?
+                if curr.y > self.lowest_rock + 2 {
+                    self.falling = false;
+                    break;
+                }
+            }
+        }
+
+        // Return result
+        SandOutcome::FellIntoVoid
+    }
+}
+
+impl SandCells for DenseCells {
+    fn with_bounds(min: Position, max: Position) -> Self {
+        let size = max - min;
+        Self {
+            grid: VecGrid::new(size.x as usize + 1, size.y as usize + 1),
+            origin: min,
+        }
+    }
+
+    fn get(&self, position: Position) -> SandCell {
+        let Position { x, y } = position - self.origin;
+        if x < 0 || y < 0 {
+            return SandCell::Empty;
+        }
+        self.grid
+            .get(x as usize, y as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn set(&mut self, position: Position, cell: SandCell) {
+        let Position { x, y } = position - self.origin;
+        *self
+            .grid
+            .get_mut(x as usize, y as usize)
+            .expect("Cell is outside of the world") = cell;
+    }
+
+    fn count(&self, cell: SandCell) -> usize {
+        self.grid
+            .iter_with_coords()
+            .filter(|&(_, _, &c)| c == cell)
+            .count()
+    }
+
+    fn filled_bounds(&self) -> Option<(Position, Position)> {
+        let filled = self
+            .grid
+            .iter_with_coords()
+            .filter(|&(_, _, &cell)| cell != SandCell::Empty)
+            .map(|(x, y, _)| Position::new(x as isize, y as isize) + self.origin)
+            .collect_vec();
+        let (min_x, max_x) = filled.iter().map(|pos| pos.x).minmax().into_option()?;
+        let (min_y, max_y) = filled.iter().map(|pos| pos.y).minmax().into_option()?;
+        Some((Position::new(min_x, min_y), Position::new(max_x, max_y)))
+    }
+}
+
+/// The original storage, kept to compare against the dense grid
+impl SandCells for SparseGrid<SandCell> {
+    fn with_bounds(_min: Position, _max: Position) -> Self {
+        Self::new()
+    }
+
+    fn get(&self, position: Position) -> SandCell {
+        SparseGrid::get(self, &position)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn set(&mut self, position: Position, cell: SandCell) {
+        self.insert(position, cell);
+    }
+
+    fn count(&self, cell: SandCell) -> usize {
+        self.iter().filter(|&(_, &c)| c == cell).count()
+    }
+
+    fn filled_bounds(&self) -> Option<(Position, Position)> {
+        self.bounds()
+    }
+}
+
+/// Pour sand until it stops coming to rest, drawing a frame as each grain settles and, with
+/// `descent`, as each grain falls
+fn pour_and_animate(world: &mut SandWorld, viz: &mut Visualizer, descent: bool) {
+    loop {
+        let outcome = if descent {
+            world.step_with(|world| viz.frame(world))
+        } else {
+            world.step()
+        };
+        if outcome != SandOutcome::AtRest {
+            break;
+        }
+        viz.frame(world);
+    }
+}
+
+/// Pour sand onto the rocks until it falls into the void or, with a floor, blocks the source,
+/// returning how many grains come to rest. Generic over how cells are stored for benchmarking.
+pub fn pour_sand<C: SandCells>(rock_sequences: &[RockLineSequence], floor: bool) -> usize {
+    let mut builder = SandWorldBuilder::new()
+        .rock_sequences(rock_sequences)
+        .sand_spawn(Position::new(500, 0));
+    if floor {
+        builder = builder.floor_offset(2);
+    }
+    let mut world = builder.build::<C>().unwrap();
+    while world.step() == SandOutcome::AtRest {}
+    world.sand_count()
+}
+
+/// The rock lines in the cave, and whether to draw each grain of sand falling as well as where
+/// it settles, if the program was given a `--descent` flag
+pub struct Cave {
+    pub rock_sequences: Vec<RockLineSequence>,
+    pub descent: bool,
+}
+
+pub struct Day14;
+
+common::solution_fns!(Day14);
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = Cave;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const FLAGS: &'static [Flag] = &[Flag::switch("descent")];
+
+    fn validate(input: &str) -> common::Result<()> {
+        parse::parse_lines::<RockLineSequence>(input.trim_end()).map(|_| ())
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        Cave {
+            rock_sequences: parse::parse_lines(input.trim_end()).unwrap(),
+            descent: false,
+        }
+    }
+
+    fn parse_with(input: &str, flags: &Flags) -> Result<Self::Input, String> {
+        Ok(Cave {
+            descent: flags.is_set("descent"),
+            ..Self::parse(input)
+        })
+    }
+
+    fn part1(cave: &Self::Input) -> Self::Part1 {
+        let mut world = SandWorldBuilder::new()
+            .rock_sequences(&cave.rock_sequences)
+            .sand_spawn(Position::new(500, 0))
+            .build::<DenseCells>()
+            .unwrap();
+        pour_and_animate(&mut world, &mut Visualizer::from_args(), cave.descent);
+        world.sand_count()
+    }
+
+    fn part2(cave: &Self::Input) -> Self::Part2 {
+        let mut world = SandWorldBuilder::new()
+            .rock_sequences(&cave.rock_sequences)
+            .sand_spawn(Position::new(500, 0))
+            .floor_offset(2)
+            .build::<DenseCells>()
+            .unwrap();
+        pour_and_animate(
+            &mut world,
+            &mut Visualizer::from_args().every(20),
+            cave.descent,
+        );
+        world.sand_count()
+    }
+}
+
+/* Parsing */
+impl FromStr for RockLineSequence {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let points = s
+            .split(" -> ")
+            .map(|pair| {
+                let (x, y) = pair
+                    .split_once(',')
+                    .ok_or_else(|| AocError::parse_at(s, pair, "Expected a point like x,y"))?;
+                Ok(Position::new(x.parse()?, y.parse()?))
+            })
+            .collect::<Result<_, AocError>>()?;
+        Ok(Self { points })
+    }
+}
+
+/* Debug Impls */
+
+impl<C: SandCells> std::fmt::Display for SandWorld<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some((min, max)) = self.cells.filled_bounds() else {
+            return Ok(());
+        };
+        // Include the trail of sand, which starts above the rocks and can fall past them
+        let (min, max) = self.path.iter().fold((min, max), |(min, max), pos| {
+            (
+                Position::new(min.x.min(pos.x), min.y.min(pos.y)),
+                Position::new(max.x.max(pos.x), max.y.max(pos.y)),
+            )
+        });
+        let trail: FastSet<Position> = self.path.iter().copied().collect();
+        let grain = self.path.last().filter(|_| self.falling);
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let position = Position::new(x, y);
+                let c = match self.cells.get(position) {
+                    SandCell::Rock => "\u{2592}".white(),
+                    SandCell::Sand => "o".yellow(),
+                    SandCell::Empty if grain == Some(&position) => "o".bright_red(),
+                    SandCell::Empty if trail.contains(&position) => "\u{b7}".yellow().dimmed(),
+                    SandCell::Empty => " ".white(),
+                };
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_world {
+    use super::*;
+    use common::sample_test;
+
+    sample_test!(test_sim_sand, Day14::part1, file "sample.txt", 24);
+    sample_test!(test_sim_sand_with_floor, Day14::part2, file "sample.txt", 93);
+
+    #[test]
+    fn test_resume_from_path() {
+        let rock_sequences = Day14::parse(include_str!("../sample.txt")).rock_sequences;
+        let mut world = SandWorldBuilder::new()
+            .rock_sequences(&rock_sequences)
+            .sand_spawn(Position::new(500, 0))
+            .build::<DenseCells>()
+            .unwrap();
+        assert_eq!(world.lowest_rock, 9);
+
+        // The first grain rests on the rock shelf, so the next starts just above it
+        assert_eq!(world.step(), SandOutcome::AtRest);
+        assert_eq!(world.cells.get(Position::new(500, 8)), SandCell::Sand);
+        assert_eq!(world.path.last(), Some(&Position::new(500, 7)));
+        assert_eq!(world.path.len(), 8);
+    }
+
+    #[test]
+    fn test_descent() {
+        let rock_sequences = Day14::parse(include_str!("../sample.txt")).rock_sequences;
+        let mut world = SandWorldBuilder::new()
+            .rock_sequences(&rock_sequences)
+            .sand_spawn(Position::new(500, 0))
+            .build::<DenseCells>()
+            .unwrap();
+
+        // The first grain falls straight down onto the shelf, drawn as it goes
+        let mut frames = Vec::new();
+        world.step_with(|world| frames.push(world.to_string()));
+        assert_eq!(frames.len(), 8);
+        assert!(frames.iter().all(|frame| frame.contains('o')));
+        assert!(!world.falling);
+        assert_eq!(world.sand_count(), 1);
+    }
+
+    #[test]
+    fn test_descent_flag() {
+        let parse = |args: &[&str]| {
+            let args = args.iter().map(|arg| arg.to_string());
+            let flags = common::cli::parse_from(args, Day14::FLAGS).unwrap().flags;
+            Day14::parse_with(include_str!("../sample.txt"), &flags).unwrap()
+        };
+        assert!(!parse(&[]).descent);
+        let cave = parse(&["--animate", "--descent", "--record", "sand.gif"]);
+        assert!(cave.descent);
+        assert_eq!(cave.rock_sequences.len(), 2);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(Day14::validate(include_str!("../sample.txt")), Ok(()));
+        assert_eq!(
+            Day14::validate("498,4 -> 498,6\n503,4 -> 502")
+                .unwrap_err()
+                .to_string(),
+            "Parse error at 2:10: Expected a point like x,y"
+        );
+        assert!(Day14::validate("498,4 -> 498,x").is_err());
+    }
+
+    #[test]
+    fn test_sparse_matches_dense() {
+        let rock_sequences = Day14::parse(include_str!("../sample.txt")).rock_sequences;
+        for floor in [false, true] {
+            assert_eq!(
+                pour_sand::<SparseGrid<SandCell>>(&rock_sequences, floor),
+                pour_sand::<DenseCells>(&rock_sequences, floor)
+            );
+        }
+    }
+}
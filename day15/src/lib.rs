@@ -0,0 +1,443 @@
+use std::{ops::RangeInclusive, path::PathBuf, str::FromStr};
+
+use common::{
+    cli::{Flag, Flags},
+    par, parse, progress,
+    viz::Svg,
+    AocError, Intervals, Point, Set, Solution, Span,
+};
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    sequence::{pair, preceded},
+    IResult,
+};
+const PT1_TARGET_ROW: isize = 2_000_000;
+const PT2_TARGET_RANGE: Span<isize> = Span::new(0, 4_000_000);
+
+type Position = Point<isize>;
+
+/// Where and how to look for beacons. The area is much smaller for the sample than the real
+/// puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    /// The row to count beaconless positions on in part 1
+    pub row: isize,
+    /// The range of x and y to search for the distress beacon in part 2
+    pub bounds: Span<isize>,
+    /// Whether to find the distress beacon by scanning every row
+    pub sweep: bool,
+    /// Where to draw the sensors' ranges and the distress beacon
+    pub svg: Option<PathBuf>,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            row: PT1_TARGET_ROW,
+            bounds: PT2_TARGET_RANGE,
+            sweep: false,
+            svg: None,
+        }
+    }
+}
+
+impl Params {
+    /// The sample's row 10 and bounds of 0 to 20
+    pub fn sample() -> Self {
+        Self {
+            row: 10,
+            bounds: Span::new(0, 20),
+            ..Self::default()
+        }
+    }
+
+    /// The real puzzle's params, or the sample's with `--sample`, overridden by `--row N` and
+    /// `--max N` (searching 0 to `N`). `--sweep` scans every row and `--svg PATH` draws the
+    /// distress beacon.
+    pub fn from_flags(flags: &Flags) -> Result<Self, String> {
+        let mut params = if flags.sample {
+            Self::sample()
+        } else {
+            Self::default()
+        };
+        if let Some(row) = flags.parse("row")? {
+            params.row = row;
+        }
+        match flags.parse("max")? {
+            Some(max) if max < 0 => return Err("--max can't be negative".to_owned()),
+            Some(max) => params.bounds = Span::new(0, max),
+            None => {}
+        }
+        params.sweep = flags.is_set("sweep");
+        params.svg = flags.value("svg").map(PathBuf::from);
+        Ok(params)
+    }
+}
+
+pub struct SensorReport(Position, Position);
+
+impl SensorReport {
+    fn new(sensor: Position, beacon: Position) -> Self {
+        Self(sensor, beacon)
+    }
+
+    /// The manhattan dist between the beacon and sensor of this report
+    fn distance(&self) -> usize {
+        self.0.manhattan_dist(&self.1) as usize
+    }
+
+    /// Whether a given other point is in range of this sensor
+    /// i.e whether its existence would cause this report to be invalid
+    fn in_influence(&self, position: &Position) -> bool {
+        self.0.manhattan_dist(position) as usize <= self.distance()
+    }
+
+    /// Get range of positions covered by this report on a single row, if it reaches that row.
+    /// i.e the range of positions where a beacon cannot be, as determined by this report
+    fn compute_influence_on_row(&self, row: isize) -> Option<Span<isize>> {
+        // Get our properties
+        let distance = self.distance();
+        let (my_x, my_y) = (self.0.x, self.0.y);
+
+        // Determine radius of influence on this row
+        let y_diff = row.abs_diff(my_y);
+        let radius = distance.checked_sub(y_diff)? as isize;
+
+        Some(Span::new(-radius + my_x, radius + my_x))
+    }
+}
+
+/// The combined influence of every report on a single row
+fn influence_on_row(reports: &[SensorReport], row: isize) -> Intervals {
+    reports
+        .iter()
+        .flat_map(|report| report.compute_influence_on_row(row))
+        .map(RangeInclusive::from)
+        .collect()
+}
+
+/// Count the positions on a row where a beacon cannot be, i.e those some sensor can see apart
+/// from the beacons already there. Beacons seen by several sensors are only taken away once,
+/// and squares with a sensor still count as no beacon can be there.
+fn count_beaconless_positions(reports: &[SensorReport], row: isize) -> usize {
+    let influence = influence_on_row(reports, row);
+    let beacons_on_row = reports
+        .iter()
+        .map(|report| &report.1)
+        .filter(|beacon| beacon.y == row && influence.contains(beacon.x))
+        .collect::<Set<_>>();
+    influence.total_len() - beacons_on_row.len()
+}
+
+/// Find the only position within the bounds that no sensor can see. Unless it's in a corner of
+/// the bounds, it must be just out of range of sensors on either side, so it's somewhere the
+/// diagonal edges just outside two sensors' ranges cross, or on an edge of the bounds where one
+/// of those diagonals meets it.
+fn find_distress_beacon(reports: &[SensorReport], bounds: Span<isize>) -> Option<Position> {
+    // The edges lie along lines where either x + y or x - y is constant
+    let mut descending = Set::default();
+    let mut ascending = Set::default();
+    for report in reports {
+        let (x, y) = (report.0.x, report.0.y);
+        let d = report.distance() as isize + 1;
+        descending.extend([x + y - d, x + y + d]);
+        ascending.extend([x - y - d, x - y + d]);
+    }
+
+    // Only crossings on a whole position count
+    let crossings = descending
+        .iter()
+        .cartesian_product(&ascending)
+        .filter(|&(sum, diff)| (sum + diff) % 2 == 0)
+        .map(|(sum, diff)| Position::new((sum + diff) / 2, (sum - diff) / 2));
+    let (min, max) = (*bounds.start(), *bounds.end());
+    let corners = [(min, min), (max, min), (min, max), (max, max)].map(Position::from);
+    // Along an edge of the bounds, a single diagonal on either side is enough to hem it in
+    let on_edges = [min, max].into_iter().flat_map(|edge| {
+        let descending = descending.iter().flat_map(move |sum| {
+            [
+                Position::new(edge, sum - edge),
+                Position::new(sum - edge, edge),
+            ]
+        });
+        let ascending = ascending.iter().flat_map(move |diff| {
+            [
+                Position::new(edge, edge - diff),
+                Position::new(diff + edge, edge),
+            ]
+        });
+        descending.chain(ascending)
+    });
+    crossings
+        .chain(corners)
+        .chain(on_edges)
+        .filter(|p| bounds.contains(&p.x) && bounds.contains(&p.y))
+        .find(|p| !reports.iter().any(|report| report.in_influence(p)))
+}
+
+/// Find the only position within the bounds that no sensor can see, scanning rows in parallel.
+/// Much slower than `find_distress_beacon`, but doesn't rely on the beacon being hemmed in.
+/// Each row only needs its merged sensor intervals, so with the `parallel` feature the rows are
+/// split across threads, which all stop once any of them finds the gap.
+fn sweep_for_distress_beacon(reports: &[SensorReport], bounds: Span<isize>) -> Option<Position> {
+    let progress = progress::bar(bounds.len() as u64);
+    let rows = RangeInclusive::from(bounds);
+    par::find_map_any(rows.clone(), |y| {
+        progress.inc();
+        let gaps = influence_on_row(reports, y).gaps(rows.clone());
+        gaps.first().map(|gap| Position::new(*gap.start(), y))
+    })
+}
+
+/// Draw the area each sensor can see, every reported beacon and the distress beacon
+fn draw_reports(reports: &[SensorReport], distress_beacon: Position) -> Svg {
+    let mut svg = Svg::new();
+    let radius = reports.iter().map(|r| r.distance()).max().unwrap_or(1) as f64 / 50.0;
+    for report in reports {
+        let (x, y) = (report.0.x as f64, report.0.y as f64);
+        let d = report.distance() as f64;
+        svg.polygon(
+            [(x, y - d), (x + d, y), (x, y + d), (x - d, y)],
+            "rgba(137, 180, 250, 0.2)",
+            "#89b4fa",
+        );
+        svg.circle((report.1.x as f64, report.1.y as f64), radius, "#f9e2af");
+    }
+    svg.circle(
+        (distress_beacon.x as f64, distress_beacon.y as f64),
+        radius * 2.0,
+        "#f38ba8",
+    );
+    svg
+}
+
+/// Count the positions on the params' row where a beacon cannot be
+pub fn part1_with(reports: &[SensorReport], params: &Params) -> usize {
+    count_beaconless_positions(reports, params.row)
+}
+
+/// Find the tuning frequency of the distress beacon within the params' bounds
+pub fn part2_with(reports: &[SensorReport], params: &Params) -> isize {
+    let bounds = params.bounds.clone();
+    let pos = if params.sweep {
+        sweep_for_distress_beacon(reports, bounds)
+    } else {
+        find_distress_beacon(reports, bounds)
+    }
+    .expect("Couldn't find the distress beacon");
+    if let Some(path) = &params.svg {
+        draw_reports(reports, pos)
+            .save(path)
+            .unwrap_or_else(|err| eprintln!("Couldn't save {}: {}", path.display(), err));
+    }
+    pos.x * 4_000_000 + pos.y
+}
+
+pub struct Day15;
+
+common::solution_fns!(Day15);
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Input = (Vec<SensorReport>, Params);
+    type Part1 = usize;
+    type Part2 = isize;
+
+    const FLAGS: &'static [Flag] = &[
+        Flag::with_value("row", "N"),
+        Flag::with_value("max", "N"),
+        Flag::switch("sweep"),
+    ];
+
+    fn validate(input: &str) -> common::Result<()> {
+        parse::parse_lines::<SensorReport>(input.trim_end()).map(|_| ())
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        let reports = parse::parse_lines(input.trim_end()).unwrap();
+        (reports, Params::default())
+    }
+
+    fn parse_with(input: &str, flags: &Flags) -> Result<Self::Input, String> {
+        let params = Params::from_flags(flags)?;
+        Ok((Self::parse(input).0, params))
+    }
+
+    fn part1((reports, params): &Self::Input) -> Self::Part1 {
+        // Compute influence on specific line
+        part1_with(reports, params)
+    }
+
+    fn part2((reports, params): &Self::Input) -> Self::Part2 {
+        // Find the distress beacon
+        part2_with(reports, params)
+    }
+}
+
+#[cfg(test)]
+mod test_solution {
+    use super::*;
+    use common::sample_test;
+
+    sample_test!(
+        test_row_influence_computation,
+        |input| part1_with(&Day15::parse(input).0, &Params::sample()),
+        file "sample.txt",
+        26
+    );
+
+    sample_test!(
+        test_tuning_frequency,
+        |input| part2_with(&Day15::parse(input).0, &Params::sample()),
+        file "sample.txt",
+        56000011
+    );
+
+    #[test]
+    fn test_influence_on_sample_row() {
+        let reports = Day15::parse(include_str!("../sample.txt")).0;
+        let influence = influence_on_row(&reports, 10);
+        assert_eq!(influence.iter().cloned().collect_vec(), vec![-2..=24]);
+
+        // The beacon at 2,10 is the closest to three sensors, but only takes one position away
+        let beacons = reports
+            .iter()
+            .filter(|r| r.1 == Position::new(2, 10))
+            .count();
+        assert_eq!(beacons, 3);
+        assert_eq!(count_beaconless_positions(&reports, 10), 27 - 1);
+    }
+
+    #[test]
+    fn test_params_from_flags() {
+        let params = |args: &[&str]| {
+            let args = args.iter().map(|arg| arg.to_string());
+            Params::from_flags(&common::cli::parse_from(args, Day15::FLAGS)?.flags)
+        };
+        assert_eq!(params(&[]), Ok(Params::default()));
+        assert_eq!(params(&["--sample"]), Ok(Params::sample()));
+        let custom = params(&["--row", "3", "--max=9", "--sweep", "--svg", "out.svg"]).unwrap();
+        assert_eq!(custom.row, 3);
+        assert_eq!(custom.bounds, Span::new(0, 9));
+        assert!(custom.sweep);
+        assert_eq!(custom.svg, Some(PathBuf::from("out.svg")));
+        assert_eq!(
+            params(&["--row=ten"]),
+            Err("Invalid value ten for --row".to_owned())
+        );
+        assert_eq!(
+            params(&["--max", "-1"]),
+            Err("--max can't be negative".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_count_beaconless_positions() {
+        // Two sensors on the row sharing a beacon between them
+        let reports = [
+            SensorReport::new(Position::new(0, 0), Position::new(2, 0)),
+            SensorReport::new(Position::new(4, 0), Position::new(2, 0)),
+        ];
+        assert_eq!(count_beaconless_positions(&reports, 0), 9 - 1);
+        assert_eq!(count_beaconless_positions(&reports, 1), 3 + 3);
+        assert_eq!(count_beaconless_positions(&reports, 100), 0);
+    }
+
+    sample_test!(
+        test_find_distress_beacon,
+        |input| find_distress_beacon(&Day15::parse(input).0, Span::new(0, 20)),
+        file "sample.txt",
+        Some(Position::new(14, 11))
+    );
+
+    sample_test!(
+        test_sweep_for_distress_beacon,
+        |input| sweep_for_distress_beacon(&Day15::parse(input).0, Span::new(0, 20)),
+        file "sample.txt",
+        Some(Position::new(14, 11))
+    );
+
+    #[test]
+    fn test_distress_beacon_on_edge() {
+        // Three sensors covering everything but a spot on the left edge, which no two sensors'
+        // edges cross at
+        let reports = [
+            SensorReport::new(Position::new(-3, -2), Position::new(3, -2)),
+            SensorReport::new(Position::new(1, -3), Position::new(5, -3)),
+            SensorReport::new(Position::new(4, 4), Position::new(9, 4)),
+        ];
+        assert_eq!(
+            find_distress_beacon(&reports, Span::new(0, 4)),
+            Some(Position::new(0, 2))
+        );
+        assert_eq!(
+            sweep_for_distress_beacon(&reports, Span::new(0, 4)),
+            Some(Position::new(0, 2))
+        );
+    }
+
+    #[test]
+    fn test_distress_beacon_in_corner() {
+        // A single sensor covering everything but the bottom right corner
+        let reports = [SensorReport::new(Position::new(0, 0), Position::new(0, 7))];
+        assert_eq!(
+            find_distress_beacon(&reports, Span::new(0, 4)),
+            Some(Position::new(4, 4))
+        );
+        assert_eq!(
+            sweep_for_distress_beacon(&reports, Span::new(0, 4)),
+            Some(Position::new(4, 4))
+        );
+    }
+}
+
+/* Parsing */
+
+impl FromStr for SensorReport {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse::parse_all(
+            pair(
+                preceded(tag("Sensor at "), parse_labeled_position),
+                preceded(tag(": closest beacon is at "), parse_labeled_position),
+            ),
+            s,
+        )
+        .map(|(sensor, beacon)| SensorReport::new(sensor, beacon))
+    }
+}
+
+fn parse_labeled_position(s: &str) -> IResult<&str, Position> {
+    parse::point("x=", ", y=")(s)
+}
+
+#[cfg(test)]
+mod test_parsing {
+    use super::*;
+
+    #[test]
+    fn test_parse_report() {
+        let report = SensorReport::from_str(
+            "Sensor at x=3056788, y=2626224: closest beacon is at x=3355914, y=2862466",
+        )
+        .unwrap();
+        assert_eq!(report.distance(), 535368);
+    }
+
+    #[test]
+    fn test_parse_position() {
+        let (_, p) = parse_labeled_position("x=3992558, y=1933059").unwrap();
+        assert_eq!(p.x, 3992558);
+        assert_eq!(p.y, 1933059);
+    }
+}
+
+/* Debug Impls */
+
+impl std::fmt::Debug for SensorReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Sensor{:?} Closest Beacon{:?}", self.0, self.1)
+    }
+}
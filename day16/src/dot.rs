@@ -0,0 +1,101 @@
+//! Drawing the valve network as a Graphviz graph, e.g `dot -Tsvg day16.dot -o day16.svg`
+
+use std::fmt::Write;
+
+use common::{Map, Set};
+
+use crate::{PlanStep, ValveAction, ValveID, ValveNetwork};
+
+/// Colour of the tunnels walked and valves opened by each actor in a plan
+const ACTOR_COLORS: [&str; 3] = ["#d20f39", "#1e66f5", "#40a02b"];
+
+impl ValveNetwork {
+    /// A Graphviz graph of the network with each valve labelled by its flow rate, highlighting
+    /// the tunnels each actor in a plan walks along and the valves they open
+    pub fn to_dot<S: PlanStep>(&self, plan: &[S]) -> String {
+        // Follow the plan, noting who went where
+        let mut walked: Map<(ValveID, ValveID), Vec<usize>> = Map::default();
+        let mut opened: Map<ValveID, usize> = Map::default();
+        let mut positions: Vec<ValveID> = Vec::new();
+        for step in plan {
+            let actions = step.actions();
+            positions.resize(actions.len().max(positions.len()), self.start_position);
+            for (actor, action) in actions.into_iter().enumerate() {
+                let position = &mut positions[actor];
+                match action {
+                    ValveAction::MoveTo(valve) => {
+                        let tunnel = ((*position).min(valve), (*position).max(valve));
+                        let actors = walked.entry(tunnel).or_default();
+                        if !actors.contains(&actor) {
+                            actors.push(actor);
+                        }
+                        *position = valve;
+                    }
+                    ValveAction::Open => {
+                        opened.entry(*position).or_insert(actor);
+                    }
+                    ValveAction::Wait => {}
+                }
+            }
+        }
+        let color = |actor: usize| ACTOR_COLORS[actor % ACTOR_COLORS.len()];
+
+        let mut dot = String::from("graph valves {\n");
+        for (id, name) in self.names.iter().enumerate() {
+            let valve = ValveID(id);
+            let rate = self.flow_rates[&valve];
+            write!(dot, "    {} [label=\"{}\\n{}\"", name, name, rate).unwrap();
+            if valve == self.start_position {
+                dot.push_str(", shape=doublecircle");
+            }
+            if rate == 0 {
+                dot.push_str(", color=gray, fontcolor=gray");
+            }
+            if let Some(&actor) = opened.get(&valve) {
+                write!(dot, ", style=filled, fillcolor=\"{}\"", color(actor)).unwrap();
+            }
+            dot.push_str("];\n");
+        }
+
+        // Each tunnel goes both ways, so only draw it once
+        let mut drawn = Set::default();
+        for (id, name) in self.names.iter().enumerate() {
+            for &to in &self.edges[&ValveID(id)] {
+                let tunnel = (ValveID(id).min(to), ValveID(id).max(to));
+                if !drawn.insert(tunnel) {
+                    continue;
+                }
+                write!(dot, "    {} -- {}", name, self.name(to)).unwrap();
+                if let Some(actors) = walked.get(&tunnel) {
+                    let colors: Vec<&str> = actors.iter().map(|&actor| color(actor)).collect();
+                    write!(dot, " [color=\"{}\", penwidth=3]", colors.join(":")).unwrap();
+                }
+                dot.push_str(";\n");
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Day16, ValveAction::*};
+    use common::Solution;
+
+    #[test]
+    fn test_to_dot() {
+        let network = Day16::parse(include_str!("../sample.txt")).network;
+        let valve = |name| MoveTo(network.valve(name).unwrap());
+        let dot = network.to_dot(&[(valve("DD"), valve("BB")), (Open, Open)]);
+
+        assert!(dot.starts_with("graph valves {\n"));
+        assert!(dot.contains("    AA [label=\"AA\\n0\", shape=doublecircle, color=gray"));
+        assert!(dot.contains("    DD [label=\"DD\\n20\", style=filled, fillcolor=\"#d20f39\"];"));
+        assert!(dot.contains("    BB [label=\"BB\\n13\", style=filled, fillcolor=\"#1e66f5\"];"));
+        assert!(dot.contains("    AA -- DD [color=\"#d20f39\", penwidth=3];"));
+        assert!(dot.contains("    AA -- BB [color=\"#1e66f5\", penwidth=3];"));
+        assert!(dot.contains("    AA -- II;"));
+        assert!(!dot.contains("BB -- AA"));
+    }
+}
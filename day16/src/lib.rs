@@ -0,0 +1,481 @@
+use std::{hash::Hash, path::PathBuf};
+
+use common::{
+    cli::{Flag, Flags},
+    parse, search,
+    tracing::debug,
+    viz::numbered_path,
+    AocError, BitSet, Map, Solution,
+};
+use itertools::Itertools;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete,
+    sequence::{preceded, tuple},
+};
+
+#[derive(Default, Hash, Eq, PartialEq, Clone, Debug)]
+pub struct OpenValves(BitSet<1>);
+
+impl OpenValves {
+    fn open(&self, id: ValveID) -> Self {
+        Self(self.0.with(id.0))
+    }
+
+    fn is_open(&self, id: ValveID) -> bool {
+        self.0.contains(id.0)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = ValveID> + '_ {
+        self.0.iter().map(ValveID)
+    }
+}
+
+#[derive(Hash, Eq, PartialEq, PartialOrd, Ord, Clone, Copy)]
+pub struct ValveID(usize);
+
+impl From<usize> for ValveID {
+    fn from(id: usize) -> Self {
+        Self(id)
+    }
+}
+
+pub mod dot;
+
+mod simulate;
+pub use simulate::{PlanError, PlanStep};
+
+mod tunnels;
+use tunnels::Tunnels;
+
+#[derive(Debug)]
+pub struct ValveNetwork {
+    /// The name of each valve, by `ValveID`
+    names: Vec<String>,
+    start_position: ValveID,
+    flow_rates: Map<ValveID, usize>,
+    edges: Map<ValveID, Vec<ValveID>>,
+}
+
+impl ValveNetwork {
+    /// Look up a valve by its name, e.g `AA`
+    pub fn valve(&self, name: &str) -> Option<ValveID> {
+        self.names.iter().position(|n| n == name).map(ValveID)
+    }
+
+    pub fn name(&self, valve: ValveID) -> &str {
+        &self.names[valve.0]
+    }
+
+    /// A shortest walk between two valves, including both of them
+    fn path(&self, from: ValveID, to: ValveID) -> Vec<ValveID> {
+        search::bfs(
+            from,
+            |valve| self.edges[valve].clone(),
+            |&valve| valve == to,
+        )
+        .expect("Valves aren't connected")
+    }
+
+    /// Walk from the start to open each valve in turn
+    fn actions_to_open(&self, order: &[ValveID]) -> Vec<ValveAction> {
+        let mut actions = Vec::new();
+        let mut position = self.start_position;
+        for &valve in order {
+            let path = self.path(position, valve);
+            actions.extend(path.into_iter().skip(1).map(ValveAction::MoveTo));
+            actions.push(ValveAction::Open);
+            position = valve;
+        }
+        actions
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Copy)]
+pub enum ValveAction {
+    MoveTo(ValveID),
+    Open,
+    /// Stay put for a minute
+    Wait,
+}
+
+/// Plans for any number of agents opening valves at once, e.g `NetworkPlan::solve(&network, 3, 26)`
+mod plan {
+    use super::*;
+
+    /// What every agent does in a single minute
+    type SimultaneousAction = Vec<ValveAction>;
+
+    #[derive(Clone)]
+    pub struct NetworkPlan<'a> {
+        network: &'a ValveNetwork,
+        actions: Vec<SimultaneousAction>,
+    }
+
+    impl<'a> NetworkPlan<'a> {
+        pub fn total_pressure_released(&self, minutes: usize) -> Result<usize, &'static str> {
+            // Init released amount
+            let mut released = 0;
+
+            // Init graph state
+            let mut open_valves = OpenValves::default();
+            let mut positions: Vec<ValveID> = Vec::new();
+
+            for minute in 0..minutes.saturating_sub(1) {
+                // Perform each agent's action
+                if let Some(actions) = self.actions.get(minute) {
+                    positions.resize(
+                        actions.len().max(positions.len()),
+                        self.network.start_position,
+                    );
+                    for (position, action) in positions.iter_mut().zip(actions) {
+                        match action {
+                            ValveAction::MoveTo(valve_id) => {
+                                if !self.network.edges[position].contains(valve_id) {
+                                    return Err("Cannot move to valve from current valve");
+                                }
+                                *position = *valve_id;
+                            }
+                            ValveAction::Open => {
+                                open_valves = open_valves.open(*position);
+                            }
+                            ValveAction::Wait => {}
+                        }
+                    }
+                }
+
+                // Add to flow rate
+                released += open_valves
+                    .iter()
+                    .map(|valve_id| self.network.flow_rates[&valve_id])
+                    .sum::<usize>();
+            }
+
+            Ok(released)
+        }
+
+        /// Find the sequences of actions for some agents working at once which maximise the flow
+        /// rate, by splitting the valves between them then finding the best order for each
+        pub fn solve(network: &ValveNetwork, agents: usize, minutes: usize) -> NetworkPlan<'_> {
+            let tunnels = Tunnels::new(network);
+            let (released, split) = tunnels.best_split(network.start_position, minutes, agents);
+            debug!(released, agents, "Found best split of valves");
+
+            // Each only opens its own valves
+            let mut agent_actions: Vec<Vec<ValveAction>> = split
+                .into_iter()
+                .map(|opened| {
+                    let (_, order) = tunnels.best_order(network.start_position, minutes, !opened);
+                    network.actions_to_open(&order)
+                })
+                .collect();
+
+            // Whoever finishes first waits for the others
+            let len = agent_actions.iter().map(Vec::len).max().unwrap_or(0);
+            for actions in &mut agent_actions {
+                actions.resize(len, ValveAction::Wait);
+            }
+
+            NetworkPlan {
+                network,
+                actions: (0..len)
+                    .map(|minute| {
+                        agent_actions
+                            .iter()
+                            .map(|actions| actions[minute])
+                            .collect()
+                    })
+                    .collect(),
+            }
+        }
+
+        pub fn to_dot(&self) -> String {
+            self.network.to_dot(&self.actions)
+        }
+    }
+
+    impl<'a> std::fmt::Debug for NetworkPlan<'a> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.actions)
+        }
+    }
+
+    #[cfg(test)]
+    mod test_with_sample {
+        use super::*;
+
+        const SAMPLE_INPUT: &str = include_str!("../sample.txt");
+
+        macro_rules! action {
+            (-> $c:expr) => {{
+                let num = ((($c).to_uppercase().chars().next().unwrap() as u8) - b'A') as usize;
+                ValveAction::MoveTo(num.into())
+            }};
+            (*) => {
+                ValveAction::Open
+            };
+        }
+
+        fn get_sample_plan() -> Vec<ValveAction> {
+            vec![
+                ValveAction::MoveTo(3.into()),
+                ValveAction::Open,
+                ValveAction::MoveTo(2.into()),
+                ValveAction::MoveTo(1.into()),
+                ValveAction::Open,
+                ValveAction::MoveTo(0.into()),
+                ValveAction::MoveTo(8.into()),
+                ValveAction::MoveTo(9.into()),
+                ValveAction::Open,
+                ValveAction::MoveTo(8.into()),
+                ValveAction::MoveTo(0.into()),
+                ValveAction::MoveTo(3.into()),
+                ValveAction::MoveTo(4.into()),
+                ValveAction::MoveTo(5.into()),
+                ValveAction::MoveTo(6.into()),
+                ValveAction::MoveTo(7.into()),
+                ValveAction::Open,
+                ValveAction::MoveTo(6.into()),
+                ValveAction::MoveTo(5.into()),
+                ValveAction::MoveTo(4.into()),
+                ValveAction::Open,
+                ValveAction::MoveTo(3.into()),
+                ValveAction::MoveTo(2.into()),
+                ValveAction::Open,
+            ]
+        }
+
+        fn get_sample_pair_plan() -> Vec<SimultaneousAction> {
+            vec![
+                vec![action!(-> "II"), action!(-> "DD")],
+                vec![action!(-> "JJ"), action!(*)],
+                vec![action!(*), action!(-> "EE")],
+                vec![action!(-> "II"), action!(-> "FF")],
+                vec![action!(-> "AA"), action!(-> "GG")],
+                vec![action!(-> "BB"), action!(-> "HH")],
+                vec![action!(*), action!(*)],
+                vec![action!(-> "CC"), action!(-> "GG")],
+                vec![action!(*), action!(-> "FF")],
+                vec![action!(*), action!(-> "EE")],
+                vec![action!(*), action!(*)],
+            ]
+        }
+
+        #[test]
+        fn test_parse_sample() {
+            let network = SAMPLE_INPUT.parse::<ValveNetwork>();
+            assert!(network.is_ok(), "Failed to parse sample network");
+        }
+
+        #[test]
+        fn test_flow_rate_calc() {
+            let network = SAMPLE_INPUT.parse::<ValveNetwork>().unwrap();
+            let plan = NetworkPlan {
+                network: &network,
+                actions: get_sample_plan()
+                    .into_iter()
+                    .map(|action| vec![action])
+                    .collect(),
+            };
+            assert_eq!(plan.total_pressure_released(30), Ok(1651));
+
+            let plan = NetworkPlan {
+                network: &network,
+                actions: get_sample_pair_plan(),
+            };
+            assert_eq!(plan.total_pressure_released(26), Ok(1707));
+        }
+
+        #[test]
+        fn test_solve_sample_alone() {
+            let network = SAMPLE_INPUT.parse::<ValveNetwork>().unwrap();
+            let plan = NetworkPlan::solve(&network, 1, 30);
+            dbg!(&plan);
+            assert_eq!(plan.total_pressure_released(30), Ok(1651));
+            assert_eq!(network.simulate(&plan.actions, 30), Ok(1651));
+            assert_eq!(
+                plan.actions.into_iter().take(24).flatten().collect_vec(),
+                get_sample_plan()
+            )
+        }
+
+        #[test]
+        fn test_solve_sample_with_elephant() {
+            let network = SAMPLE_INPUT.parse::<ValveNetwork>().unwrap();
+            let plan = NetworkPlan::solve(&network, 2, 26);
+            dbg!(&plan);
+            assert_eq!(plan.total_pressure_released(26), Ok(1707));
+            assert_eq!(network.simulate(&plan.actions, 26), Ok(1707));
+            let (released, split) =
+                Tunnels::new(&network).best_split(network.start_position, 26, 2);
+            assert_eq!(released, 1707);
+            assert_eq!(split[0] & split[1], 0);
+        }
+
+        #[test]
+        fn test_solve_sample_more_agents() {
+            let network = SAMPLE_INPUT.parse::<ValveNetwork>().unwrap();
+
+            // A third agent can only help, and with enough of them every valve gets opened
+            let three = NetworkPlan::solve(&network, 3, 26);
+            let released = network.simulate(&three.actions, 26).unwrap();
+            assert!(released >= 1707);
+            assert_eq!(three.total_pressure_released(26), Ok(released));
+            assert!(three.actions.iter().all(|actions| actions.len() == 3));
+
+            // No time to do anything
+            let plan = NetworkPlan::solve(&network, 2, 1);
+            assert_eq!(network.simulate(&plan.actions, 1), Ok(0));
+        }
+
+        #[test]
+        fn test_solve_without_time() {
+            let network = SAMPLE_INPUT.parse::<ValveNetwork>().unwrap();
+            for minutes in [0, 1] {
+                for agents in [1, 2] {
+                    let plan = NetworkPlan::solve(&network, agents, minutes);
+                    assert_eq!(plan.total_pressure_released(minutes), Ok(0));
+                }
+            }
+        }
+    }
+}
+
+pub use plan::NetworkPlan;
+
+/// The valve network, along with where to save drawings of each part's plan if the program was
+/// given a `--dot PATH` flag
+pub struct Volcano {
+    pub network: ValveNetwork,
+    pub dot: Option<PathBuf>,
+}
+
+pub struct Day16;
+
+common::solution_fns!(Day16);
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Input = Volcano;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const FLAGS: &'static [Flag] = &[Flag::with_value("dot", "PATH")];
+
+    fn validate(input: &str) -> common::Result<()> {
+        input.parse::<ValveNetwork>().map(|_| ())
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        Volcano {
+            network: input.parse().unwrap(),
+            dot: None,
+        }
+    }
+
+    fn parse_with(input: &str, flags: &Flags) -> Result<Self::Input, String> {
+        Ok(Volcano {
+            dot: flags.value("dot").map(PathBuf::from),
+            ..Self::parse(input)
+        })
+    }
+
+    fn part1(volcano: &Self::Input) -> Self::Part1 {
+        let plan = plan::NetworkPlan::solve(&volcano.network, 1, 30);
+        save_dot(volcano, 0, || plan.to_dot());
+        plan.total_pressure_released(30).unwrap()
+    }
+
+    fn part2(volcano: &Self::Input) -> Self::Part2 {
+        let plan = plan::NetworkPlan::solve(&volcano.network, 2, 26);
+        save_dot(volcano, 1, || plan.to_dot());
+        plan.total_pressure_released(26).unwrap()
+    }
+}
+
+/// Save a drawing of a part's plan if there's somewhere to save it,
+/// numbering the second part's drawing like `day16-2.dot`
+fn save_dot(volcano: &Volcano, part: usize, dot: impl FnOnce() -> String) {
+    if let Some(path) = &volcano.dot {
+        let path = numbered_path(path, part);
+        match std::fs::write(&path, dot()) {
+            Ok(()) => eprintln!("Saved {}", path.display()),
+            Err(err) => eprintln!("Couldn't save {}: {}", path.display(), err),
+        }
+    }
+}
+
+/* Parsing */
+
+impl std::str::FromStr for ValveNetwork {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut flow_rates: Map<String, usize> = Map::default();
+        let mut edges: Map<String, Vec<String>> = Map::default();
+
+        // Parse lines
+        for (i, line) in s.trim_end().lines().enumerate() {
+            // Parse line
+            let (id, flow_rate, valve_edges) = parse::parse_all(
+                tuple((
+                    preceded(tag("Valve "), complete::alpha1),
+                    preceded(tag(" has flow rate="), parse::unsigned_int::<usize>),
+                    preceded(
+                        alt((
+                            tag("; tunnels lead to valves "),
+                            tag("; tunnel leads to valve "),
+                        )),
+                        parse::comma_separated_list(complete::alpha1),
+                    ),
+                )),
+                line,
+            )
+            .map_err(|err| err.offset_lines(i))?;
+
+            // Add to records
+            flow_rates.insert(id.to_owned(), flow_rate);
+            edges.insert(
+                id.to_owned(),
+                valve_edges.into_iter().map(|s| s.to_owned()).collect(),
+            );
+        }
+
+        // Convert valve ids to integers
+        let mut valve_ids: Map<String, ValveID> = Map::default();
+        for valve_str_id in flow_rates.keys().sorted() {
+            valve_ids.insert(valve_str_id.to_string(), valve_ids.len().into());
+        }
+
+        let lookup = |id: &String| {
+            valve_ids
+                .get(id)
+                .copied()
+                .ok_or_else(|| AocError::BadInput(format!("Tunnel leads to unknown valve {}", id)))
+        };
+
+        Ok(Self {
+            names: flow_rates.keys().sorted().cloned().collect(),
+            start_position: lookup(&"AA".to_owned())?,
+            flow_rates: flow_rates.iter().map(|(k, &v)| (valve_ids[k], v)).collect(),
+            edges: edges
+                .iter()
+                .map(|(k, v)| {
+                    Ok((
+                        valve_ids[k],
+                        v.iter().map(lookup).collect::<common::Result<_>>()?,
+                    ))
+                })
+                .collect::<common::Result<_>>()?,
+        })
+    }
+}
+
+/* Display impls */
+
+impl std::fmt::Debug for ValveID {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,220 @@
+//! Checking and scoring plans which come from outside the solver
+
+use std::fmt::Display;
+
+use crate::{OpenValves, ValveAction, ValveID, ValveNetwork};
+
+/// What every actor following a plan does in a single minute, either one `ValveAction`, a
+/// pair of them for the human and the elephant, or one for each of any number of agents
+pub trait PlanStep {
+    fn actions(&self) -> Vec<ValveAction>;
+}
+
+impl PlanStep for ValveAction {
+    fn actions(&self) -> Vec<ValveAction> {
+        vec![*self]
+    }
+}
+
+impl PlanStep for (ValveAction, ValveAction) {
+    fn actions(&self) -> Vec<ValveAction> {
+        vec![self.0, self.1]
+    }
+}
+
+impl PlanStep for Vec<ValveAction> {
+    fn actions(&self) -> Vec<ValveAction> {
+        self.clone()
+    }
+}
+
+/// Why a plan can't be followed. Minutes count from 1 and actors from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanError {
+    /// The plan has more steps than there are minutes
+    TooLong { steps: usize, minutes: usize },
+    /// An action names a valve that isn't in the network
+    UnknownValve {
+        minute: usize,
+        actor: usize,
+        valve: ValveID,
+    },
+    /// An actor tried to move between valves with no tunnel between them
+    NoTunnel {
+        minute: usize,
+        actor: usize,
+        from: String,
+        to: String,
+    },
+    /// An actor tried to open a valve that was already open
+    AlreadyOpen {
+        minute: usize,
+        actor: usize,
+        valve: String,
+    },
+}
+
+impl ValveNetwork {
+    /// Follow a plan for some minutes, returning the total pressure it releases.
+    /// Every actor starts at `AA`, and stays where it is once its part of the plan runs out.
+    pub fn simulate<S: PlanStep>(&self, plan: &[S], minutes: usize) -> Result<usize, PlanError> {
+        if plan.len() > minutes {
+            return Err(PlanError::TooLong {
+                steps: plan.len(),
+                minutes,
+            });
+        }
+
+        let mut released = 0;
+        let mut open_valves = OpenValves::default();
+        let mut positions: Vec<ValveID> = Vec::new();
+        for (i, step) in plan.iter().enumerate() {
+            let minute = i + 1;
+            let actions = step.actions();
+            positions.resize(actions.len().max(positions.len()), self.start_position);
+
+            for (actor, action) in actions.into_iter().enumerate() {
+                let position = &mut positions[actor];
+                match action {
+                    ValveAction::MoveTo(valve) => {
+                        if valve.0 >= self.names.len() {
+                            return Err(PlanError::UnknownValve {
+                                minute,
+                                actor,
+                                valve,
+                            });
+                        }
+                        if !self.edges[position].contains(&valve) {
+                            return Err(PlanError::NoTunnel {
+                                minute,
+                                actor,
+                                from: self.name(*position).to_owned(),
+                                to: self.name(valve).to_owned(),
+                            });
+                        }
+                        *position = valve;
+                    }
+                    ValveAction::Open => {
+                        if open_valves.is_open(*position) {
+                            return Err(PlanError::AlreadyOpen {
+                                minute,
+                                actor,
+                                valve: self.name(*position).to_owned(),
+                            });
+                        }
+                        open_valves = open_valves.open(*position);
+
+                        // It releases pressure for the rest of the minutes
+                        released += self.flow_rates[position] * (minutes - minute);
+                    }
+                    ValveAction::Wait => {}
+                }
+            }
+        }
+
+        Ok(released)
+    }
+}
+
+impl Display for PlanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlanError::TooLong { steps, minutes } => write!(
+                f,
+                "Plan has {} steps but there are only {} minutes",
+                steps, minutes
+            ),
+            PlanError::UnknownValve {
+                minute,
+                actor,
+                valve,
+            } => write!(
+                f,
+                "Minute {}: actor {} moves to unknown valve {:?}",
+                minute, actor, valve
+            ),
+            PlanError::NoTunnel {
+                minute,
+                actor,
+                from,
+                to,
+            } => write!(
+                f,
+                "Minute {}: actor {} can't move from {} to {}, there's no tunnel",
+                minute, actor, from, to
+            ),
+            PlanError::AlreadyOpen {
+                minute,
+                actor,
+                valve,
+            } => write!(
+                f,
+                "Minute {}: actor {} opens {}, which is already open",
+                minute, actor, valve
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PlanError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Day16, ValveAction::*};
+    use common::Solution;
+
+    fn network() -> ValveNetwork {
+        Day16::parse(include_str!("../sample.txt")).network
+    }
+
+    #[test]
+    fn test_simulate() {
+        let network = network();
+        let valve = |name| MoveTo(network.valve(name).unwrap());
+
+        // One actor
+        let plan = [valve("DD"), Open, valve("CC"), valve("BB"), Open];
+        assert_eq!(network.simulate(&plan, 30), Ok(20 * 28 + 13 * 25));
+
+        // The human and the elephant, where the elephant waits then opens AA
+        let plan = [(valve("DD"), Wait), (Open, Open)];
+        assert_eq!(network.simulate(&plan, 26), Ok(20 * 24));
+    }
+
+    #[test]
+    fn test_simulate_errors() {
+        let network = network();
+        let valve = |name| MoveTo(network.valve(name).unwrap());
+
+        assert_eq!(
+            network.simulate(&[valve("DD"), valve("HH")], 30),
+            Err(PlanError::NoTunnel {
+                minute: 2,
+                actor: 0,
+                from: "DD".to_owned(),
+                to: "HH".to_owned()
+            })
+        );
+        assert_eq!(
+            network.simulate(&[(valve("DD"), valve("DD")), (Open, Open)], 26),
+            Err(PlanError::AlreadyOpen {
+                minute: 2,
+                actor: 1,
+                valve: "DD".to_owned()
+            })
+        );
+        assert_eq!(
+            network.simulate(&[MoveTo(ValveID(99))], 30),
+            Err(PlanError::UnknownValve {
+                minute: 1,
+                actor: 0,
+                valve: ValveID(99)
+            })
+        );
+        assert_eq!(
+            network.simulate(&[Wait, Wait], 1).unwrap_err().to_string(),
+            "Plan has 2 steps but there are only 1 minutes"
+        );
+    }
+}
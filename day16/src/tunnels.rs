@@ -0,0 +1,197 @@
+//! The valves worth opening and the shortest walks between them, so a search only has to
+//! decide which valve to open next rather than every single step
+
+use common::{memo::Memo, Map};
+
+use crate::{ValveID, ValveNetwork};
+
+/// Which of the `Tunnels`' valves are open, a bit for each
+pub type Opened = u32;
+
+/// A state of the search, the valve we're at with some minutes left and some valves open
+type State = (ValveID, usize, Opened);
+
+pub struct Tunnels {
+    /// Valves with a flow rate, where a valve's index is its bit in `Opened`
+    valves: Vec<ValveID>,
+    flow_rates: Vec<usize>,
+    /// Minutes it takes to walk from one valve to another, by `ValveID`
+    distances: Vec<Vec<usize>>,
+}
+
+impl Tunnels {
+    pub fn new(network: &ValveNetwork) -> Self {
+        // Find the distance between every pair of valves (Floyd-Warshall)
+        let n = network.flow_rates.len();
+        let mut distances = vec![vec![usize::MAX / 2; n]; n];
+        for (from, tunnels) in &network.edges {
+            for to in tunnels {
+                distances[from.0][to.0] = 1;
+            }
+        }
+        for (i, row) in distances.iter_mut().enumerate() {
+            row[i] = 0;
+        }
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    let through_k = distances[i][k] + distances[k][j];
+                    if through_k < distances[i][j] {
+                        distances[i][j] = through_k;
+                    }
+                }
+            }
+        }
+
+        // Only valves with a flow rate are worth walking to
+        let valves: Vec<ValveID> = (0..n)
+            .map(ValveID)
+            .filter(|valve| network.flow_rates[valve] > 0)
+            .collect();
+        assert!(
+            valves.len() <= Opened::BITS as usize,
+            "Too many valves with a flow rate"
+        );
+        let flow_rates = valves
+            .iter()
+            .map(|valve| network.flow_rates[valve])
+            .collect();
+
+        Self {
+            valves,
+            flow_rates,
+            distances,
+        }
+    }
+
+    pub fn distance(&self, from: ValveID, to: ValveID) -> usize {
+        self.distances[from.0][to.0]
+    }
+
+    /// The valves that could be opened next from a state, with the state after opening each
+    /// and the pressure that valve will release in the minutes left
+    fn next_valves(
+        &self,
+        (from, minutes, opened): State,
+    ) -> impl Iterator<Item = (ValveID, State, usize)> + '_ {
+        self.valves
+            .iter()
+            .enumerate()
+            .filter(move |(bit, _)| opened & (1 << bit) == 0)
+            .filter_map(move |(bit, &valve)| {
+                // Walk there and spend a minute opening it
+                let left = minutes
+                    .checked_sub(self.distance(from, valve) + 1)
+                    .filter(|&left| left > 0)?;
+                let released = self.flow_rates[bit] * left;
+                Some((valve, (valve, left, opened | 1 << bit), released))
+            })
+    }
+
+    /// The most pressure that can be released from a state, remembering every state it reaches
+    fn best_release(&self, memo: &mut Memo<State, usize>, state: State) -> usize {
+        memo.compute(state, &|best, state| {
+            self.next_valves(state)
+                .map(|(_, next, released)| released + best(next))
+                .max()
+                .unwrap_or(0)
+        })
+    }
+
+    /// The order to open valves in to release the most pressure in some minutes from a valve,
+    /// along with how much pressure that releases. Valves in `opened` are left alone.
+    pub fn best_order(
+        &self,
+        from: ValveID,
+        minutes: usize,
+        opened: Opened,
+    ) -> (usize, Vec<ValveID>) {
+        let mut memo = Memo::new();
+        let start = (from, minutes, opened);
+        let total = self.best_release(&mut memo, start);
+
+        // Follow the choices which led to the best total
+        let mut order = Vec::new();
+        let mut state = start;
+        let mut remaining = total;
+        while remaining > 0 {
+            let (valve, next, released) = self
+                .next_valves(state)
+                .find(|&(_, next, released)| {
+                    released + self.best_release(&mut memo, next) == remaining
+                })
+                .unwrap();
+            order.push(valve);
+            remaining -= released;
+            state = next;
+        }
+
+        (total, order)
+    }
+
+    /// The most pressure one actor can release in some minutes from a valve by opening exactly
+    /// each set of valves, for every set it has time to open
+    pub fn best_per_subset(&self, from: ValveID, minutes: usize) -> Map<Opened, usize> {
+        let mut best: Map<Opened, usize> = Map::default();
+        let mut stack = vec![((from, minutes, 0), 0)];
+        while let Some((state, released)) = stack.pop() {
+            let best_released = best.entry(state.2).or_default();
+            *best_released = released.max(*best_released);
+            for (_, next, more) in self.next_valves(state) {
+                stack.push((next, released + more));
+            }
+        }
+        best
+    }
+
+    /// The most pressure some actors can release together in some minutes from a valve, and the
+    /// valves each should open. They never need to open the same valve, so this is the best
+    /// choice of disjoint sets from `best_per_subset`, one for each actor.
+    pub fn best_split(&self, from: ValveID, minutes: usize, agents: usize) -> (usize, Vec<Opened>) {
+        let mut subsets: Vec<(Opened, usize)> =
+            self.best_per_subset(from, minutes).into_iter().collect();
+        subsets.sort_unstable_by_key(|&(_, released)| std::cmp::Reverse(released));
+
+        let mut best = (0, vec![0; agents]);
+        let mut chosen = Vec::with_capacity(agents);
+        Self::search_split(&subsets, 0, agents, 0, 0, &mut chosen, &mut best);
+        best
+    }
+
+    /// Choose a set for each remaining actor, no better than the last one chosen so each split is
+    /// only tried once. Stops once the remaining actors couldn't beat the best found.
+    fn search_split(
+        subsets: &[(Opened, usize)],
+        start: usize,
+        agents: usize,
+        used: Opened,
+        released: usize,
+        chosen: &mut Vec<Opened>,
+        best: &mut (usize, Vec<Opened>),
+    ) {
+        if agents == 0 {
+            if released > best.0 {
+                *best = (released, chosen.clone());
+            }
+            return;
+        }
+        for (i, &(set, more)) in subsets.iter().enumerate().skip(start) {
+            if released + more * agents <= best.0 {
+                break;
+            }
+            if set & used == 0 {
+                chosen.push(set);
+                Self::search_split(
+                    subsets,
+                    i,
+                    agents - 1,
+                    used | set,
+                    released + more,
+                    chosen,
+                    best,
+                );
+                chosen.pop();
+            }
+        }
+    }
+}
@@ -1,27 +1,82 @@
+use proc_macro::Delimiter;
 use proc_macro::TokenStream;
 use proc_macro::TokenTree;
 
 #[derive(Debug, PartialEq, Eq)]
 enum ShapeElement {
     Fill,
+    Value(char),
     Space,
     NewLine,
 }
 
+type Offset = (usize, usize);
+
+#[derive(Debug, Default)]
+struct ShapeOptions {
+    /// Emit every rotation and mirror image of the shape
+    rotations: bool,
+    /// Characters to map to cell values, with the expression for each value
+    cell_map: Option<Vec<(char, String)>>,
+}
+
+/// Build a vector of `(x, y)` offsets from a shape drawn with `@` (filled) and `.` (empty)
+/// cells, where each row is terminated by a `,`.
+///
+/// Options can be given before the shape, separated by commas and terminated by a `;`:
+/// - `rotations` produces a vector of shapes containing every distinct rotation and mirror
+///   image of the drawn shape, each normalised to the top left.
+/// - `map { 'S' => Cell::Start, '#' => Cell::Wall }` draws the shape with the given
+///   characters instead, producing `(x, y, value)` triples for every mapped cell.
 #[proc_macro]
 pub fn shape(_item: TokenStream) -> TokenStream {
+    let mut tokens = _item.into_iter().peekable();
+
+    // Parse options
+    let mut options = ShapeOptions::default();
+    while let Some(TokenTree::Ident(ident)) = tokens.peek() {
+        match ident.to_string().as_str() {
+            "rotations" => options.rotations = true,
+            "map" => {
+                tokens.next();
+                match tokens.peek() {
+                    Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+                        options.cell_map = Some(parse_cell_map(group.stream()));
+                    }
+                    _ => panic!("Expected braces after map option"),
+                }
+            }
+            other => panic!("Unknown shape option: {}", other),
+        }
+        tokens.next();
+        match tokens.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ';' => break,
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => continue,
+            _ => panic!("Expected ';' after shape options"),
+        }
+    }
+    if options.rotations && options.cell_map.is_some() {
+        panic!("The rotations and map options can't be combined");
+    }
+
     // Parse stream
-    let shape_elements = _item
-        .into_iter()
-        .map(|token_tree| match token_tree {
-            TokenTree::Punct(punct) => match punct.as_char() {
-                '@' => ShapeElement::Fill,
-                ',' => ShapeElement::NewLine,
-                '.' => ShapeElement::Space,
-                _ => panic!("Unknown character"),
-            },
+    let shape_elements = tokens
+        .flat_map(|token_tree| match token_tree {
+            TokenTree::Punct(punct) => vec![punct.as_char()],
+            TokenTree::Ident(_) | TokenTree::Literal(_) if options.cell_map.is_some() => {
+                token_tree.to_string().chars().collect()
+            }
             _ => panic!("Unexpected token"),
         })
+        .map(|c| match (c, &options.cell_map) {
+            (',', _) => ShapeElement::NewLine,
+            (c, Some(cell_map)) if cell_map.iter().any(|(key, _)| *key == c) => {
+                ShapeElement::Value(c)
+            }
+            ('.', _) => ShapeElement::Space,
+            ('@', None) => ShapeElement::Fill,
+            _ => panic!("Unknown character"),
+        })
         .collect::<Vec<_>>();
 
     // Split elements into lines
@@ -29,17 +84,148 @@ pub fn shape(_item: TokenStream) -> TokenStream {
         .split(|el| *el == ShapeElement::NewLine)
         .collect::<Vec<_>>();
 
-    let shape_offsets = shape_lines
+    let shape_cells = shape_lines
         .iter()
         .enumerate()
         .flat_map(|(y, &line)| {
             line.iter()
                 .enumerate()
-                .filter(|(_, cell)| **cell == ShapeElement::Fill)
-                .map(move |(x, _)| (x, y))
+                .filter(|(_, cell)| **cell != ShapeElement::Space)
+                .map(move |(x, cell)| (x, y, cell))
         })
         .collect::<Vec<_>>();
 
-    let textual = format!("vec!{:?}", shape_offsets);
+    let textual = if let Some(cell_map) = &options.cell_map {
+        let triples = shape_cells
+            .iter()
+            .map(|(x, y, cell)| {
+                let value = match cell {
+                    ShapeElement::Value(c) => cell_map.iter().find(|(key, _)| key == c).unwrap(),
+                    _ => unreachable!(),
+                };
+                format!("({}, {}, {})", x, y, value.1)
+            })
+            .collect::<Vec<_>>();
+        format!("vec![{}]", triples.join(", "))
+    } else {
+        let shape_offsets = shape_cells
+            .iter()
+            .map(|&(x, y, _)| (x, y))
+            .collect::<Vec<_>>();
+        if options.rotations {
+            let variants = shape_variants(&shape_offsets)
+                .iter()
+                .map(|variant| format!("vec!{:?}", variant))
+                .collect::<Vec<_>>();
+            format!("vec![{}]", variants.join(", "))
+        } else {
+            format!("vec!{:?}", shape_offsets)
+        }
+    };
     textual.parse().unwrap()
 }
+
+/// Parse the body of a `map { 'c' => value, ... }` option
+fn parse_cell_map(stream: TokenStream) -> Vec<(char, String)> {
+    let tokens = stream.into_iter().collect::<Vec<_>>();
+    tokens
+        .split(|token| matches!(token, TokenTree::Punct(punct) if punct.as_char() == ','))
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| match entry {
+            [TokenTree::Literal(key), TokenTree::Punct(eq), TokenTree::Punct(gt), value @ ..]
+                if eq.as_char() == '=' && gt.as_char() == '>' && !value.is_empty() =>
+            {
+                let value = value.iter().cloned().collect::<TokenStream>().to_string();
+                (parse_char_literal(&key.to_string()), value)
+            }
+            _ => panic!("Expected map entries of the form 'c' => value"),
+        })
+        .collect()
+}
+
+/// Get the character out of a char literal such as `'#'`
+fn parse_char_literal(literal: &str) -> char {
+    let mut chars = literal
+        .strip_prefix('\'')
+        .and_then(|s| s.strip_suffix('\''))
+        .unwrap_or_else(|| panic!("Expected a char literal, found {}", literal))
+        .chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => c,
+        _ => panic!("Expected a single character, found {}", literal),
+    }
+}
+
+/// Every distinct rotation and mirror image of a shape, starting with the shape itself
+fn shape_variants(offsets: &[Offset]) -> Vec<Vec<Offset>> {
+    let mut variants: Vec<Vec<Offset>> = Vec::new();
+    for base in [normalise(offsets.to_vec()), mirror(offsets)] {
+        let mut variant = base;
+        for _ in 0..4 {
+            if !variants.contains(&variant) {
+                variants.push(variant.clone());
+            }
+            variant = rotate(&variant);
+        }
+    }
+    variants
+}
+
+/// Rotate a shape 90 degrees clockwise
+fn rotate(offsets: &[Offset]) -> Vec<Offset> {
+    let height = offsets.iter().map(|p| p.1).max().unwrap_or(0);
+    normalise(offsets.iter().map(|&(x, y)| (height - y, x)).collect())
+}
+
+/// Mirror a shape horizontally
+fn mirror(offsets: &[Offset]) -> Vec<Offset> {
+    let width = offsets.iter().map(|p| p.0).max().unwrap_or(0);
+    normalise(offsets.iter().map(|&(x, y)| (width - x, y)).collect())
+}
+
+/// Shift a shape so it touches the top left, with offsets ordered row by row
+fn normalise(mut offsets: Vec<Offset>) -> Vec<Offset> {
+    let min_x = offsets.iter().map(|p| p.0).min().unwrap_or(0);
+    let min_y = offsets.iter().map(|p| p.1).min().unwrap_or(0);
+    for (x, y) in offsets.iter_mut() {
+        *x -= min_x;
+        *y -= min_y;
+    }
+    offsets.sort_by_key(|&(x, y)| (y, x));
+    offsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shape_variant_counts() {
+        let square = vec![(0, 0), (1, 0), (0, 1), (1, 1)];
+        let line = vec![(0, 0), (1, 0), (2, 0), (3, 0)];
+        let plus = vec![(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)];
+        let tee = vec![(0, 0), (1, 0), (2, 0), (1, 1)];
+        let corner = vec![(2, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let ell = vec![(0, 0), (0, 1), (0, 2), (1, 2)];
+        assert_eq!(shape_variants(&square).len(), 1);
+        assert_eq!(shape_variants(&line).len(), 2);
+        assert_eq!(shape_variants(&plus).len(), 1);
+        assert_eq!(shape_variants(&tee).len(), 4);
+        assert_eq!(shape_variants(&corner).len(), 4);
+        assert_eq!(shape_variants(&ell).len(), 8);
+    }
+
+    #[test]
+    fn test_parse_char_literal() {
+        assert_eq!(parse_char_literal("'#'"), '#');
+        assert_eq!(parse_char_literal("'S'"), 'S');
+    }
+
+    #[test]
+    fn test_shape_variants_start_with_original() {
+        let ell = vec![(0, 0), (0, 1), (0, 2), (1, 2)];
+        let variants = shape_variants(&ell);
+        assert_eq!(variants[0], ell);
+        assert!(variants.contains(&vec![(0, 0), (1, 0), (2, 0), (0, 1)]));
+    }
+}
@@ -0,0 +1,51 @@
+use shape_macro::shape;
+
+#[test]
+fn test_plain_shape() {
+    let tee: Vec<(usize, usize)> = shape!(@@@, .@.);
+    assert_eq!(tee, vec![(0, 0), (1, 0), (2, 0), (1, 1)]);
+}
+
+#[test]
+fn test_rotations() {
+    let ell: Vec<Vec<(usize, usize)>> = shape!(rotations; @., @., @@);
+    assert_eq!(ell.len(), 8);
+    assert_eq!(ell[0], vec![(0, 0), (0, 1), (0, 2), (1, 2)]);
+    assert!(ell.contains(&vec![(0, 0), (1, 0), (2, 0), (0, 1)]));
+    for (i, variant) in ell.iter().enumerate() {
+        assert!(!ell[..i].contains(variant), "variant {} is repeated", i);
+    }
+
+    let square: Vec<Vec<(usize, usize)>> = shape!(rotations; @@, @@);
+    assert_eq!(square, vec![vec![(0, 0), (1, 0), (0, 1), (1, 1)]]);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Start,
+    Wall,
+    Key(u8),
+    End,
+}
+
+#[test]
+fn test_map() {
+    // Idents and literals are split into a cell per character, and unmapped `.`s are left empty
+    let cells: Vec<(usize, usize, Cell)> = shape!(
+        map { 'S' => Cell::Start, '#' => Cell::Wall, '1' => Cell::Key(1), 'E' => Cell::End };
+        S.#,
+        #1.,
+        .EE
+    );
+    assert_eq!(
+        cells,
+        vec![
+            (0, 0, Cell::Start),
+            (2, 0, Cell::Wall),
+            (0, 1, Cell::Wall),
+            (1, 1, Cell::Key(1)),
+            (1, 2, Cell::End),
+            (2, 2, Cell::End),
+        ]
+    );
+}
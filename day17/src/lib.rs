@@ -0,0 +1,723 @@
+use colored::{Color, Colorize};
+use common::{
+    cycles, progress, tracing::debug, viz::Visualizer, AocError, FastMap, FastSet, Solution,
+};
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use shape_macro::shape;
+
+const WORLD_WIDTH: usize = 7;
+
+/// How many rows the tower can grow to before the rows no rock can reach are dropped
+const PRUNE_ROWS: usize = 256;
+const PT2_ROCKS: usize = 1_000_000_000_000;
+
+static COLORS: Lazy<Vec<Color>> = Lazy::new(|| {
+    vec![
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+    ]
+});
+
+static ROCK_SHAPES: Lazy<Vec<RockShape>> = Lazy::new(|| {
+    vec![
+        shape!(
+            @@@@,
+        ),
+        shape!(
+            .@.,
+            @@@,
+            .@.,
+        ),
+        shape!(
+            ..@,
+            ..@,
+            @@@,
+        ),
+        shape!(
+            @,
+            @,
+            @,
+            @,
+        ),
+        shape!(
+            @@,
+            @@,
+        ),
+    ]
+    .into_iter()
+    .map(|segments| {
+        let height = segments.iter().map(|p| p.1).max().unwrap();
+        let segments = segments
+            .into_iter()
+            .map(|(x, y)| Position { x, y: height - y })
+            .collect_vec();
+        let mut rows = vec![0; height as usize + 1];
+        for p in &segments {
+            rows[p.y as usize] |= 1 << p.x;
+        }
+        RockShape {
+            width: segments.iter().map(|p| p.x).max().unwrap() + 1,
+            rows,
+            segments,
+        }
+    })
+    .collect_vec()
+});
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Direction {
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct JetDirection(Direction);
+
+#[derive(
+    Hash,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    derive_more::Add,
+    derive_more::AddAssign,
+    derive_more::Sub,
+)]
+struct Position {
+    x: isize,
+    y: isize,
+}
+
+#[derive(Debug, Clone)]
+struct RockShape {
+    /// Segments of rock shape, relative to bottom left
+    segments: Vec<Position>,
+    /// Each row of the shape from the bottom up, with a bit set for each segment in the row
+    rows: Vec<u8>,
+    width: isize,
+}
+
+#[derive(Debug)]
+struct Rock {
+    shape_index: usize,
+    position: Position,
+}
+
+#[derive(Debug, Default)]
+pub struct RockWorld {
+    /// Each row of settled rock from the floor up, with bit `x` set if column `x` has rock.
+    /// The bottom row is at `y = floor + 1`.
+    rows: Vec<u8>,
+    /// Height of the floor, which rises as rows below the reach of falling rocks are dropped.
+    /// Everything at or below it counts as rock.
+    floor: isize,
+    /// Which rock settled at each position, only kept for drawing the tower in colour
+    colors: Option<FastMap<Position, usize>>,
+    falling_rock: Option<Rock>,
+    settled_rocks: usize,
+    jets: Vec<JetDirection>,
+    /// Index of the next jet to push a rock
+    jet_index: usize,
+}
+
+/// Everything that decides how the next rocks will fall, for finding when the tower repeats
+#[derive(Debug, Hash, PartialEq, Eq)]
+struct RockWorldState {
+    shape_index: usize,
+    jet_index: usize,
+    /// The top of the tower, see `WorldState::surface`
+    surface: Vec<u8>,
+}
+
+/// A copy of a world between rocks, which can be restored to carry on from the same point
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorldState {
+    /// The rows at the top of the tower that falling rocks could touch, from the bottom up.
+    /// Empty cells rocks can't reach are filled in, as they can't affect the rocks to come.
+    surface: Vec<u8>,
+    /// Height of the floor under the surface
+    floor: isize,
+    settled_rocks: usize,
+    jet_index: usize,
+}
+
+#[derive(Debug)]
+enum RockMovement {
+    FromJet,
+    FromGravity,
+}
+use RockMovement::*;
+
+macro_rules! position {
+    ($v: expr) => {
+        Position {
+            x: $v as isize,
+            y: $v as isize,
+        }
+    };
+    ($x: expr, $y: expr) => {
+        Position {
+            x: $x as isize,
+            y: $y as isize,
+        }
+    };
+}
+
+impl Direction {
+    fn to_position(self) -> Position {
+        match self {
+            Direction::Down => position!(0, -1),
+            Direction::Left => position!(-1, 0),
+            Direction::Right => position!(1, 0),
+        }
+    }
+}
+
+impl RockWorld {
+    pub fn new(jets: Vec<JetDirection>) -> Self {
+        Self {
+            jets,
+            ..Default::default()
+        }
+    }
+
+    /// Remember which rock settled where, so the tower can be drawn in colour
+    pub fn with_colors(mut self) -> Self {
+        self.colors = Some(FastMap::default());
+        self
+    }
+
+    fn is_rock(&self, pos: Position) -> bool {
+        pos.y <= self.floor
+            || self
+                .rows
+                .get((pos.y - self.floor - 1) as usize)
+                .is_some_and(|row| row & (1 << pos.x) != 0)
+    }
+
+    /// Whether a shape at a position would overlap settled rock, the walls or the floor
+    fn collides(&self, shape: &RockShape, position: Position) -> bool {
+        if position.x < 0
+            || position.x + shape.width > WORLD_WIDTH as isize
+            || position.y <= self.floor
+        {
+            return true;
+        }
+        let bottom = (position.y - self.floor - 1) as usize;
+        shape.rows.iter().enumerate().any(|(i, mask)| {
+            self.rows
+                .get(bottom + i)
+                .is_some_and(|row| row & (mask << position.x) != 0)
+        })
+    }
+
+    /// Attempt to move the rock and return whether it did
+    fn try_move_falling(&mut self, direction: Direction) -> bool {
+        let rock = self
+            .falling_rock
+            .as_ref()
+            .expect("Can't move falling rock as there isn't any");
+        let position = rock.position + direction.to_position();
+        let can_move = !self.collides(rock.shape(), position);
+        if can_move {
+            self.falling_rock.as_mut().unwrap().position = position;
+        }
+        can_move
+    }
+
+    pub fn highest_rock(&self) -> isize {
+        self.floor + self.rows.len() as isize
+    }
+
+    /// Drop the rows below `y`, raising the floor to just under it.
+    /// Only safe if no falling rock could reach below `y`.
+    pub fn truncate_below(&mut self, y: isize) {
+        let dropped = (y - self.floor - 1).clamp(0, self.rows.len() as isize);
+        self.rows.drain(..dropped as usize);
+        self.floor += dropped;
+        if let Some(colors) = &mut self.colors {
+            colors.retain(|pos, _| pos.y > self.floor);
+        }
+    }
+
+    /// Drop the rows below the lowest one a falling rock could touch
+    fn prune(&mut self) {
+        if let Some(lowest) = self.reachable().iter().map(|pos| pos.y).min() {
+            // The row under the lowest reachable cell is what stops rocks falling further
+            self.truncate_below(lowest - 1);
+        }
+    }
+
+    pub fn settled_rocks(&self) -> usize {
+        self.settled_rocks
+    }
+
+    /// Index of the next jet to push a rock, which wraps back to 0 after the last jet
+    pub fn jet_index(&self) -> usize {
+        self.jet_index
+    }
+
+    /// The jets that will push rocks next, starting from the current one
+    fn upcoming_jets(&self) -> impl Iterator<Item = &JetDirection> {
+        self.jets.iter().cycle().skip(self.jet_index)
+    }
+
+    /// The state of the world between rocks
+    fn state(&self) -> RockWorldState {
+        let snapshot = self.snapshot();
+        RockWorldState {
+            shape_index: snapshot.rock_index(),
+            jet_index: snapshot.jet_index,
+            surface: snapshot.surface,
+        }
+    }
+
+    /// Save the state of the world between rocks, which only needs the top of the tower
+    pub fn snapshot(&self) -> WorldState {
+        let reachable = self.reachable();
+
+        // The row under the lowest reachable cell is what stops rocks falling further
+        let lowest = reachable
+            .iter()
+            .map(|pos| pos.y)
+            .min()
+            .unwrap_or(self.floor + 1);
+        let bottom = (lowest - 1).max(self.floor + 1);
+        let surface = (bottom..=self.highest_rock())
+            .map(|y| {
+                (0..WORLD_WIDTH)
+                    .filter(|&x| !reachable.contains(&position!(x, y)))
+                    .fold(0, |row, x| row | 1 << x)
+            })
+            .collect();
+
+        WorldState {
+            surface,
+            floor: bottom - 1,
+            settled_rocks: self.settled_rocks,
+            jet_index: self.jet_index,
+        }
+    }
+
+    /// Go back to a saved state, dropping any falling rock
+    pub fn restore(&mut self, state: &WorldState) {
+        self.rows = state.surface.clone();
+        self.floor = state.floor;
+        self.settled_rocks = state.settled_rocks;
+        self.jet_index = state.jet_index;
+        self.falling_rock = None;
+        if let Some(colors) = &mut self.colors {
+            colors.clear();
+        }
+    }
+
+    /// The empty cells below the top of the tower that can be reached from the row above it,
+    /// moving down, left or right like a falling rock
+    fn reachable(&self) -> FastSet<Position> {
+        let top = self.highest_rock() + 1;
+        let mut seen = FastSet::default();
+        let mut stack = (0..WORLD_WIDTH).map(|x| position!(x, top)).collect_vec();
+        while let Some(pos) = stack.pop() {
+            if pos.y > top
+                || pos.x < 0
+                || pos.x >= WORLD_WIDTH as isize
+                || self.is_rock(pos)
+                || !seen.insert(pos)
+            {
+                continue;
+            }
+            for direction in [Direction::Down, Direction::Left, Direction::Right] {
+                stack.push(pos + direction.to_position());
+            }
+        }
+        seen
+    }
+
+    fn rock_spawn_pos(&self) -> Position {
+        position!(2, self.highest_rock() + 4)
+    }
+
+    pub fn step(&mut self) {
+        // Spawn a new rock if we dont have one
+        if self.falling_rock.is_none() {
+            self.falling_rock = Some(Rock::new(self.settled_rocks(), self.rock_spawn_pos()));
+        }
+
+        // Move rock until settled
+        for movement in [FromJet, FromGravity].iter().cycle() {
+            match movement {
+                FromJet => {
+                    // Move from jet
+                    let jet = self.jets[self.jet_index];
+                    self.try_move_falling(jet.0);
+
+                    // Cycle jets
+                    self.jet_index = (self.jet_index + 1) % self.jets.len();
+                }
+                FromGravity => {
+                    let hit_ground = !self.try_move_falling(Direction::Down);
+                    if hit_ground {
+                        // Convert rock to settled rock
+                        let rock = self.falling_rock.take().unwrap();
+                        let bottom = (rock.position.y - self.floor - 1) as usize;
+                        let shape_rows = &rock.shape().rows;
+                        if self.rows.len() < bottom + shape_rows.len() {
+                            self.rows.resize(bottom + shape_rows.len(), 0);
+                        }
+                        for (i, mask) in shape_rows.iter().enumerate() {
+                            self.rows[bottom + i] |= mask << rock.position.x;
+                        }
+                        if let Some(colors) = &mut self.colors {
+                            for pos in rock.to_positions() {
+                                colors.insert(pos, self.settled_rocks + 1);
+                            }
+                        }
+
+                        // Increment counter
+                        self.settled_rocks += 1;
+
+                        // Keep memory bounded when dropping lots of rocks
+                        if self.rows.len() > PRUNE_ROWS {
+                            self.prune();
+                        }
+
+                        // End of step
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl WorldState {
+    /// Index of the shape of the next rock to fall
+    pub fn rock_index(&self) -> usize {
+        self.settled_rocks % ROCK_SHAPES.len()
+    }
+
+    pub fn height(&self) -> isize {
+        self.floor + self.surface.len() as isize
+    }
+}
+
+impl Rock {
+    pub fn new(shape_index: usize, position: Position) -> Self {
+        Self {
+            shape_index: shape_index % ROCK_SHAPES.len(),
+            position,
+        }
+    }
+
+    pub fn shape(&self) -> &RockShape {
+        &ROCK_SHAPES[self.shape_index]
+    }
+
+    pub fn height(&self) -> isize {
+        self.shape()
+            .segments
+            .iter()
+            .map(|pos| pos.y)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn overlaps_with(&self, pos: &Position) -> bool {
+        let relative = *pos - self.position;
+        self.shape().segments.contains(&relative)
+    }
+
+    pub fn to_positions(&self) -> Vec<Position> {
+        self.shape()
+            .segments
+            .iter()
+            .map(|&pos| pos + self.position)
+            .collect()
+    }
+}
+
+impl TryFrom<char> for JetDirection {
+    type Error = &'static str;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '>' => Ok(JetDirection(Direction::Right)),
+            '<' => Ok(JetDirection(Direction::Left)),
+            _ => Err("Unknown character"),
+        }
+    }
+}
+
+pub struct Day17;
+
+common::solution_fns!(Day17);
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    type Input = Vec<JetDirection>;
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn validate(input: &str) -> common::Result<()> {
+        // The jets are a single line of arrows
+        let jets = input.trim_end();
+        match jets.find(|c| c != '<' && c != '>') {
+            Some(i) => Err(AocError::parse_at(
+                input,
+                &jets[i..],
+                "Expected a < or > jet",
+            )),
+            None if jets.is_empty() => Err(AocError::BadInput("No jets in input".into())),
+            None => Ok(()),
+        }
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        input
+            .trim_end()
+            .chars()
+            .map(|c| TryFrom::try_from(c).unwrap())
+            .collect()
+    }
+
+    fn part1(jets: &Self::Input) -> Self::Part1 {
+        let mut viz = Visualizer::from_args();
+        let mut world = RockWorld::new(jets.clone());
+        if viz.is_enabled() {
+            world = world.with_colors();
+        }
+        let progress = progress::bar(2022);
+        while world.settled_rocks() < 2022 {
+            let settled = world.settled_rocks();
+            world.step();
+            progress.advance((world.settled_rocks() - settled) as u64);
+            viz.frame(&world);
+        }
+        world.highest_rock()
+    }
+
+    fn part2(jets: &Self::Input) -> Self::Part2 {
+        // Drop rocks until the top of the tower repeats, recording its height after each rock
+        let mut world = RockWorld::new(jets.clone());
+        let mut heights = vec![world.highest_rock()];
+        let states = std::iter::from_fn(|| {
+            let state = world.state();
+            world.step();
+            heights.push(world.highest_rock());
+            Some(state)
+        });
+        let (offset, period) = cycles::find_cycle(states).unwrap();
+        debug!(offset, period, "Found cycle in tower");
+
+        // Skip ahead to the final rock
+        cycles::extrapolate(PT2_ROCKS, offset, period, &heights)
+    }
+}
+
+impl std::fmt::Display for RockWorld {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let top = self.highest_rock().max(
+            self.falling_rock
+                .as_ref()
+                .map(|r| r.position.y + r.height())
+                .unwrap_or(0),
+        );
+        for y in (self.floor + 1..=top).rev() {
+            write!(f, "|")?;
+            for x in 0..WORLD_WIDTH {
+                let p = position!(x, y);
+
+                let c = if self.is_rock(p) {
+                    match self.colors.as_ref().and_then(|colors| colors.get(&p)) {
+                        Some(col) => "#".color(COLORS[col % COLORS.len()]),
+                        None => "#".normal(),
+                    }
+                } else if self
+                    .falling_rock
+                    .as_ref()
+                    .map(|rock| rock.overlaps_with(&p))
+                    .unwrap_or(false)
+                {
+                    "@".red()
+                } else {
+                    ".".black()
+                };
+                write!(f, "{}", c)?;
+            }
+            writeln!(
+                f,
+                "| {}",
+                if y == top {
+                    self.upcoming_jets()
+                        .take(5)
+                        .map(|j| format!("{:?}", j))
+                        .join("")
+                } else {
+                    "".to_owned()
+                }
+            )?;
+        }
+        if self.floor == 0 {
+            write!(f, "+{}+", "-".repeat(WORLD_WIDTH))?;
+        } else {
+            write!(f, "+{}+ {}", "~".repeat(WORLD_WIDTH), self.floor)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for JetDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self.0 {
+                Direction::Right => ">",
+                Direction::Left => "<",
+                _ => unreachable!(),
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_with_sample {
+    use super::*;
+    use common::sample_test;
+
+    sample_test!(test_tower_height, Day17::part1, file "sample.txt", 3068);
+    sample_test!(test_tall_tower_height, Day17::part2, file "sample.txt", 1514285714288);
+
+    #[test]
+    fn test_state_repeats() {
+        let mut world = RockWorld::new(Day17::parse(include_str!("../sample.txt")));
+        assert_eq!(world.state().surface, Vec::<u8>::new());
+
+        // The sample's tower repeats every 35 rocks once it gets going
+        let states = std::iter::from_fn(|| {
+            let state = world.state();
+            world.step();
+            Some(state)
+        });
+        let (offset, period) = cycles::find_cycle(states).unwrap();
+        assert_eq!(period, 35);
+        assert!(offset < 100);
+    }
+
+    #[test]
+    fn test_jet_index() {
+        let jets = Day17::parse(include_str!("../sample.txt"));
+        let mut world = RockWorld::new(jets.clone());
+        assert_eq!(world.jet_index(), 0);
+
+        // The first rock is pushed 4 times before it lands on the floor
+        world.step();
+        assert_eq!(world.jet_index(), 4);
+        assert_eq!(world.upcoming_jets().next(), jets.get(4));
+
+        // Wraps around once every jet has been used
+        while world.settled_rocks() < 100 {
+            world.step();
+        }
+        assert!(world.jet_index() < jets.len());
+    }
+
+    #[test]
+    fn test_rows() {
+        let mut world = RockWorld::new(Day17::parse(include_str!("../sample.txt")));
+        world.step();
+        world.step();
+
+        // The flat rock lands in the middle of the floor with the cross on top of it, where bit `x`
+        // is column `x`
+        assert_eq!(world.rows, vec![0b0111100, 0b0001000, 0b0011100, 0b0001000]);
+        assert!(world.is_rock(position!(3, 4)));
+        assert!(!world.is_rock(position!(2, 4)));
+
+        let square = &ROCK_SHAPES[4];
+        assert!(world.collides(square, position!(3, 1)));
+        assert!(world.collides(square, position!(-1, 5)));
+        assert!(world.collides(square, position!(6, 5)));
+        assert!(!world.collides(square, position!(5, 2)));
+        assert!(world.colors.is_none());
+    }
+
+    #[test]
+    fn test_truncate_below() {
+        let mut world = RockWorld::new(Day17::parse(include_str!("../sample.txt")));
+        world.step();
+        world.step();
+        world.truncate_below(3);
+        assert_eq!(world.floor, 2);
+        assert_eq!(world.rows, vec![0b0011100, 0b0001000]);
+        assert_eq!(world.highest_rock(), 4);
+        assert!(world.is_rock(position!(0, 2)));
+        assert!(world.is_rock(position!(3, 4)));
+
+        // Rocks keep falling onto the raised floor
+        world.step();
+        assert_eq!(world.highest_rock(), 6);
+    }
+
+    #[test]
+    fn test_pruning() {
+        let mut world = RockWorld::new(Day17::parse(include_str!("../sample.txt")));
+        while world.settled_rocks() < 2022 {
+            world.step();
+            assert!(world.rows.len() <= PRUNE_ROWS + 4);
+        }
+
+        // The dropped rows still count towards the height
+        assert!(world.floor > 0);
+        assert_eq!(world.highest_rock(), 3068);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let jets = Day17::parse(include_str!("../sample.txt"));
+        let mut world = RockWorld::new(jets.clone());
+        for _ in 0..2 {
+            world.step();
+        }
+
+        // Every empty cell can still be reached by sliding in from the side
+        let snapshot = world.snapshot();
+        assert_eq!(snapshot.surface, world.rows);
+        assert_eq!(snapshot.floor, 0);
+        assert_eq!((snapshot.rock_index(), snapshot.jet_index), (2, 8));
+        assert_eq!(snapshot.height(), world.highest_rock());
+
+        // Carrying on from a restored snapshot matches carrying on from where it was taken
+        for _ in 0..500 {
+            world.step();
+        }
+        let snapshot = world.snapshot();
+        for _ in 0..500 {
+            world.step();
+        }
+        let mut restored = RockWorld::new(jets);
+        restored.restore(&snapshot);
+        assert_eq!(restored.settled_rocks(), 502);
+        for _ in 0..500 {
+            restored.step();
+        }
+        assert_eq!(restored.highest_rock(), world.highest_rock());
+        assert_eq!(restored.snapshot(), world.snapshot());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(Day17::validate(include_str!("../sample.txt")), Ok(()));
+        assert_eq!(
+            Day17::validate("<<>\n>").unwrap_err().to_string(),
+            "Parse error at 1:4: Expected a < or > jet"
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use std::path::PathBuf;
+
+use common::{
+    cli::{Flag, Flags},
+    par, parse, FastSet, Point3, Solution,
+};
+
+pub mod mesh;
+use mesh::Mesh;
+
+pub type Cube = Point3<i32>;
+
+/// Every cube in a box, stored as a list of flags slab by slab along z
+struct Region {
+    min: Cube,
+    size: Cube,
+}
+
+impl Region {
+    /// The box from `min` to `max`, inclusive
+    fn new(min: Cube, max: Cube) -> Self {
+        Self {
+            min,
+            size: max - min + Cube::new(1, 1, 1),
+        }
+    }
+
+    fn len(&self) -> usize {
+        (self.size.x * self.size.y * self.size.z) as usize
+    }
+
+    /// Where a cube's flag is kept, if it's in the box
+    fn index(&self, cube: Cube) -> Option<usize> {
+        let offset = cube - self.min;
+        let inside = |d: i32, size: i32| (0..size).contains(&d);
+        (inside(offset.x, self.size.x)
+            && inside(offset.y, self.size.y)
+            && inside(offset.z, self.size.z))
+        .then(|| ((offset.z * self.size.y + offset.y) * self.size.x + offset.x) as usize)
+    }
+}
+
+/// The number of faces of the cubes that aren't against another cube
+pub fn surface_area(cubes: &FastSet<Cube>) -> usize {
+    par::sum_by(cubes, |cube| {
+        cube.neighbors6()
+            .filter(|side| !cubes.contains(side))
+            .count()
+    })
+}
+
+/// The number of faces of the cubes that can be reached from outside, not counting air
+/// pockets trapped inside the droplet
+pub fn exterior_surface_area(cubes: &FastSet<Cube>) -> usize {
+    exterior_faces(cubes).len()
+}
+
+/// Each face of the cubes that can be reached from outside, as the cube and the direction the
+/// face points in
+pub fn exterior_faces(cubes: &FastSet<Cube>) -> Vec<(Cube, Cube)> {
+    // Find bounds of particle on each axis, leaving a layer of air around it
+    let Some((min, max)) = Cube::bounds(cubes.iter().copied()) else {
+        return Vec::new();
+    };
+    let region = Region::new(min - Cube::new(1, 1, 1), max + Cube::new(1, 1, 1));
+    let mut lava = vec![false; region.len()];
+    for &cube in cubes {
+        lava[region.index(cube).unwrap()] = true;
+    }
+
+    // Flood fill the air around the droplet from a corner, keeping each face of lava it
+    // touches. Each air cube is only visited once, so each face is only found once.
+    let start = region.min;
+    let mut air = vec![false; region.len()];
+    air[region.index(start).unwrap()] = true;
+    let mut frontier = vec![start];
+    let mut faces = Vec::new();
+    while let Some(cube) = frontier.pop() {
+        for side in cube.neighbors6() {
+            match region.index(side) {
+                Some(i) if lava[i] => faces.push((side, cube - side)),
+                Some(i) if !air[i] => {
+                    air[i] = true;
+                    frontier.push(side);
+                }
+                _ => {}
+            }
+        }
+    }
+    faces
+}
+
+/// The scanned cubes of lava, along with where to export a mesh of their outside if the program
+/// was given an `--export PATH` flag
+pub struct Droplet {
+    pub cubes: FastSet<Cube>,
+    pub export: Option<PathBuf>,
+}
+
+pub struct Day18;
+
+common::solution_fns!(Day18);
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    type Input = Droplet;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const FLAGS: &'static [Flag] = &[Flag::with_value("export", "PATH")];
+
+    fn validate(input: &str) -> common::Result<()> {
+        parse::parse_lines::<Cube>(input).map(|_| ())
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        // Parse input points
+        Droplet {
+            cubes: input.lines().map(|line| line.parse().unwrap()).collect(),
+            export: None,
+        }
+    }
+
+    fn parse_with(input: &str, flags: &Flags) -> Result<Self::Input, String> {
+        Ok(Droplet {
+            export: flags.value("export").map(PathBuf::from),
+            ..Self::parse(input)
+        })
+    }
+
+    fn part1(droplet: &Self::Input) -> Self::Part1 {
+        surface_area(&droplet.cubes)
+    }
+
+    fn part2(droplet: &Self::Input) -> Self::Part2 {
+        let faces = exterior_faces(&droplet.cubes);
+        if let Some(path) = &droplet.export {
+            match Mesh::from_faces(faces.iter().copied()).save(path) {
+                Ok(()) => eprintln!("Saved {}", path.display()),
+                Err(err) => eprintln!("Couldn't save {}: {}", path.display(), err),
+            }
+        }
+        faces.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::sample_test;
+
+    fn cubes(points: &[(i32, i32, i32)]) -> FastSet<Cube> {
+        points.iter().map(|&(x, y, z)| Cube::new(x, y, z)).collect()
+    }
+
+    #[test]
+    fn test_two_cubes() {
+        let cubes = cubes(&[(1, 1, 1), (2, 1, 1)]);
+        assert_eq!(surface_area(&cubes), 10);
+        assert_eq!(exterior_surface_area(&cubes), 10);
+        assert_eq!(exterior_surface_area(&FastSet::default()), 0);
+    }
+
+    #[test]
+    fn test_air_pocket() {
+        // A hollow 3x3x3 cube only shows its outside
+        let shell = (0..27)
+            .map(|i| (i % 3, i / 3 % 3, i / 9))
+            .filter(|&p| p != (1, 1, 1))
+            .collect::<Vec<_>>();
+        let shell = cubes(&shell);
+        assert_eq!(surface_area(&shell), 54 + 6);
+        assert_eq!(exterior_surface_area(&shell), 54);
+    }
+
+    #[test]
+    fn test_region_index() {
+        let region = Region::new(Cube::new(-1, -1, -1), Cube::new(2, 3, 4));
+        assert_eq!(region.len(), 4 * 5 * 6);
+        assert_eq!(region.index(Cube::new(-1, -1, -1)), Some(0));
+        assert_eq!(region.index(Cube::new(2, 3, 4)), Some(4 * 5 * 6 - 1));
+        assert_eq!(region.index(Cube::new(3, 0, 0)), None);
+        assert_eq!(region.index(Cube::new(0, -2, 0)), None);
+    }
+
+    sample_test!(test_part1_with_sample, Day18::part1, file "sample.txt", 64);
+    sample_test!(test_part2_with_sample, Day18::part2, file "sample.txt", 58);
+}
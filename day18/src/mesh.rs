@@ -0,0 +1,180 @@
+//! Triangle meshes of the droplet's surface to open in a 3D viewer, e.g `--export droplet.obj`
+
+use std::{fmt::Write, path::Path};
+
+use common::FastMap;
+
+use crate::Cube;
+
+/// Triangles between corners, with each corner only stored once however many triangles meet
+/// at it
+#[derive(Debug, Default)]
+pub struct Mesh {
+    vertices: Vec<Cube>,
+    indices: FastMap<Cube, usize>,
+    triangles: Vec<[usize; 3]>,
+}
+
+impl Mesh {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A mesh of sides of unit cubes, each given by the cube and the direction it faces
+    pub fn from_faces(faces: impl IntoIterator<Item = (Cube, Cube)>) -> Self {
+        let mut mesh = Self::new();
+        for (cube, normal) in faces {
+            mesh.add_face(cube, normal);
+        }
+        mesh
+    }
+
+    /// Add the side of a cube facing `normal` as two triangles, going anticlockwise when seen
+    /// from outside
+    pub fn add_face(&mut self, cube: Cube, normal: Cube) {
+        // The face lies across the other two axes, in the order that makes a right handed set
+        let normal = [normal.x, normal.y, normal.z];
+        let axis = normal
+            .iter()
+            .position(|&n| n != 0)
+            .expect("A face needs a direction");
+        let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+        let mut base = [cube.x, cube.y, cube.z];
+        if normal[axis] > 0 {
+            base[axis] += 1;
+        }
+
+        let mut corners = [(0, 0), (1, 0), (1, 1), (0, 1)].map(|(du, dv)| {
+            let mut corner = base;
+            corner[u] += du;
+            corner[v] += dv;
+            self.vertex(Cube::new(corner[0], corner[1], corner[2]))
+        });
+        if normal[axis] < 0 {
+            corners.reverse();
+        }
+        let [a, b, c, d] = corners;
+        self.triangles.extend([[a, b, c], [a, c, d]]);
+    }
+
+    /// The index of a corner, adding it if it's new
+    fn vertex(&mut self, corner: Cube) -> usize {
+        *self.indices.entry(corner).or_insert_with(|| {
+            self.vertices.push(corner);
+            self.vertices.len() - 1
+        })
+    }
+
+    pub fn vertices(&self) -> &[Cube] {
+        &self.vertices
+    }
+
+    /// Each triangle's corners, as indices into `vertices`
+    pub fn triangles(&self) -> &[[usize; 3]] {
+        &self.triangles
+    }
+
+    /// The mesh as a Wavefront OBJ file, which numbers vertices from 1
+    pub fn to_obj(&self) -> String {
+        let mut obj = String::new();
+        for v in &self.vertices {
+            writeln!(obj, "v {} {} {}", v.x, v.y, v.z).unwrap();
+        }
+        for [a, b, c] in &self.triangles {
+            writeln!(obj, "f {} {} {}", a + 1, b + 1, c + 1).unwrap();
+        }
+        obj
+    }
+
+    /// The mesh as an ASCII STL file, which repeats the corners of every triangle
+    pub fn to_stl(&self) -> String {
+        let mut stl = String::from("solid droplet\n");
+        for triangle in &self.triangles {
+            let [a, b, c] = triangle.map(|i| self.vertices[i]);
+            let n = cross(b - a, c - a);
+            writeln!(stl, "  facet normal {} {} {}", n.x, n.y, n.z).unwrap();
+            stl.push_str("    outer loop\n");
+            for p in [a, b, c] {
+                writeln!(stl, "      vertex {} {} {}", p.x, p.y, p.z).unwrap();
+            }
+            stl.push_str("    endloop\n  endfacet\n");
+        }
+        stl.push_str("endsolid droplet\n");
+        stl
+    }
+
+    /// Save the mesh as an STL file if the path ends in `.stl`, otherwise as an OBJ file
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let stl = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("stl"));
+        std::fs::write(path, if stl { self.to_stl() } else { self.to_obj() })
+    }
+}
+
+fn cross(a: Cube, b: Cube) -> Cube {
+    Cube::new(
+        a.y * b.z - a.z * b.y,
+        a.z * b.x - a.x * b.z,
+        a.x * b.y - a.y * b.x,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{exterior_faces, Day18};
+    use common::{Map, Solution};
+
+    /// The volume inside a closed mesh, six times over so it stays a whole number
+    fn volume_x6(mesh: &Mesh) -> i32 {
+        mesh.triangles()
+            .iter()
+            .map(|t| t.map(|i| mesh.vertices()[i]))
+            .map(|[a, b, c]| {
+                let n = cross(b, c);
+                a.x * n.x + a.y * n.y + a.z * n.z
+            })
+            .sum()
+    }
+
+    /// Whether every edge is walked once in each direction, so the mesh is closed with its
+    /// triangles all facing the same way
+    fn is_closed(mesh: &Mesh) -> bool {
+        let mut edges = Map::<(usize, usize), i32>::default();
+        for &[a, b, c] in mesh.triangles() {
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                *edges.entry((from.min(to), from.max(to))).or_default() +=
+                    if from < to { 1 } else { -1 };
+            }
+        }
+        edges.values().all(|&count| count == 0)
+    }
+
+    #[test]
+    fn test_single_cube() {
+        let cube = Cube::new(2, 0, -1);
+        let mesh = Mesh::from_faces(cube.neighbors6().map(|side| (cube, side - cube)));
+        assert_eq!(mesh.vertices().len(), 8);
+        assert_eq!(mesh.triangles().len(), 12);
+        assert!(is_closed(&mesh));
+        assert_eq!(volume_x6(&mesh), 6);
+
+        let obj = mesh.to_obj();
+        assert_eq!(obj.lines().filter(|l| l.starts_with("v ")).count(), 8);
+        assert_eq!(obj.lines().filter(|l| l.starts_with("f ")).count(), 12);
+        let stl = mesh.to_stl();
+        assert_eq!(stl.matches("facet normal").count(), 12);
+        assert!(stl.contains("facet normal 0 0 -1\n"));
+    }
+
+    #[test]
+    fn test_sample_droplet() {
+        // The outside of the droplet holds its 13 cubes and the pocket of air inside
+        let cubes = Day18::parse(include_str!("../sample.txt")).cubes;
+        let mesh = Mesh::from_faces(exterior_faces(&cubes));
+        assert_eq!(mesh.triangles().len(), 58 * 2);
+        assert!(is_closed(&mesh));
+        assert_eq!(volume_x6(&mesh), (13 + 1) * 6);
+    }
+}
@@ -0,0 +1,330 @@
+use common::{par, parse, AocError, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, multispace0, multispace1},
+    combinator::value,
+    multi::{many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    IResult,
+};
+
+const PT1_MINUTES: u32 = 24;
+const PT2_MINUTES: u32 = 32;
+
+/// How many blueprints the elephants' snacking leaves for part 2
+const PT2_BLUEPRINTS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    Ore,
+    Clay,
+    Obsidian,
+    Geode,
+}
+
+impl Resource {
+    /// Every resource, in the order their robots are tried, best first
+    const ALL: [Resource; 4] = [
+        Resource::Geode,
+        Resource::Obsidian,
+        Resource::Clay,
+        Resource::Ore,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Resource::Ore => "ore",
+            Resource::Clay => "clay",
+            Resource::Obsidian => "obsidian",
+            Resource::Geode => "geode",
+        }
+    }
+}
+
+/// What each kind of robot costs in ore, clay and obsidian. Nothing costs geodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blueprint {
+    pub id: u32,
+    costs: [[u32; 3]; 4],
+    /// The most of each resource that can be spent in a minute, since there's no point
+    /// collecting faster than that
+    max_spend: [u32; 3],
+}
+
+/// The state of the factory between building robots. Geodes are counted as soon as a geode
+/// robot is built, for every minute it will have left, so geode robots aren't kept.
+#[derive(Debug, Clone, Copy)]
+struct State {
+    minutes_left: u32,
+    robots: [u32; 3],
+    resources: [u32; 3],
+    geodes: u32,
+}
+
+impl Blueprint {
+    /// A blueprint from the cost of each robot, as amounts of resources
+    pub fn new(id: u32, robots: &[(Resource, Vec<(u32, Resource)>)]) -> common::Result<Self> {
+        let mut costs = [None; 4];
+        for (robot, cost) in robots {
+            if costs[*robot as usize].is_some() {
+                return Err(AocError::BadInput(format!(
+                    "Blueprint {} has two {} robots",
+                    id,
+                    robot.name()
+                )));
+            }
+            let mut amounts = [0; 3];
+            for &(amount, resource) in cost {
+                if resource == Resource::Geode {
+                    return Err(AocError::BadInput(format!(
+                        "Blueprint {}'s {} robot costs geodes",
+                        id,
+                        robot.name()
+                    )));
+                }
+                amounts[resource as usize] += amount;
+            }
+            costs[*robot as usize] = Some(amounts);
+        }
+
+        let missing = Resource::ALL
+            .into_iter()
+            .find(|&r| costs[r as usize].is_none());
+        if let Some(robot) = missing {
+            return Err(AocError::BadInput(format!(
+                "Blueprint {} has no {} robot",
+                id,
+                robot.name()
+            )));
+        }
+        let costs = costs.map(Option::unwrap);
+        let max_spend = [0, 1, 2].map(|r| costs.iter().map(|cost| cost[r]).max().unwrap());
+        Ok(Self {
+            id,
+            costs,
+            max_spend,
+        })
+    }
+
+    /// The most geodes the blueprint's factory can open in some minutes, starting with one
+    /// ore robot
+    pub fn max_geodes(&self, minutes: u32) -> u32 {
+        let start = State {
+            minutes_left: minutes,
+            robots: [1, 0, 0],
+            resources: [0; 3],
+            geodes: 0,
+        };
+        let mut best = 0;
+        self.search(start, &mut best);
+        best
+    }
+
+    /// Try building each kind of robot next, waiting until it can be afforded. Branches that
+    /// couldn't beat the best so far even by building a geode robot every minute are cut off.
+    fn search(&self, state: State, best: &mut u32) {
+        *best = (*best).max(state.geodes);
+        let t = state.minutes_left;
+        if state.geodes + t * t.saturating_sub(1) / 2 <= *best {
+            return;
+        }
+
+        for robot in Resource::ALL {
+            if robot != Resource::Geode && self.has_enough(&state, robot as usize) {
+                continue;
+            }
+            if let Some(next) = self.build(&state, robot) {
+                self.search(next, best);
+            }
+        }
+    }
+
+    /// Whether another robot collecting a resource would be wasted, as there's already enough
+    /// coming in to spend as much as possible every minute that's left. Any state with more of
+    /// those robots is no better than the one without.
+    fn has_enough(&self, state: &State, resource: usize) -> bool {
+        let t = state.minutes_left;
+        state.robots[resource] >= self.max_spend[resource]
+            || state.resources[resource] + state.robots[resource] * t
+                >= self.max_spend[resource] * t
+    }
+
+    /// Wait until a robot can be afforded then build it, if there's time for it to collect
+    /// anything afterwards
+    fn build(&self, state: &State, robot: Resource) -> Option<State> {
+        let cost = self.costs[robot as usize];
+        let mut wait = 0;
+        for (r, &needed) in cost.iter().enumerate() {
+            if needed > state.resources[r] {
+                if state.robots[r] == 0 {
+                    return None;
+                }
+                wait = wait.max((needed - state.resources[r]).div_ceil(state.robots[r]));
+            }
+        }
+
+        // It takes a minute to build once it's affordable
+        let elapsed = wait + 1;
+        if elapsed >= state.minutes_left {
+            return None;
+        }
+        let mut next = State {
+            minutes_left: state.minutes_left - elapsed,
+            resources: [0, 1, 2].map(|r| state.resources[r] + state.robots[r] * elapsed - cost[r]),
+            ..*state
+        };
+        match robot {
+            Resource::Geode => next.geodes += next.minutes_left,
+            _ => next.robots[robot as usize] += 1,
+        }
+        Some(next)
+    }
+
+    /// The blueprint's id times the most geodes it can open in 24 minutes
+    pub fn quality_level(&self) -> u32 {
+        self.id * self.max_geodes(PT1_MINUTES)
+    }
+}
+
+/// Read every blueprint, which may be wrapped over several lines
+pub fn parse_blueprints(input: &str) -> common::Result<Vec<Blueprint>> {
+    parse::parse_all(
+        delimited(
+            multispace0,
+            many1(terminated(blueprint, multispace0)),
+            multispace0,
+        ),
+        input,
+    )?
+    .into_iter()
+    .map(|(id, robots)| Blueprint::new(id, &robots))
+    .collect()
+}
+
+pub struct Day19;
+
+common::solution_fns!(Day19);
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    type Input = Vec<Blueprint>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn validate(input: &str) -> common::Result<()> {
+        parse_blueprints(input).map(|_| ())
+    }
+
+    fn parse(input: &str) -> Self::Input {
+        parse_blueprints(input).unwrap()
+    }
+
+    fn part1(blueprints: &Self::Input) -> Self::Part1 {
+        // Sum quality levels
+        par::sum_by(blueprints, |blueprint| blueprint.quality_level() as usize) as u32
+    }
+
+    fn part2(blueprints: &Self::Input) -> Self::Part2 {
+        // Multiply the geodes opened by the blueprints that are left, given longer
+        blueprints
+            .iter()
+            .take(PT2_BLUEPRINTS)
+            .map(|blueprint| blueprint.max_geodes(PT2_MINUTES))
+            .product()
+    }
+}
+
+/* Parsing */
+
+type RobotCost = (Resource, Vec<(u32, Resource)>);
+
+fn blueprint(input: &str) -> IResult<&str, (u32, Vec<RobotCost>)> {
+    pair(
+        delimited(tag("Blueprint "), parse::unsigned_int, char(':')),
+        many1(preceded(multispace1, robot)),
+    )(input)
+}
+
+/// A robot and what it costs, like `Each obsidian robot costs 3 ore and 14 clay.`
+fn robot(input: &str) -> IResult<&str, RobotCost> {
+    let (input, (_, robot, _)) = tuple((
+        pair(tag("Each"), multispace1),
+        resource,
+        tuple((
+            multispace1,
+            tag("robot"),
+            multispace1,
+            tag("costs"),
+            multispace1,
+        )),
+    ))(input)?;
+    let (input, cost) = terminated(
+        separated_list1(
+            delimited(multispace1, tag("and"), multispace1),
+            separated_pair(parse::unsigned_int, multispace1, resource),
+        ),
+        char('.'),
+    )(input)?;
+    Ok((input, (robot, cost)))
+}
+
+fn resource(input: &str) -> IResult<&str, Resource> {
+    alt((
+        value(Resource::Ore, tag("ore")),
+        value(Resource::Clay, tag("clay")),
+        value(Resource::Obsidian, tag("obsidian")),
+        value(Resource::Geode, tag("geode")),
+    ))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::sample_test;
+
+    #[test]
+    fn test_parse_blueprint() {
+        let blueprints = Day19::parse(include_str!("../sample.txt"));
+        assert_eq!(blueprints.len(), 2);
+        assert_eq!(blueprints[1].id, 2);
+        assert_eq!(
+            blueprints[1].costs,
+            [[2, 0, 0], [3, 0, 0], [3, 8, 0], [3, 0, 12]]
+        );
+        assert_eq!(blueprints[1].max_spend, [3, 8, 12]);
+
+        // The puzzle's example wraps each blueprint over several lines
+        let wrapped = include_str!("../sample.txt").replace(". ", ".\n  ");
+        assert_eq!(Day19::parse(&wrapped), blueprints);
+    }
+
+    #[test]
+    fn test_bad_blueprints() {
+        let err = parse_blueprints(
+            "Blueprint 1: Each ore robot costs 4 ore.\nBlueprint 2: Each ore robot costs 4 gold.",
+        )
+        .unwrap_err();
+        assert!(matches!(err, AocError::Parse { line: 2, .. }), "{}", err);
+
+        let err = parse_blueprints("Blueprint 7: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each geode robot costs 2 ore and 7 clay.")
+            .unwrap_err();
+        assert_eq!(
+            err,
+            AocError::BadInput("Blueprint 7 has no obsidian robot".into())
+        );
+    }
+
+    #[test]
+    fn test_max_geodes() {
+        let blueprints = Day19::parse(include_str!("../sample.txt"));
+        assert_eq!(blueprints[0].max_geodes(PT1_MINUTES), 9);
+        assert_eq!(blueprints[1].max_geodes(PT1_MINUTES), 12);
+        assert_eq!(blueprints[0].max_geodes(PT2_MINUTES), 56);
+        assert_eq!(blueprints[1].max_geodes(PT2_MINUTES), 62);
+        assert_eq!(blueprints[0].max_geodes(0), 0);
+    }
+
+    sample_test!(test_part1_with_sample, Day19::part1, file "sample.txt", 33);
+    sample_test!(test_part2_with_sample, Day19::part2, file "sample.txt", 56 * 62);
+}
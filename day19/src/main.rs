@@ -0,0 +1,3 @@
+fn main() {
+    common::cli::run::<day19::Day19>(common::sample_path!());
+}
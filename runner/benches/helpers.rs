@@ -0,0 +1,77 @@
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, Hash},
+};
+
+use common::{top_k, FastMap, Map, Point3};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// A large list of scattered numbers, from a simple linear congruential generator
+fn scattered(len: usize) -> Vec<u64> {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    (0..len)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            state >> 33
+        })
+        .collect()
+}
+
+/// Taking the largest few values with a bounded heap vs sorting everything
+fn top_three(c: &mut Criterion) {
+    let mut group = c.benchmark_group("top 3");
+    for len in [1_000, 100_000, 1_000_000] {
+        let values = scattered(len);
+        group.bench_with_input(BenchmarkId::new("top_k", len), &values, |b, values| {
+            b.iter(|| top_k(black_box(values).iter().copied(), 3))
+        });
+        group.bench_with_input(BenchmarkId::new("sort", len), &values, |b, values| {
+            b.iter(|| {
+                let mut values = black_box(values).clone();
+                values.sort();
+                values.into_iter().rev().take(3).collect::<Vec<_>>()
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Fill an empty map with every key then look each of them up, like the cube and cell lookups
+/// in the simulation days
+fn fill_and_lookup<K: Hash + Eq + Copy, S: BuildHasher>(
+    mut map: HashMap<K, usize, S>,
+    keys: &[K],
+) -> usize {
+    for (i, &key) in keys.iter().enumerate() {
+        map.insert(key, i);
+    }
+    keys.iter().filter_map(|key| map.get(key)).sum()
+}
+
+/// std's default hasher against the deterministic and fast maps from common
+fn hash_maps(c: &mut Criterion) {
+    let cubes: Vec<Point3<i32>> = scattered(30_000)
+        .chunks(3)
+        .map(|c| Point3::new(c[0] as i32 % 64, c[1] as i32 % 64, c[2] as i32 % 64))
+        .collect();
+    let mut group = c.benchmark_group("hash map of cubes");
+    group.bench_function("std", |b| {
+        b.iter(|| fill_and_lookup(HashMap::new(), black_box(&cubes)))
+    });
+    group.bench_function("Map", |b| {
+        b.iter(|| fill_and_lookup(Map::default(), black_box(&cubes)))
+    });
+    group.bench_function("FastMap", |b| {
+        b.iter(|| fill_and_lookup(FastMap::default(), black_box(&cubes)))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    name = helpers;
+    config = Criterion::default().sample_size(10);
+    targets = top_three, hash_maps
+);
+criterion_main!(helpers);
@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use common::{input_path, Day, Set, Solution, YEAR};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// The inputs available for a day, the sample and the real input if it has been downloaded
+fn inputs(day: u8) -> Vec<(&'static str, String)> {
+    let dir = Day::new(day).unwrap().dir_name();
+    let sample = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(dir)
+        .join("sample.txt");
+    [("sample", sample), ("input", input_path(YEAR, day))]
+        .into_iter()
+        .flat_map(|(name, path)| {
+            std::fs::read_to_string(path)
+                .ok()
+                .map(|input| (name, input))
+        })
+        .collect()
+}
+
+/// Benchmark parsing then running a single part of a solution over each of its inputs
+fn bench_part<S: Solution, T>(c: &mut Criterion, name: &str, day: u8, part: fn(&S::Input) -> T) {
+    let mut group = c.benchmark_group(name);
+    for (input_name, input) in inputs(day) {
+        let parsed = S::parse(&input);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(input_name),
+            &parsed,
+            |b, parsed| b.iter(|| part(parsed)),
+        );
+    }
+    group.finish();
+}
+
+/// Benchmark a day's `part1` or `part2` function, parsing included, over each of its inputs
+fn bench_raw<T>(c: &mut Criterion, name: &str, day: u8, part: fn(&str) -> T) {
+    let mut group = c.benchmark_group(name);
+    for (input_name, input) in inputs(day) {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(input_name),
+            input.as_str(),
+            |b, input| b.iter(|| part(input)),
+        );
+    }
+    group.finish();
+}
+
+/// Find each group's badge by intersecting sets of their items, as day 3 used to
+fn badges_with_sets(input: &str) -> usize {
+    input
+        .lines()
+        .collect::<Vec<_>>()
+        .chunks_exact(3)
+        .map(|group| {
+            let badge = group
+                .iter()
+                .map(|items| items.chars().collect::<Set<_>>())
+                .reduce(|a, b| a.intersection(&b).copied().collect())
+                .and_then(|set| set.into_iter().next())
+                .unwrap();
+            day3::Rucksack::item_priority(badge) as usize
+        })
+        .sum()
+}
+
+fn day03_rucksacks(c: &mut Criterion) {
+    // Against the sets of items rucksacks used to hold, on a thousand copies of the sample
+    let sample = inputs(3).into_iter().next().unwrap().1;
+    let input = sample.repeat(1000);
+    let mut group = c.benchmark_group("day03 badges (6000 rucksacks)");
+    group.bench_function("bitmasks", |b| b.iter(|| day3::part2(&input)));
+    group.bench_function("hash sets", |b| b.iter(|| badges_with_sets(&input)));
+    group.finish();
+}
+
+fn day08_visibility(c: &mut Criterion) {
+    bench_part::<day8::Day8, _>(c, "day08 visibility", 8, day8::Day8::part1);
+
+    // On a forest much bigger than the puzzle's
+    let forest = day8::Forest::synthetic(1000);
+    c.bench_function("day08 scenic scores (1000x1000)", |b| {
+        b.iter(|| day8::Day8::part2(&forest))
+    });
+}
+
+fn day11_monkeys(c: &mut Criterion) {
+    bench_raw(c, "day11 parse and play rounds", 11, day11::part2);
+}
+
+fn day12_pathfinding(c: &mut Criterion) {
+    bench_part::<day12::Day12, _>(c, "day12 path from start", 12, day12::Day12::part1);
+    bench_part::<day12::Day12, _>(c, "day12 path from any a", 12, day12::Day12::part2);
+}
+
+fn day13_packets(c: &mut Criterion) {
+    bench_raw(c, "day13 parse and find decoder key", 13, day13::part2);
+}
+
+fn day14_sand(c: &mut Criterion) {
+    bench_part::<day14::Day14, _>(c, "day14 sand into abyss", 14, day14::Day14::part1);
+    bench_part::<day14::Day14, _>(c, "day14 sand onto floor", 14, day14::Day14::part2);
+
+    // Against the sparse map the world used to be stored in
+    bench_part::<day14::Day14, _>(c, "day14 sand onto floor (sparse)", 14, |cave| {
+        day14::pour_sand::<common::SparseGrid<day14::SandCell>>(&cave.rock_sequences, true)
+    });
+}
+
+fn day16_valves(c: &mut Criterion) {
+    bench_part::<day16::Day16, _>(c, "day16 valve search", 16, day16::Day16::part1);
+}
+
+fn day17_rocks(c: &mut Criterion) {
+    bench_part::<day17::Day17, _>(c, "day17 rock dropping", 17, day17::Day17::part1);
+}
+
+criterion_group!(
+    name = solvers;
+    config = Criterion::default().sample_size(10);
+    targets = day03_rucksacks, day08_visibility, day11_monkeys, day12_pathfinding, day13_packets, day14_sand, day16_valves, day17_rocks
+);
+criterion_main!(solvers);
@@ -0,0 +1,72 @@
+//! Registers every day crate the runner depends on, so there's no list of days to keep up to date
+
+use std::{env, fmt::Write, fs, path::Path};
+
+// Share the day naming rules with `common` without building all of it for the build script
+#[allow(dead_code)]
+#[path = "../common/src/day.rs"]
+mod day;
+
+use day::Day;
+
+fn main() {
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    println!("cargo:rerun-if-changed=../common/src/day.rs");
+
+    // Find the dependencies named after a day, in either naming scheme
+    let manifest: toml::Value = fs::read_to_string("Cargo.toml")
+        .expect("Couldn't read the runner's Cargo.toml")
+        .parse()
+        .expect("Invalid Cargo.toml");
+    let mut days: Vec<(Day, &String)> = manifest["dependencies"]
+        .as_table()
+        .expect("The runner has no dependencies")
+        .keys()
+        .filter_map(|name| Some((Day::from_crate_name(name)?, name)))
+        .collect();
+    days.sort();
+
+    // Generate the list of days and a macro matching each to its solution
+    let mut code = String::new();
+    let numbers: Vec<String> = days
+        .iter()
+        .map(|(day, _)| day.number().to_string())
+        .collect();
+    writeln!(
+        code,
+        "const DAY_NUMBERS: &[u8] = &[{}];",
+        numbers.join(", ")
+    )
+    .unwrap();
+    writeln!(code, "macro_rules! with_solution {{").unwrap();
+    writeln!(
+        code,
+        "    ($day: expr, $solution: ident => $body: expr) => {{"
+    )
+    .unwrap();
+    writeln!(code, "        match $day.number() {{").unwrap();
+    for (day, name) in &days {
+        writeln!(
+            code,
+            "            {} => with_solution!(@day {}::Day{}, $solution => $body),",
+            day.number(),
+            name,
+            day.number()
+        )
+        .unwrap();
+    }
+    writeln!(
+        code,
+        "            day => Err(format!(\"Day {{}} has no registered solution\", day)),"
+    )
+    .unwrap();
+    writeln!(code, "        }}\n    }};").unwrap();
+    writeln!(
+        code,
+        "    (@day $ty: ty, $solution: ident => $body: expr) => {{{{\n        type $solution = $ty;\n        Ok($body)\n    }}}};\n}}"
+    )
+    .unwrap();
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(out, code).expect("Couldn't write the registered days");
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use common::{input_path, read_input, Day, Part, YEAR};
+use runner::{days, solve_day};
+
+/// Recorded answers for each part of a day
+type Answers = HashMap<u8, [Option<String>; 2]>;
+
+/// The result of checking a single part against its recorded answer
+enum Check {
+    Pass,
+    Fail { expected: String, actual: String },
+    NoAnswer,
+    NoInput,
+}
+
+impl std::fmt::Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Pass => write!(f, "pass"),
+            Check::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {}, got {})", expected, actual)
+            }
+            Check::NoAnswer => write!(f, "-"),
+            Check::NoInput => write!(f, "no input"),
+        }
+    }
+}
+
+/// Read answers from a toml file with a table per day, e.g `[1]` then `part1 = "24000"`
+fn parse_answers(s: &str) -> Result<Answers, String> {
+    let table = s
+        .parse::<toml::Value>()
+        .map_err(|err| format!("Invalid answers file: {}", err))?;
+    let table = table.as_table().ok_or("Answers file must be a table")?;
+    table
+        .iter()
+        .map(|(day, parts)| {
+            let day = day
+                .parse()
+                .map_err(|_| format!("Expected a day number, found {}", day))?;
+            let answer = |key: &str| {
+                parts.get(key).map(|value| match value {
+                    toml::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+            };
+            Ok((day, [answer("part1"), answer("part2")]))
+        })
+        .collect()
+}
+
+/// Run a day against its real input and check each part against the recorded answers
+fn verify_day(day: Day, recorded: &[Option<String>; 2]) -> Result<[Check; 2], String> {
+    let path = input_path(YEAR, day.number());
+    if !path.exists() {
+        return Ok([Check::NoInput, Check::NoInput]);
+    }
+    let part = match recorded {
+        [Some(_), Some(_)] => Part::Both,
+        [Some(_), None] => Part::One,
+        [None, Some(_)] => Part::Two,
+        [None, None] => return Ok([Check::NoAnswer, Check::NoAnswer]),
+    };
+    let answers = solve_day(day, &read_input(path, Some((YEAR, day.number()))), part)?;
+    Ok([1, 2].map(|part| {
+        let expected = &recorded[part as usize - 1];
+        let actual = answers.iter().find(|(p, _)| *p == part).map(|(_, a)| a);
+        match (expected, actual) {
+            (Some(expected), Some(actual)) if expected == actual => Check::Pass,
+            (Some(expected), Some(actual)) => Check::Fail {
+                expected: expected.clone(),
+                actual: actual.clone(),
+            },
+            _ => Check::NoAnswer,
+        }
+    }))
+}
+
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "./answers.toml".to_owned());
+    let answers = std::fs::read_to_string(&path)
+        .map_err(|err| format!("Couldn't read {}: {}", path, err))
+        .and_then(|s| parse_answers(&s))
+        .unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        });
+
+    println!("{:>4}  {:<30} {:<30}", "Day", "Part 1", "Part 2");
+    let mut failed = false;
+    for day in days() {
+        let recorded = answers.get(&day.number()).cloned().unwrap_or_default();
+        let checks = verify_day(day, &recorded).unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        });
+        failed |= checks
+            .iter()
+            .any(|check| matches!(check, Check::Fail { .. }));
+        println!(
+            "{:>4}  {:<30} {:<30}",
+            day,
+            checks[0].to_string(),
+            checks[1].to_string()
+        );
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers =
+            parse_answers("[1]\npart1 = \"24000\"\npart2 = 45000\n\n[10]\npart1 = 13140\n")
+                .unwrap();
+        assert_eq!(
+            answers[&1],
+            [Some("24000".to_owned()), Some("45000".to_owned())]
+        );
+        assert_eq!(answers[&10], [Some("13140".to_owned()), None]);
+        assert!(parse_answers("[one]\npart1 = 1").is_err());
+    }
+}
@@ -0,0 +1,42 @@
+//! Runs any registered day, shared by the runner and verify binaries
+
+use common::{
+    bench,
+    cli::{Flag, Flags},
+    run, solve, Day, Part, Solution,
+};
+
+// Defines `DAY_NUMBERS` and `with_solution!`, which evaluates an expression with `$solution`
+// naming the solution type for a day. Both are generated from the day crates in Cargo.toml.
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+/// Every day with a registered solution, in order
+pub fn days() -> impl Iterator<Item = Day> {
+    DAY_NUMBERS.iter().filter_map(|&day| Day::new(day))
+}
+
+/// The flags a registered day accepts besides the shared ones
+pub fn day_flags(day: Day) -> Result<&'static [Flag], String> {
+    with_solution!(day, S => S::FLAGS)
+}
+
+/// Run a single part (or both parts) of a registered day with the flags it was given, printing
+/// and returning the answers
+pub fn run_day(
+    day: Day,
+    input: &str,
+    part: Part,
+    flags: &Flags,
+) -> Result<Vec<(u8, String)>, String> {
+    with_solution!(day, S => run::<S>(input, part, flags))?
+}
+
+/// Find the answers to a registered day without printing them
+pub fn solve_day(day: Day, input: &str, part: Part) -> Result<Vec<(u8, String)>, String> {
+    with_solution!(day, S => solve::<S>(input, part))
+}
+
+/// Time every stage of a registered day
+pub fn bench_day(day: Day, input: &str) -> Result<bench::Timings, String> {
+    with_solution!(day, S => bench::time::<S>(input))
+}
@@ -0,0 +1,133 @@
+mod report;
+
+use common::{alloc::TrackingAllocator, cli, input_path, read_input, submit, Day, Part};
+use report::SortBy;
+use runner::{bench_day, day_flags, days, run_day};
+
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+struct Args {
+    day: Option<Day>,
+    submit: bool,
+    bench: bool,
+    sort: SortBy,
+    /// The input, part, year and flags, parsed the same way as for a day's own binary
+    cli: cli::Args,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut day = None;
+    let mut submit = false;
+    let mut bench = false;
+    let mut sort = SortBy::Day;
+
+    // Take the runner's own arguments, leaving the rest for `common::cli`
+    let mut rest = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--day" => day = Some(value()?.parse()?),
+            "--submit" => submit = true,
+            "--bench" => bench = true,
+            "--sort" => sort = value()?.parse()?,
+            _ => rest.push(arg),
+        }
+    }
+
+    // The day's own flags are accepted alongside the shared ones
+    let flags = match day {
+        Some(day) => day_flags(day)?,
+        None => &[],
+    };
+    let cli = cli::parse_from(rest, flags).unwrap_or_else(|err| exit_with_usage(&err, day));
+    if cli.flags.sample {
+        return Err("--sample only works when running a day on its own".to_owned());
+    }
+
+    if submit && cli.part == Part::Both {
+        return Err("Choose a single --part to submit".to_owned());
+    }
+    if day.is_none() && !bench {
+        return Err("The --day argument is required".to_owned());
+    }
+
+    Ok(Args {
+        day,
+        submit,
+        bench,
+        sort,
+        cli,
+    })
+}
+
+/// Print an error and the usage, listing a day's own flags if it's known, then exit
+fn exit_with_usage(err: &str, day: Option<Day>) -> ! {
+    let flags = day.and_then(|day| day_flags(day).ok()).unwrap_or(&[]);
+    eprintln!("{}", err);
+    eprintln!(
+        "Usage: runner --day <N> [--year <YYYY>] [--part 1|2|both] [--input PATH] [--submit] {}",
+        cli::flags_usage(flags)
+    );
+    eprintln!("       runner --bench [--year <YYYY>] [--day <N>] [--sort day|parse|part1|part2|total|memory|allocs]");
+    std::process::exit(1);
+}
+
+/// Benchmark the given day, or every day with an input available
+fn bench(args: &Args) -> Result<(), String> {
+    let days = match args.day {
+        Some(day) => vec![day],
+        None => days()
+            .filter(|day| input_path(args.cli.year, day.number()).exists())
+            .collect(),
+    };
+
+    let mut timings = Vec::new();
+    for day in days {
+        let path = match &args.cli.input {
+            Some(path) => path.into(),
+            None => input_path(args.cli.year, day.number()),
+        };
+        eprintln!("Running day {}...", day);
+        let input = read_input(path, Some((args.cli.year, day.number())));
+        timings.push(bench_day(day, &input)?);
+    }
+    report::print_table(timings, args.sort);
+    Ok(())
+}
+
+fn main() {
+    let args = parse_args().unwrap_or_else(|err| exit_with_usage(&err, None));
+    common::log::init(args.cli.flags.is_set("verbose"));
+
+    if args.bench {
+        if let Err(err) = bench(&args) {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Inputs are kept in `inputs/{year}/{day}.txt`
+    let day = args.day.unwrap();
+    let path = args
+        .cli
+        .input
+        .as_deref()
+        .map_or_else(|| input_path(args.cli.year, day.number()), Into::into);
+    let input = read_input(path, Some((args.cli.year, day.number())));
+
+    let answers = run_day(day, &input, args.cli.part, &args.cli.flags)
+        .unwrap_or_else(|err| exit_with_usage(&err, Some(day)));
+
+    // Submit answers to adventofcode.com
+    if args.submit {
+        for (part, answer) in answers {
+            match submit(args.cli.year, day.number(), part, &answer) {
+                Ok(outcome) => println!("[PT{}] Submitted {}: {}", part, answer, outcome),
+                Err(err) => eprintln!("[PT{}] Couldn't submit {}: {}", part, answer, err),
+            }
+        }
+    }
+}
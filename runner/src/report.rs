@@ -0,0 +1,112 @@
+use std::{str::FromStr, time::Duration};
+
+use common::{alloc::AllocStats, bench::Timings};
+
+/// Column to order the benchmark table by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortBy {
+    Day,
+    Parse,
+    Part1,
+    Part2,
+    Total,
+    Memory,
+    Allocs,
+}
+
+impl FromStr for SortBy {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(SortBy::Day),
+            "parse" => Ok(SortBy::Parse),
+            "1" | "part1" => Ok(SortBy::Part1),
+            "2" | "part2" => Ok(SortBy::Part2),
+            "total" => Ok(SortBy::Total),
+            "memory" => Ok(SortBy::Memory),
+            "allocs" => Ok(SortBy::Allocs),
+            _ => Err("Sort must be one of day, parse, part1, part2, total, memory or allocs"),
+        }
+    }
+}
+
+/// Print a table of timings, slowest first unless sorted by day
+pub fn print_table(mut timings: Vec<Timings>, sort_by: SortBy) {
+    match sort_by {
+        SortBy::Day => timings.sort_by_key(|t| t.day),
+        SortBy::Parse => timings.sort_by_key(|t| std::cmp::Reverse(t.parse)),
+        SortBy::Part1 => timings.sort_by_key(|t| std::cmp::Reverse(t.part1)),
+        SortBy::Part2 => timings.sort_by_key(|t| std::cmp::Reverse(t.part2)),
+        SortBy::Total => timings.sort_by_key(|t| std::cmp::Reverse(t.total())),
+        SortBy::Memory => timings.sort_by_key(|t| std::cmp::Reverse(t.peak_bytes())),
+        SortBy::Allocs => timings.sort_by_key(|t| std::cmp::Reverse(t.allocations())),
+    }
+
+    println!(
+        "{:>5} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        "Day", "Parse", "Part 1", "Part 2", "Total", "P1 allocs", "P1 peak", "P2 allocs", "P2 peak"
+    );
+    for t in &timings {
+        let [_, part1_allocs, part2_allocs] = t.allocs;
+        let count = |a: Option<AllocStats>| a.map_or("-".into(), |a| a.count.to_string());
+        let peak = |a: Option<AllocStats>| a.map_or("-".into(), |a| format_bytes(a.peak_bytes));
+        println!(
+            "{:>5} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            t.day,
+            format_duration(t.parse),
+            format_duration(t.part1),
+            format_duration(t.part2),
+            format_duration(t.total()),
+            count(part1_allocs),
+            peak(part1_allocs),
+            count(part2_allocs),
+            peak(part2_allocs),
+        );
+    }
+
+    let sum = |f: fn(&Timings) -> Duration| timings.iter().map(f).sum::<Duration>();
+    println!(
+        "{:>5} {:>10} {:>10} {:>10} {:>10}",
+        "Total",
+        format_duration(sum(|t| t.parse)),
+        format_duration(sum(|t| t.part1)),
+        format_duration(sum(|t| t.part2)),
+        format_duration(sum(|t| t.total())),
+    );
+}
+
+fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros < 1_000 {
+        format!("{}µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.2}ms", micros as f64 / 1_000.0)
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{}B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1}KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1}MiB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formatting() {
+        assert_eq!(format_duration(Duration::from_micros(42)), "42µs");
+        assert_eq!(format_duration(Duration::from_micros(1_500)), "1.50ms");
+        assert_eq!(format_duration(Duration::from_millis(2_250)), "2.25s");
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0MiB");
+    }
+}
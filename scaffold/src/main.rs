@@ -0,0 +1,198 @@
+//! Creates the crate for a new day and registers it with the workspace and runner, e.g
+//! `cargo run -p scaffold -- 19`
+
+use std::{fs, path::Path};
+
+use common::Day;
+
+const CARGO_TOML: &str = r#"[package]
+name = "day{day}"
+version = "0.1.0"
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+common = { version = "0.1.0", path = "../common" }
+"#;
+
+const LIB_RS: &str = r#"use common::Solution;
+
+pub struct Day{day};
+
+common::solution_fns!(Day{day});
+
+impl Solution for Day{day} {
+    const DAY: u8 = {day};
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Self::Input {
+        input.lines().map(str::to_owned).collect()
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        todo!("part 1 with {} lines", input.len())
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        todo!("part 2 with {} lines", input.len())
+    }
+}
+
+#[cfg(test)]
+mod test_with_sample {
+    use super::*;
+    use common::sample_test;
+
+    // Paste the puzzle's example into sample.txt and fill in its answer
+    sample_test!(test_part1_with_sample, Day{day}::part1, file "sample.txt", 0);
+}
+"#;
+
+const MAIN_RS: &str = r#"fn main() {
+    common::cli::run::<day{day}::Day{day}>(common::sample_path!());
+}
+"#;
+
+/// Fill in a template for a day
+fn fill(template: &str, day: Day) -> String {
+    template.replace("{day}", &day.to_string())
+}
+
+/// Insert a line before the first line which `before` matches
+fn insert_before(s: &str, line: &str, before: impl Fn(&str) -> bool) -> Option<String> {
+    let index = s.lines().position(before)?;
+    let mut lines: Vec<&str> = s.lines().collect();
+    lines.insert(index, line);
+    Some(lines.join("\n") + "\n")
+}
+
+/// Add a day's crate to the workspace members, before the runner
+fn register_member(manifest: &str, day: Day) -> Result<String, String> {
+    let member = format!("    \"{}\",", day.dir_name());
+    if manifest.lines().any(|line| line == member) {
+        return Err(format!("{} is already a workspace member", day.dir_name()));
+    }
+    insert_before(manifest, &member, |line| line.trim() == "\"runner\",")
+        .ok_or_else(|| "Couldn't find the runner in the workspace members".to_owned())
+}
+
+/// Add a day's crate to the runner's dependencies, after the other days.
+/// The runner's build script registers any dependency named after a day.
+fn register_dependency(manifest: &str, day: Day) -> Result<String, String> {
+    let dependency_day = |line: &str| Day::from_crate_name(line.split(" = ").next()?);
+    if manifest
+        .lines()
+        .any(|line| dependency_day(line) == Some(day))
+    {
+        return Err(format!(
+            "The runner already depends on {}",
+            day.crate_name()
+        ));
+    }
+    let dependency = format!(
+        "{} = {{ version = \"0.1.0\", path = \"../{}\" }}",
+        day.crate_name(),
+        day.dir_name()
+    );
+    let mut lines: Vec<&str> = manifest.lines().collect();
+    let last = lines
+        .iter()
+        .rposition(|line| dependency_day(line).is_some())
+        .ok_or("Couldn't find the days in the runner's dependencies")?;
+    lines.insert(last + 1, &dependency);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("Couldn't read {}: {}", path.display(), err))
+}
+
+fn write(path: &Path, contents: String) -> Result<(), String> {
+    fs::write(path, contents).map_err(|err| format!("Couldn't write {}: {}", path.display(), err))
+}
+
+fn scaffold(root: &Path, day: Day) -> Result<(), String> {
+    let dir = root.join(day.dir_name());
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    // Work out the registrations before writing anything, so a failure leaves nothing half done
+    let workspace_path = root.join("Cargo.toml");
+    let runner_path = root.join("runner/Cargo.toml");
+    let workspace = register_member(&read(&workspace_path)?, day)?;
+    let runner = register_dependency(&read(&runner_path)?, day)?;
+
+    // Create the crate
+    fs::create_dir_all(dir.join("src"))
+        .map_err(|err| format!("Couldn't create {}: {}", dir.display(), err))?;
+    write(&dir.join("Cargo.toml"), fill(CARGO_TOML, day))?;
+    write(&dir.join("src/lib.rs"), fill(LIB_RS, day))?;
+    write(&dir.join("src/main.rs"), fill(MAIN_RS, day))?;
+    write(&dir.join("sample.txt"), String::new())?;
+
+    // Register it
+    write(&workspace_path, workspace)?;
+    write(&runner_path, runner)?;
+
+    println!(
+        "Created {} (run it with `cargo run -p day{}`)",
+        dir.display(),
+        day
+    );
+    Ok(())
+}
+
+fn main() {
+    let day = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse::<Day>().ok())
+        .unwrap_or_else(|| {
+            eprintln!("Usage: scaffold <DAY>, where DAY is from 1 to 25");
+            std::process::exit(1);
+        });
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    if let Err(err) = scaffold(root, day) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(day: u8) -> Day {
+        Day::new(day).unwrap()
+    }
+
+    #[test]
+    fn test_register_member() {
+        let manifest = "members = [\n    \"day18\",\n    \"runner\",\n]\n";
+        assert_eq!(
+            register_member(manifest, day(19)).unwrap(),
+            "members = [\n    \"day18\",\n    \"day19\",\n    \"runner\",\n]\n"
+        );
+        assert!(register_member(manifest, day(18)).is_err());
+    }
+
+    #[test]
+    fn test_register_dependency() {
+        let manifest = "[dependencies]\nday9 = { version = \"0.1.0\", path = \"../day09\" }\ntoml = \"0.5.9\"\n";
+        assert_eq!(
+            register_dependency(manifest, day(19)).unwrap(),
+            "[dependencies]\nday9 = { version = \"0.1.0\", path = \"../day09\" }\nday19 = { version = \"0.1.0\", path = \"../day19\" }\ntoml = \"0.5.9\"\n"
+        );
+        assert!(register_dependency(manifest, day(9)).is_err());
+        assert!(register_dependency("day05 = { path = \"../day05\" }", day(5)).is_err());
+    }
+
+    #[test]
+    fn test_fill() {
+        assert!(fill(LIB_RS, day(7)).contains("impl Solution for Day7 {\n    const DAY: u8 = 7;"));
+    }
+}